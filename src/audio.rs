@@ -1,16 +1,29 @@
-use bevy::{audio::Volume, prelude::*};
+use bevy::{
+    audio::{Decodable, Volume},
+    prelude::*,
+};
+
+use crate::settings::AudioSettings;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<BgmConfig>();
+    app.add_message::<RestartAudio>();
     app.add_systems(
         Update,
         (
-            apply_global_volume.run_if(resource_changed::<GlobalVolume>),
-            bgm_config_changed.run_if(resource_changed::<BgmConfig>),
-        ),
+            restart_audio.run_if(on_message::<RestartAudio>),
+            apply_global_volume
+                .run_if(resource_changed::<GlobalVolume>.or(resource_changed::<AudioSettings>)),
+            apply_category_volume_to_new_sounds,
+            fade_music_layers,
+        )
+            .chain(),
     );
 }
 
+/// How long a music layer takes to fade fully in or out.
+const MUSIC_FADE_SECONDS: f32 = 1.5;
+
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
 /// general "music" category (e.g. global background music, soundtrack).
 ///
@@ -19,16 +32,40 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 pub struct Music;
 
-#[derive(Debug, Component)]
+#[derive(Debug, Component, Clone, Copy)]
+#[require(MusicLayerVolume)]
 pub enum MusicLayer {
     Base,
     Extra,
     Perc,
 }
 
-/// A music audio instance.
-pub fn music(handle: Handle<AudioSource>) -> impl Bundle {
-    (AudioPlayer(handle), PlaybackSettings::LOOP, Music)
+/// How loud a [`MusicLayer`] currently is from 0 to 1, before [`GlobalVolume`] and
+/// [`AudioSettings::music_volume`]. Moves towards
+/// whether the layer is enabled in [`BgmConfig`] so layers fade in and out rather than cutting.
+#[derive(Debug, Component, Default)]
+pub struct MusicLayerVolume(f32);
+
+/// Marks a [`MusicLayer`] that is on its way out. It fades to silence regardless of
+/// [`BgmConfig`], then despawns, so the music of the next screen can fade in over it.
+#[derive(Debug, Component)]
+pub struct FadingOut;
+
+/// A layer of the background music, starting silent and fading in if enabled in [`BgmConfig`].
+pub fn music_layer(handle: Handle<AudioSource>, layer: MusicLayer) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::LOOP.with_volume(Volume::SILENT),
+        Music,
+        layer,
+    )
+}
+
+/// Fades out every music layer currently playing. Meant for leaving a screen with its own music.
+pub fn fade_out_music(mut commands: Commands, query: Query<Entity, With<MusicLayer>>) {
+    for entity in &query {
+        commands.entity(entity).insert(FadingOut);
+    }
 }
 
 #[derive(Debug, Resource, Default, Reflect)]
@@ -39,36 +76,78 @@ pub struct BgmConfig {
     pub percussion_enabled: bool,
 }
 
-fn bgm_config_changed(
+fn fade_music_layers(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
     config: Res<BgmConfig>,
     global_volume: Res<GlobalVolume>,
-    query: Query<(&mut AudioSink, &MusicLayer)>,
+    audio_settings: Res<AudioSettings>,
+    query: Query<(
+        Entity,
+        &MusicLayer,
+        &mut MusicLayerVolume,
+        Option<&mut AudioSink>,
+        Has<FadingOut>,
+    )>,
+) {
+    let step = time.delta_secs() / MUSIC_FADE_SECONDS;
+    for (entity, layer, mut volume, sink, fading_out) in query {
+        let enabled = !fading_out
+            && match layer {
+                MusicLayer::Base => config.base_enabled,
+                MusicLayer::Extra => config.extra_enabled,
+                MusicLayer::Perc => config.percussion_enabled,
+            };
+        let target = if enabled { 1.0 } else { 0.0 };
+        volume.0 = if volume.0 < target {
+            (volume.0 + step).min(target)
+        } else {
+            (volume.0 - step).max(target)
+        };
+        if fading_out && volume.0 == 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        // The sink only exists once the audio has started playing.
+        if let Some(mut sink) = sink {
+            sink.set_volume(
+                global_volume.volume * Volume::Linear(audio_settings.music_volume * volume.0),
+            );
+        }
+    }
+}
+
+/// Sent to restart the music from fresh audio sinks, for when the music goes silent, e.g. after
+/// the audio output device changed.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct RestartAudio;
+
+/// Respawns every music layer at the volume it had, so the layers get new sinks but stay in the
+/// same place in the mix. Layers that were fading out are dropped. [`BgmConfig`] and
+/// [`GlobalVolume`] are then applied again to everything still playing.
+fn restart_audio(
+    mut commands: Commands,
+    layer_query: Query<(
+        Entity,
+        &AudioPlayer,
+        &MusicLayer,
+        &MusicLayerVolume,
+        Has<FadingOut>,
+    )>,
+    mut bgm_config: ResMut<BgmConfig>,
+    mut global_volume: ResMut<GlobalVolume>,
 ) {
-    for (mut sink, layer) in query {
-        match layer {
-            MusicLayer::Base => {
-                if config.base_enabled {
-                    sink.set_volume(global_volume.volume);
-                } else {
-                    sink.set_volume(Volume::SILENT);
-                }
-            }
-            MusicLayer::Extra => {
-                if config.extra_enabled {
-                    sink.set_volume(global_volume.volume);
-                } else {
-                    sink.set_volume(Volume::SILENT);
-                }
-            }
-            MusicLayer::Perc => {
-                if config.percussion_enabled {
-                    sink.set_volume(global_volume.volume);
-                } else {
-                    sink.set_volume(Volume::SILENT);
-                }
-            }
+    for (entity, player, layer, volume, fading_out) in &layer_query {
+        commands.entity(entity).despawn();
+        if !fading_out {
+            commands.spawn((
+                music_layer(player.0.clone(), *layer),
+                MusicLayerVolume(volume.0),
+            ));
         }
     }
+    bgm_config.set_changed();
+    global_volume.set_changed();
 }
 
 /// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
@@ -85,12 +164,22 @@ pub fn sound_effect(handle: Handle<AudioSource>) -> impl Bundle {
 }
 
 pub fn sound_effect_3d(handle: Handle<AudioSource>, translation: Vec3) -> impl Bundle {
+    pitched_sound_effect_3d(handle, translation, 1.0)
+}
+
+/// A positional sound effect played back at `speed`, which also shifts its pitch.
+pub fn pitched_sound_effect_3d<T: Asset + Decodable>(
+    handle: Handle<T>,
+    translation: Vec3,
+    speed: f32,
+) -> impl Bundle {
     (
         AudioPlayer(handle),
         PlaybackSettings {
             mode: bevy::audio::PlaybackMode::Despawn,
             spatial: true,
             volume: Volume::Linear(0.8),
+            speed,
             ..PlaybackSettings::ONCE
         },
         SoundEffect,
@@ -98,12 +187,79 @@ pub fn sound_effect_3d(handle: Handle<AudioSource>, translation: Vec3) -> impl B
     )
 }
 
-/// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
+/// How loud a sound with [`Music`] or [`SoundEffect`] should be, relative to its own volume.
+fn category_volume(settings: &AudioSettings, is_music: bool, is_sound_effect: bool) -> Volume {
+    if is_music {
+        Volume::Linear(settings.music_volume)
+    } else if is_sound_effect {
+        Volume::Linear(settings.sfx_volume)
+    } else {
+        Volume::Linear(1.0)
+    }
+}
+
+/// [`GlobalVolume`] and [`AudioSettings`] don't apply to already-running audio entities, so this
+/// system will update them. Music layers are left to [`fade_music_layers`], which applies them
+/// every frame.
 fn apply_global_volume(
     global_volume: Res<GlobalVolume>,
-    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink)>,
+    audio_settings: Res<AudioSettings>,
+    mut audio_query: Query<
+        (
+            &PlaybackSettings,
+            &mut AudioSink,
+            Has<Music>,
+            Has<SoundEffect>,
+        ),
+        Without<MusicLayer>,
+    >,
+    mut spatial_query: Query<(
+        &PlaybackSettings,
+        &mut SpatialAudioSink,
+        Has<Music>,
+        Has<SoundEffect>,
+    )>,
 ) {
-    for (playback, mut sink) in &mut audio_query {
-        sink.set_volume(global_volume.volume * playback.volume);
+    for (playback, mut sink, is_music, is_sound_effect) in &mut audio_query {
+        let category = category_volume(&audio_settings, is_music, is_sound_effect);
+        sink.set_volume(global_volume.volume * category * playback.volume);
+    }
+    for (playback, mut sink, is_music, is_sound_effect) in &mut spatial_query {
+        let category = category_volume(&audio_settings, is_music, is_sound_effect);
+        sink.set_volume(global_volume.volume * category * playback.volume);
+    }
+}
+
+/// Bevy only applies [`GlobalVolume`] when a sound starts, so this adds the [`AudioSettings`]
+/// category volume on top once the sink exists.
+fn apply_category_volume_to_new_sounds(
+    global_volume: Res<GlobalVolume>,
+    audio_settings: Res<AudioSettings>,
+    mut audio_query: Query<
+        (
+            &PlaybackSettings,
+            &mut AudioSink,
+            Has<Music>,
+            Has<SoundEffect>,
+        ),
+        (Added<AudioSink>, Without<MusicLayer>),
+    >,
+    mut spatial_query: Query<
+        (
+            &PlaybackSettings,
+            &mut SpatialAudioSink,
+            Has<Music>,
+            Has<SoundEffect>,
+        ),
+        Added<SpatialAudioSink>,
+    >,
+) {
+    for (playback, mut sink, is_music, is_sound_effect) in &mut audio_query {
+        let category = category_volume(&audio_settings, is_music, is_sound_effect);
+        sink.set_volume(global_volume.volume * category * playback.volume);
+    }
+    for (playback, mut sink, is_music, is_sound_effect) in &mut spatial_query {
+        let category = category_volume(&audio_settings, is_music, is_sound_effect);
+        sink.set_volume(global_volume.volume * category * playback.volume);
     }
 }
@@ -0,0 +1,48 @@
+//! Copying to and pasting from the system clipboard.
+
+use bevy::prelude::*;
+
+#[cfg(not(target_family = "wasm"))]
+thread_local! {
+    // On some platforms the clipboard contents only live as long as the `Clipboard` that set them,
+    // so keep one around instead of creating it on demand.
+    static CLIPBOARD: std::cell::RefCell<Option<arboard::Clipboard>> =
+        std::cell::RefCell::new(arboard::Clipboard::new().ok());
+}
+
+/// Puts `text` on the clipboard, returning whether it worked.
+#[cfg(not(target_family = "wasm"))]
+pub fn copy(text: &str) -> bool {
+    CLIPBOARD.with_borrow_mut(|clipboard| {
+        let Some(clipboard) = clipboard else {
+            warn!("No clipboard available");
+            return false;
+        };
+        clipboard
+            .set_text(text)
+            .map_err(|err| warn!("Failed to copy to clipboard: {err}"))
+            .is_ok()
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn paste() -> Option<String> {
+    CLIPBOARD.with_borrow_mut(|clipboard| clipboard.as_mut()?.get_text().ok())
+}
+
+/// Puts `text` on the clipboard, returning whether it worked.
+#[cfg(target_family = "wasm")]
+pub fn copy(text: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+    // The browser finishes the write asynchronously; there's nothing useful to do if it fails.
+    let _ = window.navigator().clipboard().write_text(text);
+    true
+}
+
+/// Browsers only allow reading the clipboard asynchronously, so on web codes have to be typed.
+#[cfg(target_family = "wasm")]
+pub fn paste() -> Option<String> {
+    None
+}
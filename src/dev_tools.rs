@@ -9,7 +9,13 @@ use bevy::{
 use bevy_inspector_egui::{bevy_egui::EguiPlugin, quick::WorldInspectorPlugin};
 
 use crate::{
-    game::{level::LevelBounds, movement::MovementController, state::GamePhase},
+    game::{
+        level::LevelBounds,
+        movement::MovementController,
+        sheep::{HerdMetrics, Sheep, SheepState},
+        state::GamePhase,
+        ufo::Ufo,
+    },
     screens::Screen,
 };
 
@@ -34,13 +40,17 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, draw_level_bounds);
     app.add_systems(
         Update,
-        skip_to_interlude.run_if(input_just_pressed(KeyCode::F3)),
+        skip_to_modifier_choice.run_if(input_just_pressed(KeyCode::F3)),
     );
     app.add_systems(
         Update,
         toggle_intent_overlay.run_if(input_just_pressed(KeyCode::F4)),
     );
-    app.add_systems(Update, draw_movement_intents);
+    app.add_systems(Update, (draw_movement_intents, draw_sheep_states));
+    app.add_systems(
+        Update,
+        draw_herd_metrics.run_if(in_state(GamePhase::Herding)),
+    );
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
@@ -139,6 +149,66 @@ fn draw_movement_intents(
     }
 }
 
-fn skip_to_interlude(mut next_state: ResMut<NextState<GamePhase>>) {
+fn draw_sheep_states(
+    mut gizmos: Gizmos,
+    debug_gizmo_overlay: Res<DebugGizmoOverlay>,
+    sheep_query: Query<(Entity, &Transform, &Sheep)>,
+    ufos: Query<&Ufo>,
+) {
+    if !debug_gizmo_overlay.enabled {
+        return;
+    }
+
+    const HEIGHT_OFFSET: f32 = 1.6;
+    const MARKER_RADIUS: f32 = 0.15;
+    const TARGET_RADIUS: f32 = 0.35;
+    let target_color = Color::srgb(1.0, 0.0, 1.0);
+
+    for (entity, transform, sheep) in &sheep_query {
+        let color = match sheep.state() {
+            SheepState::Wander(_) => Color::srgb(0.2, 0.9, 0.2),
+            SheepState::Evading(_) => Color::srgb(1.0, 0.9, 0.1),
+            SheepState::Spooked(_) => Color::srgb(1.0, 0.2, 0.2),
+            SheepState::BeingCounted => Color::srgb(0.2, 0.4, 1.0),
+            SheepState::BeingAbducted => Color::srgb(0.6, 0.2, 0.9),
+            SheepState::Sleeping => Color::srgb(0.6, 0.6, 0.6),
+        };
+        let marker = transform.translation + Vec3::Y * HEIGHT_OFFSET;
+        gizmos.sphere(marker, MARKER_RADIUS, color);
+        if ufos.iter().any(|ufo| ufo.target() == Some(entity)) {
+            gizmos.sphere(marker, TARGET_RADIUS, target_color);
+        }
+    }
+}
+
+/// Marks the flock's centroid with a ring as wide as its spread and an arrow along its heading.
+fn draw_herd_metrics(
+    mut gizmos: Gizmos,
+    debug_gizmo_overlay: Res<DebugGizmoOverlay>,
+    metrics: Res<HerdMetrics>,
+) {
+    if !debug_gizmo_overlay.enabled || metrics.count == 0 {
+        return;
+    }
+
+    const HEIGHT_OFFSET: f32 = 0.3;
+    const CENTER_RADIUS: f32 = 0.3;
+    const HEADING_LENGTH: f32 = 3.0;
+    let color = Color::srgb(1.0, 0.5, 0.1);
+
+    let center = Vec3::new(metrics.centroid.x, HEIGHT_OFFSET, metrics.centroid.y);
+    gizmos.sphere(center, CENTER_RADIUS, color);
+    gizmos.circle(
+        Isometry3d::new(center, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        metrics.spread.max(CENTER_RADIUS),
+        color,
+    );
+    if metrics.heading != Vec2::ZERO {
+        let heading = Vec3::new(metrics.heading.x, 0.0, metrics.heading.y);
+        gizmos.arrow(center, center + heading * HEADING_LENGTH, color);
+    }
+}
+
+fn skip_to_modifier_choice(mut next_state: ResMut<NextState<GamePhase>>) {
     next_state.set(GamePhase::ModifierChoice);
 }
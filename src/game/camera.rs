@@ -1,10 +1,28 @@
 use bevy::prelude::*;
 
-use crate::{AppSystems, PausableSystems, game::player::Player};
+use crate::{
+    AppSystems, PausableSystems,
+    game::{player::Player, sheep::Sheep},
+    settings::{AccessibilityConfig, DisplaySettings},
+};
+
+/// How far the camera sits from the player. The camera is orthographic, so this doesn't change
+/// how big things look; zoom is set on the projection instead.
+const CAMERA_DISTANCE: f32 = 10.0 * std::f32::consts::SQRT_2;
+/// The camera starts zooming out once a sheep strays this far from the player.
+const HERD_ZOOM_START_DISTANCE: f32 = 10.0;
+/// The furthest the camera zooms out to keep scattered sheep in view, as a multiple of the zoom
+/// from [`DisplaySettings`].
+const HERD_ZOOM_MAX: f32 = 1.6;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<CameraTarget>();
     app.init_resource::<CameraFollow>();
+    // Not pausable, so changes made in the settings menu show up while paused.
+    app.add_systems(
+        Update,
+        apply_camera_settings.run_if(resource_changed::<DisplaySettings>),
+    );
     app.add_systems(
         Update,
         (set_camera_target_to_player, move_camera_to_target)
@@ -19,14 +37,19 @@ pub struct CameraTarget(pub Option<Entity>);
 
 #[derive(Resource)]
 struct CameraFollow {
-    offset: Option<Vec3>,
+    /// Where the camera sits relative to its target, set from [`DisplaySettings`].
+    offset: Vec3,
+    /// The projection scale with the flock bunched up, set from [`DisplaySettings`].
+    scale: f32,
     smoothing: f32,
 }
 
 impl Default for CameraFollow {
     fn default() -> Self {
         Self {
-            offset: None,
+            // Matches where the camera is spawned, looking at the origin.
+            offset: Vec3::new(0.0, 10.0, 10.0),
+            scale: 1.0,
             smoothing: 8.0,
         }
     }
@@ -51,9 +74,11 @@ fn set_camera_target_to_player(
 fn move_camera_to_target(
     time: Res<Time>,
     target: Res<CameraTarget>,
-    mut follow: ResMut<CameraFollow>,
+    accessibility: Res<AccessibilityConfig>,
+    follow: Res<CameraFollow>,
     target_query: Query<&Transform, Without<MainCamera>>,
-    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+    sheep_query: Query<(&Transform, &Sheep), Without<MainCamera>>,
+    mut camera_query: Query<(&mut Transform, &mut Projection), With<MainCamera>>,
 ) {
     let Some(target_entity) = target.0 else {
         return;
@@ -63,15 +88,53 @@ fn move_camera_to_target(
         return;
     };
 
-    let Ok(mut camera_transform) = camera_query.single_mut() else {
+    let Ok((mut camera_transform, mut projection)) = camera_query.single_mut() else {
         return;
     };
 
-    let offset = follow
-        .offset
-        .get_or_insert_with(|| camera_transform.translation - target_transform.translation);
-    let desired = target_transform.translation + *offset;
+    let desired = target_transform.translation + follow.offset;
 
-    let t = 1.0 - (-follow.smoothing * time.delta_secs()).exp();
+    let t = if accessibility.reduced_motion {
+        1.0
+    } else {
+        1.0 - (-follow.smoothing * time.delta_secs()).exp()
+    };
     camera_transform.translation = camera_transform.translation.lerp(desired, t);
+
+    if let Projection::Orthographic(orthographic) = &mut *projection {
+        let desired_scale =
+            follow.scale * herd_zoom(target_transform.translation.xz(), &sheep_query);
+        orthographic.scale = orthographic.scale.lerp(desired_scale, t);
+    }
+}
+
+/// How much to zoom out so the sheep that strayed furthest from `focus` stay in view, from 1 (no
+/// change) up to [`HERD_ZOOM_MAX`]. Sheep being counted or abducted don't count.
+fn herd_zoom(focus: Vec2, sheep_query: &Query<(&Transform, &Sheep), Without<MainCamera>>) -> f32 {
+    let furthest = sheep_query
+        .iter()
+        .filter(|(_, sheep)| sheep.is_herdable())
+        .map(|(transform, _)| transform.translation.xz().distance(focus))
+        .fold(0.0, f32::max);
+    (furthest / HERD_ZOOM_START_DISTANCE).clamp(1.0, HERD_ZOOM_MAX)
+}
+
+/// Points the camera at the pitch and zoom from [`DisplaySettings`], keeping the spot it looks at
+/// in place.
+fn apply_camera_settings(
+    display: Res<DisplaySettings>,
+    mut follow: ResMut<CameraFollow>,
+    camera: Single<(&mut Transform, &mut Projection), With<MainCamera>>,
+) {
+    let (mut transform, mut projection) = camera.into_inner();
+    let pitch = display.camera_pitch_degrees().to_radians();
+    let offset = Vec3::new(0.0, pitch.sin(), pitch.cos()) * CAMERA_DISTANCE;
+    let focus = transform.translation - follow.offset;
+    follow.offset = offset;
+    transform.translation = focus + offset;
+    transform.look_to(-offset, Vec3::Y);
+    follow.scale = 1.0 / display.camera_zoom();
+    if let Projection::Orthographic(orthographic) = &mut *projection {
+        orthographic.scale = follow.scale;
+    }
 }
@@ -0,0 +1,180 @@
+//! Share codes: a short string holding a run's seed and result, which another player can enter
+//! to play the same seeded run and try to beat it.
+
+use std::fmt;
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use bevy::prelude::*;
+
+use crate::{
+    game::{rng::GameRng, state::GameMode},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PendingChallenge>();
+    app.init_resource::<PendingSeed>();
+    app.init_resource::<ActiveChallenge>();
+    app.add_systems(OnEnter(Screen::Gameplay), start_run);
+}
+
+/// Bumped whenever the encoded layout changes, so old codes are rejected instead of misread.
+const SHARE_CODE_VERSION: u8 = 2;
+/// Version, seed, completed rounds, sheep count, game mode and checksum.
+const SHARE_CODE_BYTES: usize = 1 + 8 + 4 + 2 + 1 + 1;
+
+/// What a share code holds: the seed and mode to replay, and the result to beat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareCode {
+    pub seed: u64,
+    pub completed_rounds: u32,
+    pub sheep_count: u16,
+    pub mode: GameMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareCodeError {
+    /// Not valid base64, or the wrong length.
+    Malformed,
+    /// Made by a different version of the game.
+    UnsupportedVersion,
+    /// The checksum doesn't match, most likely due to a typo.
+    Corrupted,
+}
+
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareCodeError::Malformed => write!(f, "That isn't a valid code."),
+            ShareCodeError::UnsupportedVersion => {
+                write!(f, "That code is from a different version of the game.")
+            }
+            ShareCodeError::Corrupted => write!(f, "That code has a typo in it."),
+        }
+    }
+}
+
+impl ShareCode {
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(SHARE_CODE_BYTES);
+        bytes.push(SHARE_CODE_VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&self.completed_rounds.to_le_bytes());
+        bytes.extend_from_slice(&self.sheep_count.to_le_bytes());
+        bytes.push(mode_to_byte(self.mode));
+        bytes.push(checksum(&bytes));
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    pub fn decode(code: &str) -> Result<Self, ShareCodeError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(|_| ShareCodeError::Malformed)?;
+        let Ok(bytes) = <[u8; SHARE_CODE_BYTES]>::try_from(bytes) else {
+            return Err(ShareCodeError::Malformed);
+        };
+        if bytes[0] != SHARE_CODE_VERSION {
+            return Err(ShareCodeError::UnsupportedVersion);
+        }
+        let (data, check) = (&bytes[..SHARE_CODE_BYTES - 1], bytes[SHARE_CODE_BYTES - 1]);
+        if checksum(data) != check {
+            return Err(ShareCodeError::Corrupted);
+        }
+        Ok(Self {
+            seed: u64::from_le_bytes(data[1..9].try_into().unwrap()),
+            completed_rounds: u32::from_le_bytes(data[9..13].try_into().unwrap()),
+            sheep_count: u16::from_le_bytes(data[13..15].try_into().unwrap()),
+            mode: mode_from_byte(data[15]).ok_or(ShareCodeError::Malformed)?,
+        })
+    }
+}
+
+fn mode_to_byte(mode: GameMode) -> u8 {
+    match mode {
+        GameMode::Normal => 0,
+        GameMode::Zen => 1,
+        GameMode::Gentle => 2,
+        GameMode::Draft => 3,
+        GameMode::Ranked => 4,
+    }
+}
+
+fn mode_from_byte(byte: u8) -> Option<GameMode> {
+    match byte {
+        0 => Some(GameMode::Normal),
+        1 => Some(GameMode::Zen),
+        2 => Some(GameMode::Gentle),
+        3 => Some(GameMode::Draft),
+        4 => Some(GameMode::Ranked),
+        _ => None,
+    }
+}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, byte| sum.rotate_left(3) ^ byte)
+}
+
+/// A challenge entered on the title screen, to be used by the next run.
+#[derive(Resource, Debug, Default)]
+pub struct PendingChallenge(pub Option<ShareCode>);
+
+/// The seed for the next run, when it had to be picked before the run started, e.g. to draw the
+/// draft pool. A [`PendingChallenge`]'s seed is used instead if there is one.
+#[derive(Resource, Debug, Default)]
+pub struct PendingSeed(pub Option<u64>);
+
+impl PendingSeed {
+    /// The seed the next run will use, picking one now if it doesn't have one yet.
+    pub fn get_or_pick(&mut self, challenge: &PendingChallenge) -> u64 {
+        match challenge.0 {
+            Some(challenge) => challenge.seed,
+            None => *self.0.get_or_insert_with(rand::random),
+        }
+    }
+}
+
+/// The challenge the current run is trying to beat, if any.
+#[derive(Resource, Debug, Default)]
+pub struct ActiveChallenge(pub Option<ShareCode>);
+
+fn start_run(
+    mut pending: ResMut<PendingChallenge>,
+    mut pending_seed: ResMut<PendingSeed>,
+    mut active: ResMut<ActiveChallenge>,
+    mut rng: ResMut<GameRng>,
+) {
+    active.0 = pending.0.take();
+    let seed = pending_seed.0.take();
+    let seed = active
+        .0
+        .map(|challenge| challenge.seed)
+        .or(seed)
+        .unwrap_or_else(rand::random);
+    *rng = GameRng::from_seed(seed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_code_round_trips_every_mode() {
+        for mode in [
+            GameMode::Normal,
+            GameMode::Zen,
+            GameMode::Gentle,
+            GameMode::Draft,
+            GameMode::Ranked,
+        ] {
+            let code = ShareCode {
+                seed: 0x0123_4567_89ab_cdef,
+                completed_rounds: 12,
+                sheep_count: 40,
+                mode,
+            };
+            assert_eq!(ShareCode::decode(&code.encode()), Ok(code));
+        }
+    }
+}
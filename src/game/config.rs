@@ -0,0 +1,335 @@
+//! Balance values gathered in one place. The defaults are compiled in, and are overridden by
+//! `assets/config/game.config.ron` once it loads. On native dev builds the file is hot reloaded,
+//! so values can be tuned while the game is running.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::game::ufo::UfoConfig;
+
+const GAME_CONFIG_PATH: &str = "config/game.config.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<GameConfig>();
+    app.init_asset_loader::<GameConfigLoader>();
+    app.init_resource::<GameConfig>();
+    app.add_systems(Startup, load_game_config);
+    app.add_systems(Update, apply_game_config);
+}
+
+/// Every tunable the game reads at runtime. Missing fields in the config file keep their defaults.
+#[derive(Asset, Resource, Serialize, Deserialize, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameConfig {
+    pub round: RoundConfig,
+    pub herd: HerdConfig,
+    pub player: PlayerConfig,
+    pub ufo: UfoConfig,
+    pub ranked: RankedConfig,
+    pub efficiency: EfficiencyConfig,
+    pub perfect_herd: PerfectHerdConfig,
+    pub shop: ShopConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct RoundConfig {
+    /// Length of a round.
+    pub timer_seconds: f32,
+    /// Taken off the round length while [`Charm::HalfTimeDoubleSheep`] is active.
+    ///
+    /// [`Charm::HalfTimeDoubleSheep`]: crate::game::state::shop::items::Charm::HalfTimeDoubleSheep
+    pub half_time_penalty_seconds: f32,
+    /// How long the round keeps going after the points target is met, when rounds end
+    /// automatically. Each sheep counted in that time is worth a coin. Zero ends rounds at once.
+    pub bonus_time_seconds: f32,
+    /// Completed round counts that are celebrated with an overlay.
+    pub milestone_rounds: Vec<u32>,
+}
+
+impl Default for RoundConfig {
+    fn default() -> Self {
+        Self {
+            timer_seconds: 70.0,
+            half_time_penalty_seconds: 20.0,
+            bonus_time_seconds: 3.0,
+            milestone_rounds: vec![10, 20, 30, 50],
+        }
+    }
+}
+
+/// How sheep flock together.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct HerdConfig {
+    /// Sheep within this distance count as flockmates.
+    pub radius: f32,
+    /// Flockmates closer than this push each other apart.
+    pub separation_radius: f32,
+    pub cohesion_weight: f32,
+    pub separation_weight: f32,
+    /// How much an evading sheep still follows its flock rather than running straight away.
+    pub evade_blend: f32,
+    /// How far a wandering sheep strays from the flock's heading.
+    pub wander_jitter: f32,
+    /// Flocking stops looking for flockmates after finding this many.
+    pub max_neighbors: usize,
+    /// How many ordinary sheep a herd leader counts as.
+    pub leader_weight: f32,
+}
+
+impl Default for HerdConfig {
+    fn default() -> Self {
+        Self {
+            radius: 10.0,
+            separation_radius: 2.4,
+            cohesion_weight: 0.9,
+            separation_weight: 1.5,
+            evade_blend: 0.55,
+            wander_jitter: 0.35,
+            max_neighbors: 20,
+            leader_weight: 4.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct PlayerConfig {
+    /// Shortest gap between two barks, however much stamina is left.
+    pub bark_cooldown_seconds: f32,
+    /// Sheep start evading once the player is this close.
+    pub sheep_interact_radius: f32,
+    pub max_stamina: f32,
+    /// Stamina used up by each bark. Barking needs at least this much.
+    pub bark_stamina_cost: f32,
+    /// Stamina regained per second, once the bark cooldown has finished.
+    pub stamina_regen_per_second: f32,
+}
+
+impl Default for PlayerConfig {
+    fn default() -> Self {
+        Self {
+            bark_cooldown_seconds: 0.5,
+            sheep_interact_radius: 7.0,
+            max_stamina: 100.0,
+            bark_stamina_cost: 40.0,
+            stamina_regen_per_second: 25.0,
+        }
+    }
+}
+
+/// Diminishing returns on rapid counting in ranked runs. The formula is documented in
+/// [`crate::game::state::ranked`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct RankedConfig {
+    /// How far back counts are remembered when working out the pace.
+    pub window_seconds: f32,
+    /// Counts within the window that still score in full.
+    pub free_counts: usize,
+    /// How quickly points fall off past the free counts.
+    pub falloff: f32,
+}
+
+impl Default for RankedConfig {
+    fn default() -> Self {
+        Self {
+            window_seconds: 2.0,
+            free_counts: 6,
+            falloff: 0.5,
+        }
+    }
+}
+
+/// Grading how quickly each round is won. Paces are multiples of the points per second needed to
+/// just meet the target, see [`crate::game::state::efficiency`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct EfficiencyConfig {
+    pub s_pace: f32,
+    pub a_pace: f32,
+    pub b_pace: f32,
+    /// How much the thresholds ease off for each step of modifier difficulty active: nothing for
+    /// an easy modifier, one step for a medium one and two for a hard one.
+    pub slack_per_difficulty: f32,
+}
+
+impl Default for EfficiencyConfig {
+    fn default() -> Self {
+        Self {
+            s_pace: 2.5,
+            a_pace: 1.8,
+            b_pace: 1.3,
+            slack_per_difficulty: 0.05,
+        }
+    }
+}
+
+/// The once-a-round bonus for holding the flock together, see
+/// [`crate::game::state::perfect_herd`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct PerfectHerdConfig {
+    /// The flock needs at least this many sheep on the field.
+    pub min_sheep: usize,
+    /// How much of the rest of the flock the average sheep needs nearby, from 0 to 1. Sheep stop
+    /// looking for flockmates at [`HerdConfig::max_neighbors`], so in big flocks that's the most
+    /// any sheep needs.
+    pub neighbor_fraction: f32,
+    /// How long the flock has to stay that tight.
+    pub hold_seconds: f32,
+    pub reward_money: u32,
+}
+
+impl Default for PerfectHerdConfig {
+    fn default() -> Self {
+        Self {
+            min_sheep: 8,
+            neighbor_fraction: 0.7,
+            hold_seconds: 3.0,
+            reward_money: 2,
+        }
+    }
+}
+
+/// The numbers behind the shop's boosts and charms. Their descriptions are written from these.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[serde(default)]
+pub struct ShopConfig {
+    /// How much each [`Boost::BarkPower`] grows the bark radius.
+    ///
+    /// [`Boost::BarkPower`]: crate::game::state::shop::items::Boost::BarkPower
+    pub bark_radius_per_boost: f32,
+    /// [`Boost::MaxCharms`] adds a charm slot 1 in this many times.
+    ///
+    /// [`Boost::MaxCharms`]: crate::game::state::shop::items::Boost::MaxCharms
+    pub max_charms_chance_denominator: u32,
+    /// [`Charm::ChanceBlueOnBuy`] and [`Charm::ChanceRedOnBuy`] color a bought sheep 1 in this
+    /// many times.
+    ///
+    /// [`Charm::ChanceBlueOnBuy`]: crate::game::state::shop::items::Charm::ChanceBlueOnBuy
+    /// [`Charm::ChanceRedOnBuy`]: crate::game::state::shop::items::Charm::ChanceRedOnBuy
+    pub color_on_buy_chance_denominator: u32,
+    /// How many times [`Boost::Insurance`] can be bought, so UFOs still cost more than they pay
+    /// out.
+    ///
+    /// [`Boost::Insurance`]: crate::game::state::shop::items::Boost::Insurance
+    pub max_insurance_level: u8,
+    /// Seconds left on the clock that [`Charm::TimeBonus`] turns into one coin.
+    ///
+    /// [`Charm::TimeBonus`]: crate::game::state::shop::items::Charm::TimeBonus
+    pub time_bonus_seconds_per_coin: u32,
+}
+
+impl Default for ShopConfig {
+    fn default() -> Self {
+        Self {
+            bark_radius_per_boost: 1.0,
+            max_charms_chance_denominator: 4,
+            color_on_buy_chance_denominator: 4,
+            max_insurance_level: 3,
+            time_bonus_seconds_per_coin: 10,
+        }
+    }
+}
+
+#[derive(Default, TypePath)]
+struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["config.ron"]
+    }
+}
+
+/// Keeps the config file loaded so it can be hot reloaded.
+#[derive(Resource, Debug)]
+struct GameConfigHandle(Handle<GameConfig>);
+
+fn load_game_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(GameConfigHandle(asset_server.load(GAME_CONFIG_PATH)));
+}
+
+/// Copies the config file into [`GameConfig`] whenever it's loaded or changed on disk. The UFO
+/// values go into [`UfoConfig`], which the UFOs read directly.
+fn apply_game_config(
+    mut events: MessageReader<AssetEvent<GameConfig>>,
+    handle: Option<Res<GameConfigHandle>>,
+    assets: Res<Assets<GameConfig>>,
+    mut config: ResMut<GameConfig>,
+    mut ufo_config: ResMut<UfoConfig>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    for event in events.read() {
+        if !event.is_loaded_with_dependencies(&handle.0) && !event.is_modified(&handle.0) {
+            continue;
+        }
+        let Some(loaded) = assets.get(&handle.0) else {
+            continue;
+        };
+        config.set_if_neq(loaded.clone());
+        ufo_config.set_if_neq(loaded.ufo.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The values that were hardcoded before they moved into [`GameConfig`].
+    #[test]
+    fn defaults_match_the_old_constants() {
+        let config = GameConfig::default();
+        assert_eq!(config.round.timer_seconds, 70.0);
+        assert_eq!(config.round.half_time_penalty_seconds, 20.0);
+        assert_eq!(config.herd.radius, 10.0);
+        assert_eq!(config.herd.separation_radius, 2.4);
+        assert_eq!(config.herd.cohesion_weight, 0.9);
+        assert_eq!(config.herd.separation_weight, 1.5);
+        assert_eq!(config.herd.evade_blend, 0.55);
+        assert_eq!(config.herd.wander_jitter, 0.35);
+        assert_eq!(config.herd.max_neighbors, 20);
+        assert_eq!(config.herd.leader_weight, 4.0);
+        assert_eq!(config.player.sheep_interact_radius, 7.0);
+        assert_eq!(config.ufo.abduction_seconds, 8.0);
+        assert_eq!(config.ufo.pause_seconds, 3.0);
+        assert_eq!(config.ufo.speed, 7.0);
+        assert_eq!(config.ufo.speed_per_round, 0.3);
+        assert_eq!(config.ufo.abduction_seconds_per_round, 0.25);
+        assert_eq!(config.ufo.min_abduction_seconds, 3.0);
+        assert_eq!(config.shop.bark_radius_per_boost, 1.0);
+        assert_eq!(config.shop.max_charms_chance_denominator, 4);
+        assert_eq!(config.shop.color_on_buy_chance_denominator, 4);
+        assert_eq!(config.shop.max_insurance_level, 3);
+        assert_eq!(config.shop.time_bonus_seconds_per_coin, 10);
+    }
+
+    #[test]
+    fn shipped_config_file_matches_the_defaults() {
+        let file = include_str!("../../assets/config/game.config.ron");
+        let loaded: GameConfig = ron::de::from_str(file).unwrap();
+        assert_eq!(loaded, GameConfig::default());
+    }
+}
@@ -0,0 +1,133 @@
+//! Markers that keep the goal easy to find: an arrow bobbing over each goal in the world, and an
+//! optional indicator at the edge of the screen pointing towards the main goal when it's off screen.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        camera::MainCamera,
+        level::{GoalLocation, goal_for},
+        sheep::SheepColor,
+        state::GamePhase,
+    },
+    settings::{AccessibilityConfig, GameplaySettings},
+    theme::palette::{BUTTON_BACKGROUND, LABEL_TEXT},
+};
+
+const MARKER_COLOR: Color = Color::srgba(1.0, 0.95, 0.6, 0.55);
+/// Height of the arrow's tip above the goal.
+const MARKER_TIP_HEIGHT: f32 = 2.0;
+const MARKER_LENGTH: f32 = 1.5;
+const MARKER_BOB_HEIGHT: f32 = 0.3;
+const MARKER_BOB_SPEED: f32 = 2.5;
+/// Size of the edge indicator, in logical pixels.
+const INDICATOR_SIZE: f32 = 36.0;
+/// How far the edge indicator sits in from the edge of the screen.
+const INDICATOR_MARGIN: f32 = 12.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GamePhase::Herding), spawn_goal_indicator);
+    app.add_systems(
+        Update,
+        (draw_goal_markers, update_goal_indicator).run_if(in_state(GamePhase::Herding)),
+    );
+}
+
+/// Points towards the main goal from the edge of the screen.
+#[derive(Component, Debug)]
+struct GoalIndicator;
+
+fn spawn_goal_indicator(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Goal Indicator"),
+        GoalIndicator,
+        Node {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            width: px(INDICATOR_SIZE),
+            height: px(INDICATOR_SIZE),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        BackgroundColor(BUTTON_BACKGROUND.with_alpha(0.85)),
+        Pickable::IGNORE,
+        DespawnOnExit(GamePhase::Herding),
+        children![(
+            Text::new(">"),
+            TextFont::from_font_size(24.0),
+            TextColor(LABEL_TEXT),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+fn draw_goal_markers(
+    time: Res<Time>,
+    accessibility: Res<AccessibilityConfig>,
+    goal_query: Query<&Transform, With<GoalLocation>>,
+    mut gizmos: Gizmos,
+) {
+    let bob = if accessibility.reduced_motion {
+        0.0
+    } else {
+        (time.elapsed_secs() * MARKER_BOB_SPEED).sin() * MARKER_BOB_HEIGHT
+    };
+    for transform in &goal_query {
+        let tip = transform.translation + Vec3::Y * (MARKER_TIP_HEIGHT + bob);
+        gizmos.arrow(tip + Vec3::Y * MARKER_LENGTH, tip, MARKER_COLOR);
+    }
+}
+
+/// Shows the indicator at the edge of the screen, in the direction of the main goal, whenever the
+/// goal is off screen.
+fn update_goal_indicator(
+    settings: Res<GameplaySettings>,
+    ui_scale: Res<UiScale>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    goal_query: Query<(&Transform, &GoalLocation)>,
+    indicator: Single<(&mut Node, &mut UiTransform), With<GoalIndicator>>,
+) {
+    let (mut node, mut ui_transform) = indicator.into_inner();
+    let (camera, camera_transform) = *camera;
+    let goal = goal_for(&SheepColor::White, goal_query.iter());
+    let (Some((goal_pos, _)), Some(viewport), true) = (
+        goal,
+        camera.logical_viewport_rect(),
+        settings.show_goal_indicator,
+    ) else {
+        node.display = Display::None;
+        return;
+    };
+
+    let inner = viewport.inflate(-(INDICATOR_MARGIN + INDICATOR_SIZE / 2.0));
+    let direction = match camera.world_to_viewport(camera_transform, goal_pos) {
+        Ok(position) if inner.contains(position) => {
+            node.display = Display::None;
+            return;
+        }
+        Ok(position) => position - viewport.center(),
+        // Behind the camera, so use its direction in view space instead. Screen y points down.
+        Err(_) => {
+            let view_pos = camera_transform
+                .affine()
+                .inverse()
+                .transform_point3(goal_pos);
+            Vec2::new(view_pos.x, -view_pos.y)
+        }
+    };
+    let Ok(direction) = Dir2::new(direction) else {
+        node.display = Display::None;
+        return;
+    };
+
+    // Walk out from the centre along `direction` until hitting the edge of the inner rect.
+    let half = inner.half_size();
+    let distance = (half.x / direction.x.abs()).min(half.y / direction.y.abs());
+    let position = (inner.center() + *direction * distance - INDICATOR_SIZE / 2.0) / ui_scale.0;
+    node.display = Display::Flex;
+    node.left = px(position.x);
+    node.top = px(position.y);
+    ui_transform.rotation = Rot2::radians(direction.to_angle());
+}
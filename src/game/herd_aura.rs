@@ -0,0 +1,52 @@
+//! A faint glow under sheep that are tightly flocked together, so players can see herding working.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{sheep::Sheep, state::GamePhase},
+    settings::{AccessibilityConfig, GameplaySettings},
+};
+
+/// A sheep needs this many flockmates nearby (so a cluster of 5+) to glow.
+const HERD_AURA_MIN_NEIGHBORS: usize = 4;
+const HERD_AURA_RADIUS: f32 = 1.6;
+const HERD_AURA_PULSE_AMOUNT: f32 = 0.25;
+const HERD_AURA_PULSE_SPEED: f32 = 3.0;
+const HERD_AURA_COLOR: Color = Color::srgba(1.0, 1.0, 0.8, 0.35);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        draw_herd_aura.run_if(in_state(GamePhase::Herding).and(herd_aura_enabled)),
+    );
+}
+
+fn herd_aura_enabled(settings: Res<GameplaySettings>) -> bool {
+    settings.show_herd_aura
+}
+
+fn draw_herd_aura(
+    time: Res<Time>,
+    accessibility: Res<AccessibilityConfig>,
+    sheep_query: Query<(&GlobalTransform, &Sheep)>,
+    mut gizmos: Gizmos,
+) {
+    let pulse = if accessibility.reduced_motion {
+        1.0
+    } else {
+        1.0 + (time.elapsed_secs() * HERD_AURA_PULSE_SPEED).sin() * HERD_AURA_PULSE_AMOUNT
+    };
+    // Overlapping circles under each grouped sheep merge into an outline around the cluster.
+    for (transform, sheep) in &sheep_query {
+        if sheep.neighbor_count() < HERD_AURA_MIN_NEIGHBORS {
+            continue;
+        }
+        let mut position = transform.translation();
+        position.y = 0.05;
+        gizmos.circle(
+            Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            HERD_AURA_RADIUS * pulse,
+            HERD_AURA_COLOR,
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! An optional mouse control: drag a box around sheep to send them towards the goal for a moment.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{camera::MainCamera, sheep::Sheep, state::GamePhase},
+    menus::Menu,
+    settings::GameplaySettings,
+};
+
+/// Drags smaller than this (in logical pixels) are treated as stray clicks.
+const MIN_DRAG_SIZE: f32 = 8.0;
+const SELECTION_BOX_COLOR: Color = Color::srgba(1.0, 1.0, 0.8, 0.15);
+const SELECTION_BORDER_COLOR: Color = Color::srgba(1.0, 1.0, 0.8, 0.8);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<HerdCommandDrag>();
+    app.add_systems(OnEnter(GamePhase::Herding), spawn_selection_box);
+    app.add_systems(
+        Update,
+        track_herd_command_drag
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(
+                in_state(GamePhase::Herding)
+                    .and(in_state(Menu::None))
+                    .and(herd_command_enabled),
+            ),
+    );
+}
+
+fn herd_command_enabled(settings: Res<GameplaySettings>) -> bool {
+    settings.herd_command
+}
+
+/// Where the current drag started, in viewport coordinates.
+#[derive(Resource, Debug, Default)]
+struct HerdCommandDrag(Option<Vec2>);
+
+#[derive(Component, Debug)]
+struct SelectionBox;
+
+fn spawn_selection_box(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Herd Selection Box"),
+        Node {
+            position_type: PositionType::Absolute,
+            display: Display::None,
+            border: UiRect::all(px(1)),
+            ..default()
+        },
+        BackgroundColor(SELECTION_BOX_COLOR),
+        BorderColor::all(SELECTION_BORDER_COLOR),
+        Pickable::IGNORE,
+        SelectionBox,
+        DespawnOnExit(GamePhase::Herding),
+    ));
+}
+
+fn track_herd_command_drag(
+    mouse: Res<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut drag: ResMut<HerdCommandDrag>,
+    mut selection_box: Single<&mut Node, With<SelectionBox>>,
+    mut sheep_query: Query<(&GlobalTransform, &mut Sheep)>,
+) {
+    let Some(cursor) = window.cursor_position() else {
+        return;
+    };
+    if mouse.just_pressed(MouseButton::Left) {
+        drag.0 = Some(cursor);
+    }
+    let Some(start) = drag.0 else {
+        return;
+    };
+    let selection = Rect::from_corners(start, cursor);
+
+    if mouse.pressed(MouseButton::Left) {
+        selection_box.left = px(selection.min.x);
+        selection_box.top = px(selection.min.y);
+        selection_box.width = px(selection.width());
+        selection_box.height = px(selection.height());
+        selection_box.display = Display::DEFAULT;
+        return;
+    }
+
+    // Released: nudge every sheep inside the box.
+    drag.0 = None;
+    selection_box.display = Display::None;
+    if selection.width() < MIN_DRAG_SIZE && selection.height() < MIN_DRAG_SIZE {
+        return;
+    }
+    let (camera, camera_transform) = *camera;
+    for (transform, mut sheep) in &mut sheep_query {
+        let on_screen = camera
+            .world_to_viewport(camera_transform, transform.translation())
+            .is_ok_and(|viewport_pos| selection.contains(viewport_pos));
+        if on_screen {
+            sheep.nudge_toward_goal();
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! An optional assist that points the player at the sheep worth herding next.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{
+        level::{GoalLocation, goal_for},
+        player::Player,
+        sheep::{Sheep, SheepColor},
+        state::{GamePhase, GameState},
+    },
+    settings::GameplaySettings,
+};
+
+const HINT_COLOR: Color = Color::srgba(1.0, 0.95, 0.4, 0.8);
+const HINT_HEIGHT: f32 = 0.3;
+const HINT_RING_RADIUS: f32 = 1.2;
+/// Stop the arrow this far short of the sheep so it doesn't cover it.
+const HINT_ARROW_GAP: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        draw_herding_hint.run_if(in_state(GamePhase::Herding).and(herding_hints_enabled)),
+    );
+}
+
+fn herding_hints_enabled(settings: Res<GameplaySettings>) -> bool {
+    settings.show_herding_hints
+}
+
+/// Roughly how many points counting a sheep of `color` is worth right now.
+fn hint_value(color: &SheepColor, state: &GameState) -> f32 {
+    match color {
+        SheepColor::White | SheepColor::Black | SheepColor::Gold => 1.0,
+        SheepColor::Blue => 5.0,
+        // Red sheep multiply the points so far, so they're worth more the later they're counted.
+        SheepColor::Red => (state.points as f32 * 0.5).max(1.0),
+        SheepColor::Contrarian => 3.0,
+        // No points, but the time it buys is worth about as much as a white sheep.
+        SheepColor::Green => 1.0,
+    }
+}
+
+/// Suggests the sheep with the best value for the distance the player has to cover: from the
+/// player to the sheep, then from the sheep to its goal.
+fn draw_herding_hint(
+    state: Res<GameState>,
+    player: Single<&Transform, With<Player>>,
+    sheep_query: Query<(&Transform, &Sheep)>,
+    goal_query: Query<(&Transform, &GoalLocation)>,
+    mut gizmos: Gizmos,
+) {
+    let player_pos = player.translation.xz();
+    let best = sheep_query
+        .iter()
+        .filter(|(_, sheep)| sheep.is_herdable())
+        .filter_map(|(transform, sheep)| {
+            let (goal_pos, _) = goal_for(sheep.color(), goal_query.iter())?;
+            let sheep_pos = transform.translation.xz();
+            let distance = player_pos.distance(sheep_pos) + sheep_pos.distance(goal_pos.xz());
+            let score = hint_value(sheep.color(), &state) / distance.max(1.0);
+            Some((sheep_pos, score))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b));
+    let Some((sheep_pos, _)) = best else {
+        return;
+    };
+
+    let target = Vec3::new(sheep_pos.x, HINT_HEIGHT, sheep_pos.y);
+    let start = Vec3::new(player_pos.x, HINT_HEIGHT, player_pos.y);
+    let to_target = target - start;
+    if to_target.length() > HINT_ARROW_GAP * 2.0 {
+        let end = target - to_target.normalize() * HINT_ARROW_GAP;
+        gizmos.arrow(start, end, HINT_COLOR);
+    }
+    gizmos.circle(
+        Isometry3d::new(target, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+        HINT_RING_RADIUS,
+        HINT_COLOR,
+    );
+}
@@ -1,19 +1,22 @@
 //! Spawn the main level.
 
-use bevy::prelude::*;
+use bevy::{prelude::*, scene::SceneInstanceReady};
 use rand::Rng;
 
 use crate::{
     AppSystems, PausableSystems,
     asset_tracking::LoadResource,
-    audio::{MusicLayer, music},
+    audio::{BgmConfig, FadingOut, MusicLayer, music_layer},
     game::{
         camera::MainCamera,
         modifiers::Modifier,
         movement::MovementController,
+        sheep::{SheepAssets, SheepColor},
         state::{GamePhase, GameState},
     },
+    performance::PerformanceBudget,
     screens::Screen,
+    settings::AccessibilityConfig,
 };
 
 pub const GOAL_RADIUS: f32 = 6.0;
@@ -22,6 +25,26 @@ const GOAL_TEXT_LIFETIME_SECS: f32 = 2.5;
 const GOAL_TEXT_RISE_SPEED: f32 = 0.8;
 const GOAL_TEXT_FONT_SIZE: f32 = 32.0;
 const GOAL_TEXT_HEIGHT_OFFSET: f32 = 1.0;
+/// Texts spawned at the same spot within this window are staggered so they don't overlap.
+const GOAL_TEXT_STACK_WINDOW_SECS: f32 = 0.6;
+const GOAL_TEXT_STACK_SPACING: f32 = 0.9;
+const GOAL_TEXT_STAGGER_X: f32 = 1.5;
+/// After this many staggered texts, start again from the bottom of the stack.
+const GOAL_TEXT_MAX_STACK: usize = 4;
+const TELEPORT_POOF_SECS: f32 = 0.4;
+const TELEPORT_POOF_MIN_RADIUS: f32 = 0.2;
+const TELEPORT_POOF_MAX_RADIUS: f32 = 1.6;
+const TELEPORT_POOF_COLOR: Color = Color::srgb(0.8, 0.5, 1.0);
+/// Radius of the colored pens spawned by [`Modifier::SortingPens`].
+const PEN_RADIUS: f32 = 3.0;
+/// Where the pens for each sheep color go. White sheep use the main goal opening.
+const PEN_POSITIONS: [(SheepColor, Vec3); 5] = [
+    (SheepColor::Blue, Vec3::new(-18.0, 0.0, -12.0)),
+    (SheepColor::Red, Vec3::new(18.0, 0.0, -12.0)),
+    (SheepColor::Black, Vec3::new(-18.0, 0.0, -30.0)),
+    (SheepColor::Gold, Vec3::new(18.0, 0.0, -30.0)),
+    (SheepColor::Green, Vec3::new(0.0, 0.0, -36.0)),
+];
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<LevelAssets>();
@@ -38,7 +61,41 @@ pub(super) fn plugin(app: &mut App) {
         min: (-27.6, -39.6).into(),
         max: (27.6, 7.6).into(),
     });
+    app.add_systems(
+        Update,
+        (tick_teleport_poofs, draw_teleport_poofs)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(Screen::Gameplay)),
+    );
     app.add_observer(handle_random_teleport);
+    app.add_observer(mark_arena_ready);
+}
+
+/// The arena scene spawned by [`spawn_level`].
+#[derive(Component, Debug)]
+struct LevelArena;
+
+/// Added to the [`LevelArena`] once its scene has finished spawning.
+#[derive(Component, Debug)]
+pub struct LevelArenaReady;
+
+fn mark_arena_ready(
+    scene_ready: On<SceneInstanceReady>,
+    mut commands: Commands,
+    arenas: Query<(), With<LevelArena>>,
+) {
+    if arenas.contains(scene_ready.entity) {
+        commands.entity(scene_ready.entity).insert(LevelArenaReady);
+    }
+}
+
+/// Whether the arena scene and the goal have both been spawned, so a round can start.
+pub fn level_ready(
+    arenas: Query<(), With<LevelArenaReady>>,
+    goals: Query<(), With<GoalLocation>>,
+) -> bool {
+    !arenas.is_empty() && !goals.is_empty()
 }
 
 #[derive(Resource, Debug, Reflect)]
@@ -89,14 +146,21 @@ pub struct RandomTeleport {
 
 fn handle_random_teleport(
     event: On<RandomTeleport>,
+    mut commands: Commands,
     mut query: Query<(&mut Transform, Option<&mut MovementController>)>,
     bounds: Res<LevelBounds>,
+    accessibility: Res<AccessibilityConfig>,
+    budget: Res<PerformanceBudget>,
 ) {
     if let Ok((mut transform, controller)) = query.get_mut(event.entity) {
         let rng = &mut rand::rng();
         let x = rng.random_range(bounds.min.x..=bounds.max.x);
         let z = rng.random_range(bounds.min.y..=bounds.max.y);
         let pos = Vec3::new(x, 0.0, z);
+        if !accessibility.reduced_motion && budget.visual_effects {
+            commands.spawn(teleport_poof(transform.translation, false));
+            commands.spawn(teleport_poof(pos, true));
+        }
         transform.translation = pos;
         if let Some(mut controller) = controller {
             controller.intent = pos.xz();
@@ -104,17 +168,100 @@ fn handle_random_teleport(
     }
 }
 
-#[derive(Component)]
-pub struct GoalLocation;
+/// A burst marking one end of a teleport, so it's clear where something went. It bursts outward
+/// where the teleport started and collapses inward where it ended.
+#[derive(Component, Debug)]
+struct TeleportPoof {
+    position: Vec3,
+    arriving: bool,
+    timer: Timer,
+}
+
+fn teleport_poof(position: Vec3, arriving: bool) -> impl Bundle {
+    (
+        Name::new("Teleport Poof"),
+        TeleportPoof {
+            position: position + Vec3::Y * 0.5,
+            arriving,
+            timer: Timer::from_seconds(TELEPORT_POOF_SECS, TimerMode::Once),
+        },
+        DespawnOnExit(GamePhase::Herding),
+    )
+}
+
+fn tick_teleport_poofs(
+    time: Res<Time>,
+    mut commands: Commands,
+    poof_query: Query<(Entity, &mut TeleportPoof)>,
+) {
+    for (entity, mut poof) in poof_query {
+        if poof.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn draw_teleport_poofs(poof_query: Query<&TeleportPoof>, mut gizmos: Gizmos) {
+    for poof in &poof_query {
+        let progress = poof.timer.fraction();
+        let radius = if poof.arriving {
+            TELEPORT_POOF_MAX_RADIUS.lerp(TELEPORT_POOF_MIN_RADIUS, progress)
+        } else {
+            TELEPORT_POOF_MIN_RADIUS.lerp(TELEPORT_POOF_MAX_RADIUS, progress)
+        };
+        gizmos.sphere(
+            Isometry3d::from_translation(poof.position),
+            radius,
+            TELEPORT_POOF_COLOR.with_alpha(1.0 - progress),
+        );
+    }
+}
+
+/// Somewhere sheep get counted.
+#[derive(Component, Debug)]
+pub struct GoalLocation {
+    /// The only color of sheep counted here, or `None` to count every sheep.
+    pub color: Option<SheepColor>,
+    /// Sheep that come within this distance start being counted.
+    pub radius: f32,
+}
+
+impl GoalLocation {
+    pub fn accepts(&self, color: &SheepColor) -> bool {
+        // Contrarian sheep don't get a pen of their own and share the white one.
+        let color = match color {
+            SheepColor::Contrarian => &SheepColor::White,
+            color => color,
+        };
+        self.color
+            .as_ref()
+            .is_none_or(|goal_color| goal_color == color)
+    }
+}
+
+/// Finds the position of the goal that sheep of `color` should be herded to, along with its radius.
+pub fn goal_for<'a>(
+    color: &SheepColor,
+    goals: impl IntoIterator<Item = (&'a Transform, &'a GoalLocation)>,
+) -> Option<(Vec3, f32)> {
+    goals
+        .into_iter()
+        .find(|(_, goal)| goal.accepts(color))
+        .map(|(transform, goal)| (transform.translation, goal.radius))
+}
 
 #[derive(Message, Debug, Clone)]
 pub struct GoalTextMessage {
     pub text: String,
     pub color: Option<Color>,
+    /// Where in the world to show the text. Defaults to the main goal.
+    pub position: Option<Vec3>,
 }
 
 #[derive(Component, Debug)]
 struct GoalFloatingText {
+    /// Where the text would have been placed before staggering.
+    anchor: Vec3,
     world_pos: Vec3,
     lifetime: Timer,
 }
@@ -122,13 +269,36 @@ struct GoalFloatingText {
 fn spawn_goal_text(
     mut commands: Commands,
     mut events: MessageReader<GoalTextMessage>,
-    goal_query: Query<&GlobalTransform, With<GoalLocation>>,
+    goal_query: Query<(), With<GoalLocation>>,
+    existing_text: Query<&GoalFloatingText>,
 ) {
-    let Some(goal_transform) = goal_query.iter().next() else {
+    // Only show text while the level is around.
+    if goal_query.is_empty() {
         return;
-    };
+    }
+
+    let mut recent_anchors: Vec<Vec3> = existing_text
+        .iter()
+        .filter(|text| text.lifetime.elapsed_secs() < GOAL_TEXT_STACK_WINDOW_SECS)
+        .map(|text| text.anchor)
+        .collect();
 
     for event in events.read() {
+        let anchor = event.position.unwrap_or(GOAL_POSITION) + Vec3::Y * GOAL_TEXT_HEIGHT_OFFSET;
+        let stack_index = recent_anchors
+            .iter()
+            .filter(|recent| recent.distance_squared(anchor) < 1.0)
+            .count()
+            % GOAL_TEXT_MAX_STACK;
+        recent_anchors.push(anchor);
+        // Alternate sides as the stack grows: centre, left, right, left...
+        let x_offset = match stack_index {
+            0 => 0.0,
+            i if i % 2 == 1 => -GOAL_TEXT_STAGGER_X,
+            _ => GOAL_TEXT_STAGGER_X,
+        };
+        let offset = Vec3::new(x_offset, stack_index as f32 * GOAL_TEXT_STACK_SPACING, 0.0);
+
         commands.spawn((
             Name::new("Goal Floating Text"),
             Node {
@@ -140,7 +310,8 @@ fn spawn_goal_text(
             TextColor(event.color.unwrap_or(Color::WHITE)),
             Pickable::IGNORE,
             GoalFloatingText {
-                world_pos: goal_transform.translation() + Vec3::Y * GOAL_TEXT_HEIGHT_OFFSET,
+                anchor,
+                world_pos: anchor + offset,
                 lifetime: Timer::from_seconds(GOAL_TEXT_LIFETIME_SECS, TimerMode::Once),
             },
             DespawnOnExit(Screen::Gameplay),
@@ -177,12 +348,15 @@ fn tick_goal_text(
     }
 }
 
-/// A system that spawns the main level.
+/// A system that spawns the main level. The level is respawned every round, so the music isn't
+/// part of it: see [`start_music`].
 pub fn spawn_level(
     mut commands: Commands,
     // mut gizmo_assets: ResMut<Assets<GizmoAsset>>,
     level_assets: Res<LevelAssets>,
+    sheep_assets: Res<SheepAssets>,
     game_state: Res<GameState>,
+    mut meshes: ResMut<Assets<Mesh>>,
     mut ambient_query: Query<&mut AmbientLight, With<MainCamera>>,
 ) {
     // let mut gizmo = GizmoAsset::new();
@@ -217,58 +391,142 @@ pub fn spawn_level(
         ambient.brightness = ambient_brightness;
     }
 
-    commands.spawn((
-        Name::new("Level"),
-        Visibility::default(),
-        DespawnOnExit(Screen::Gameplay),
-        DespawnOnExit(GamePhase::ModifierChoice),
-        Transform::default(),
-        children![
-            // (
-            //     Name::new("Gameplay Music"),
-            //     music(level_assets.music.clone())
-            // ),
-            SceneRoot(level_assets.arena.clone()),
-            (
-                Name::new("Sun"),
-                DirectionalLight {
-                    shadows_enabled: true,
-                    color: sun_color,
-                    ..Default::default()
+    let sorting = game_state.is_modifier_active(Modifier::SortingPens);
+    let level = commands
+        .spawn((
+            Name::new("Level"),
+            Visibility::default(),
+            DespawnOnExit(Screen::Gameplay),
+            DespawnOnExit(GamePhase::ModifierChoice),
+            Transform::default(),
+            children![
+                (LevelArena, SceneRoot(level_assets.arena.clone())),
+                (
+                    Name::new("Sun"),
+                    DirectionalLight {
+                        shadows_enabled: true,
+                        color: sun_color,
+                        ..Default::default()
+                    },
+                    sun_transform
+                ),
+                (
+                    Name::new("Goal"),
+                    GoalLocation {
+                        color: sorting.then_some(SheepColor::White),
+                        radius: GOAL_RADIUS,
+                    },
+                    Transform::from_translation(GOAL_POSITION),
+                    // Gizmo {
+                    //     handle: gizmo_assets.add(gizmo),
+                    //     line_config: GizmoLineConfig {
+                    //         width: 0.5,
+                    //         ..Default::default()
+                    //     },
+                    //     ..Default::default()
+                    // }
+                )
+            ],
+        ))
+        .id();
+
+    if sorting {
+        let pen_mesh = meshes.add(Circle::new(PEN_RADIUS));
+        for (color, position) in PEN_POSITIONS {
+            let material = sheep_assets.wool_material(&color);
+            commands.entity(level).with_child((
+                Name::new("Pen"),
+                GoalLocation {
+                    color: Some(color),
+                    radius: PEN_RADIUS,
                 },
-                sun_transform
-            ),
-            (
-                Name::new("Goal"),
-                GoalLocation,
-                Transform::from_translation(GOAL_POSITION),
-                // Gizmo {
-                //     handle: gizmo_assets.add(gizmo),
-                //     line_config: GizmoLineConfig {
-                //         width: 0.5,
-                //         ..Default::default()
-                //     },
-                //     ..Default::default()
-                // }
-            )
-        ],
-    ));
+                Transform::from_translation(position),
+                Visibility::default(),
+                children![(
+                    Mesh3d(pen_mesh.clone()),
+                    MeshMaterial3d(material),
+                    Transform::from_xyz(0.0, 0.02, 0.0)
+                        .with_rotation(Quat::from_rotation_x(-std::f32::consts::FRAC_PI_2)),
+                )],
+            ));
+        }
+    }
 }
 
+/// Starts the layered gameplay music. This is the only place gameplay music is spawned: it runs once
+/// on entering [`Screen::Gameplay`] and keeps playing through every round and phase, with each phase
+/// picking which layers are heard through [`BgmConfig`]. All three layers are spawned together so
+/// they stay in sync, and [`fade_out_music`](crate::audio::fade_out_music) fades them all out on
+/// leaving the screen.
 pub fn start_music(mut commands: Commands, assets: Res<LevelAssets>) {
     commands.spawn((
-        music(assets.bgm_layer_1.clone()),
-        MusicLayer::Base,
-        DespawnOnExit(Screen::Gameplay),
+        Name::new("Gameplay Music"),
+        music_layer(assets.bgm_layer_1.clone(), MusicLayer::Base),
     ));
     commands.spawn((
-        music(assets.bgm_layer_2.clone()),
-        MusicLayer::Extra,
-        DespawnOnExit(Screen::Gameplay),
+        Name::new("Gameplay Music"),
+        music_layer(assets.bgm_layer_2.clone(), MusicLayer::Extra),
     ));
     commands.spawn((
-        music(assets.bgm_layer_3.clone()),
-        MusicLayer::Perc,
-        DespawnOnExit(Screen::Gameplay),
+        Name::new("Gameplay Music"),
+        music_layer(assets.bgm_layer_3.clone(), MusicLayer::Perc),
     ));
 }
+
+/// Plays just the base layer on the title screen, for a calmer mood than the full gameplay mix.
+/// Waits for the music to load, and for any previous music to start fading out.
+pub fn start_title_music(
+    mut commands: Commands,
+    assets: Res<LevelAssets>,
+    mut bgm_config: ResMut<BgmConfig>,
+    playing: Query<(), (With<MusicLayer>, Without<FadingOut>)>,
+) {
+    if !playing.is_empty() {
+        return;
+    }
+    bgm_config.base_enabled = true;
+    bgm_config.extra_enabled = false;
+    bgm_config.percussion_enabled = false;
+    commands.spawn(music_layer(assets.bgm_layer_1.clone(), MusicLayer::Base));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct RoundsStarted(u32);
+
+    fn start_round(mut started: ResMut<RoundsStarted>) {
+        started.0 += 1;
+    }
+
+    #[test]
+    fn round_waits_for_a_delayed_arena_load() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<RoundsStarted>()
+            .add_systems(Update, start_round.run_if(level_ready));
+
+        app.update();
+        assert_eq!(app.world().resource::<RoundsStarted>().0, 0);
+
+        // The goal exists, but the arena scene is still loading.
+        app.world_mut().spawn((
+            GoalLocation {
+                color: None,
+                radius: GOAL_RADIUS,
+            },
+            Transform::from_translation(GOAL_POSITION),
+        ));
+        let arena = app.world_mut().spawn(LevelArena).id();
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<RoundsStarted>().0, 0);
+
+        app.world_mut().entity_mut(arena).insert(LevelArenaReady);
+        app.update();
+        assert_eq!(app.world().resource::<RoundsStarted>().0, 1);
+    }
+}
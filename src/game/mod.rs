@@ -1,25 +1,55 @@
 use bevy::prelude::*;
 
-use crate::{game::level::start_music, screens::Screen};
+use crate::{
+    audio::fade_out_music,
+    game::level::{LevelAssets, start_music, start_title_music},
+    screens::Screen,
+};
 
 pub mod camera;
+pub mod challenge;
+pub mod config;
+pub mod goal_marker;
+pub mod herd_aura;
+pub mod herd_command;
+pub mod herding_hint;
 pub mod level;
 pub mod modifiers;
 pub mod movement;
 pub mod player;
+pub mod playtime;
+pub mod rng;
 pub mod sheep;
+pub mod sheep_bleats;
+pub mod sheep_names;
 pub mod state;
 pub mod ufo;
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_plugins(config::plugin);
     app.add_plugins((
         level::plugin,
         movement::plugin,
         player::plugin,
+        playtime::plugin,
+        rng::plugin,
         sheep::plugin,
+        sheep_bleats::plugin,
+        sheep_names::plugin,
         camera::plugin,
+        challenge::plugin,
+        herd_aura::plugin,
+        herd_command::plugin,
+        herding_hint::plugin,
         state::plugin,
         ufo::plugin,
     ));
+    app.add_plugins(goal_marker::plugin);
     app.add_systems(OnEnter(Screen::Gameplay), start_music);
+    app.add_systems(
+        Update,
+        start_title_music.run_if(in_state(Screen::Title).and(resource_exists::<LevelAssets>)),
+    );
+    app.add_systems(OnExit(Screen::Title), fade_out_music);
+    app.add_systems(OnExit(Screen::Gameplay), fade_out_music);
 }
@@ -3,8 +3,16 @@ use rand::{
     Rng,
     distr::{Distribution, StandardUniform},
 };
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
+/// With dynamic modifiers on, [`Modifier::MoonGravity`] reaches full strength this far through
+/// the round.
+const MOON_GRAVITY_RAMP_PROGRESS: f32 = 0.15;
+/// With dynamic modifiers on, [`Modifier::HyperSheep`] starts the round at this strength and
+/// builds to full strength as time runs out.
+const HYPER_SHEEP_START_INTENSITY: f32 = 0.4;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect, Serialize, Deserialize)]
 pub enum Modifier {
     HyperSheep,
     MoonGravity,
@@ -17,9 +25,29 @@ pub enum Modifier {
     DogSphere,
     FeverDream,
     SheepTeleport,
+    SortingPens,
+    /// Sheep split in two when barked at. Not to be confused with the charm shown as "Mitosis",
+    /// [`Charm::Exponential`](crate::game::state::shop::items::Charm::Exponential).
+    Mitosis,
 }
 
 impl Modifier {
+    pub const ALL: [Modifier; 13] = [
+        Modifier::HyperSheep,
+        Modifier::MoonGravity,
+        Modifier::Ufo,
+        Modifier::Space,
+        Modifier::TeleportingBark,
+        Modifier::Vignette,
+        Modifier::Night,
+        Modifier::SheepSphere,
+        Modifier::DogSphere,
+        Modifier::FeverDream,
+        Modifier::SheepTeleport,
+        Modifier::SortingPens,
+        Modifier::Mitosis,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             Modifier::HyperSheep => "Hyper Sheep",
@@ -33,6 +61,8 @@ impl Modifier {
             Modifier::DogSphere => "Spherical",
             Modifier::FeverDream => "Feverdream",
             Modifier::SheepTeleport => "Teleporting Sheep",
+            Modifier::SortingPens => "Sorting Pens",
+            Modifier::Mitosis => "Cell Division",
         }
     }
 
@@ -40,7 +70,9 @@ impl Modifier {
         match self {
             Modifier::HyperSheep => "Sheep move faster and hop higher.",
             Modifier::MoonGravity => "Lower gravity makes sheep floaty.",
-            Modifier::Ufo => "A UFO will fly around and occasionally abduct a sheep.",
+            Modifier::Ufo => {
+                "A UFO will fly around and occasionally abduct a sheep. Bark at it to make it drop the sheep."
+            }
             Modifier::Space => "You fly around with floaty, frictionless movement.",
             Modifier::TeleportingBark => {
                 "Every time you bark you'll be teleported to a random location."
@@ -48,16 +80,62 @@ impl Modifier {
             Modifier::Vignette => {
                 "The clouds around the edges of the screen grow bigger, restricting your visibility."
             }
-            Modifier::Night => "The sheep start asleep.",
+            Modifier::Night => "The sheep start asleep. Bark or walk up to them to wake them.",
             Modifier::SheepSphere => "Sheep roll around like a ball.",
             Modifier::DogSphere => "You roll around like a ball.",
             Modifier::FeverDream => "Increases the intensity of certain other active modifiers",
             Modifier::SheepTeleport => {
                 "Sheep that hear your bark will teleport to a random location."
             }
+            Modifier::SortingPens => {
+                "Each color of sheep must be herded into its matching pen. Sorted sheep are worth an extra point."
+            }
+            Modifier::Mitosis => {
+                "Sheep that hear your bark may split in two. The copies only last for the round."
+            }
+        }
+    }
+
+    /// How this modifier changes the movement of sheep spawned while it's active, if at all.
+    /// `fever_dream` is whether [`Modifier::FeverDream`] is also active.
+    pub fn sheep_movement_effect(&self, fever_dream: bool) -> Option<SheepMovementEffect> {
+        match self {
+            Modifier::HyperSheep => Some(SheepMovementEffect {
+                move_speed_mult: 1.3,
+                hop_speed_mult: if fever_dream { 2.0 * 1.5 } else { 2.0 },
+                hop_interval_mult: 0.1,
+                ..default()
+            }),
+            Modifier::MoonGravity => Some(SheepMovementEffect {
+                hop_speed_mult: 0.5,
+                extra_hop_secs: if fever_dream { 0.5 + 0.5 } else { 0.5 },
+                jump_height_mult: if fever_dream { 6.0 * 1.5 } else { 6.0 },
+                ..default()
+            }),
+            _ => None,
         }
     }
 
+    /// How strongly this modifier applies, from 0 to 1, when `progress` (0 to 1) of the round has
+    /// passed. Only used with dynamic modifiers on, otherwise every modifier applies fully.
+    pub fn intensity(&self, progress: f32) -> f32 {
+        match self {
+            // Gravity eases off over the start of the round, rather than sheep floating at once.
+            Modifier::MoonGravity => (progress / MOON_GRAVITY_RAMP_PROGRESS).min(1.0),
+            // Sheep get more wound up as time runs low.
+            Modifier::HyperSheep => {
+                HYPER_SHEEP_START_INTENSITY + (1.0 - HYPER_SHEEP_START_INTENSITY) * progress
+            }
+            _ => 1.0,
+        }
+    }
+
+    /// The concrete numbers behind this modifier's effect, for showing on cards.
+    pub fn intensity_text(&self) -> Option<String> {
+        self.sheep_movement_effect(false)
+            .map(|effect| effect.describe())
+    }
+
     pub fn difficulty(&self) -> ModifierDifficulty {
         use ModifierDifficulty::*;
         match self {
@@ -72,26 +150,75 @@ impl Modifier {
             Modifier::DogSphere => Easy,
             Modifier::FeverDream => Hard,
             Modifier::SheepTeleport => Hard,
+            Modifier::SortingPens => Hard,
+            Modifier::Mitosis => Medium,
         }
     }
 }
 
 impl Distribution<Modifier> for StandardUniform {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Modifier {
-        let pool = vec![
-            Modifier::HyperSheep,
-            Modifier::MoonGravity,
-            Modifier::Ufo,
-            Modifier::Space,
-            Modifier::TeleportingBark,
-            Modifier::Vignette,
-            Modifier::Night,
-            Modifier::SheepSphere,
-            Modifier::DogSphere,
-            Modifier::FeverDream,
-            Modifier::SheepTeleport,
-        ];
-        pool[rng.random_range(0..pool.len())]
+        Modifier::ALL[rng.random_range(0..Modifier::ALL.len())]
+    }
+}
+
+/// Multipliers applied on top of the base sheep movement parameters in `sheep()`, and every frame
+/// while dynamic modifiers are on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SheepMovementEffect {
+    pub move_speed_mult: f32,
+    pub hop_speed_mult: f32,
+    /// Multiplier on the pause between hops.
+    pub hop_interval_mult: f32,
+    /// Seconds added to the length of each hop.
+    pub extra_hop_secs: f32,
+    pub jump_height_mult: f32,
+}
+
+impl Default for SheepMovementEffect {
+    fn default() -> Self {
+        Self {
+            move_speed_mult: 1.0,
+            hop_speed_mult: 1.0,
+            hop_interval_mult: 1.0,
+            extra_hop_secs: 0.0,
+            jump_height_mult: 1.0,
+        }
+    }
+}
+
+impl SheepMovementEffect {
+    /// This effect at `intensity` of its full strength, where 0 leaves movement unchanged.
+    pub fn scaled(&self, intensity: f32) -> Self {
+        let scale = |mult: f32| 1.0 + (mult - 1.0) * intensity;
+        Self {
+            move_speed_mult: scale(self.move_speed_mult),
+            hop_speed_mult: scale(self.hop_speed_mult),
+            hop_interval_mult: scale(self.hop_interval_mult),
+            extra_hop_secs: self.extra_hop_secs * intensity,
+            jump_height_mult: scale(self.jump_height_mult),
+        }
+    }
+
+    /// Lists the parameters this effect changes, e.g. "Move speed x1.3, Hop speed x2".
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.move_speed_mult != 1.0 {
+            parts.push(format!("Move speed x{}", self.move_speed_mult));
+        }
+        if self.hop_speed_mult != 1.0 {
+            parts.push(format!("Hop speed x{}", self.hop_speed_mult));
+        }
+        if self.hop_interval_mult != 1.0 {
+            parts.push(format!("Time between hops x{}", self.hop_interval_mult));
+        }
+        if self.extra_hop_secs != 0.0 {
+            parts.push(format!("Hop length +{}s", self.extra_hop_secs));
+        }
+        if self.jump_height_mult != 1.0 {
+            parts.push(format!("Jump height x{}", self.jump_height_mult));
+        }
+        parts.join(", ")
     }
 }
 
@@ -1,5 +1,7 @@
 //! Player-specific behavior.
 
+use std::f32::consts::FRAC_PI_2;
+
 use bevy::{light::NotShadowCaster, prelude::*};
 
 use crate::{
@@ -7,16 +9,44 @@ use crate::{
     asset_tracking::LoadResource,
     audio::sound_effect,
     game::{
-        level::RandomTeleport,
+        config::PlayerConfig,
+        level::{LevelBounds, RandomTeleport},
         modifiers::Modifier,
         movement::MovementController,
-        sheep::Sheep,
-        state::{GamePhase, GameState},
+        sheep::{self, Sheep, SheepAssets, SheepColor, SheepState},
+        state::{
+            GamePhase, GameState, RoundStats, RunStatistics,
+            shop::items::{Charm, ECHO_BARK_RADIUS_PER_SHEEP, ECHO_MAX_BARK_RADIUS_BONUS},
+        },
+        ufo::Ufo,
     },
+    performance::PerformanceBudget,
+    settings::AccessibilityConfig,
 };
 
+/// Left stick tilt below this is treated as no input, so a worn stick doesn't drift the player.
+const STICK_DEADZONE: f32 = 0.15;
+/// Always move the player, alongside the keys in [`KeyBindings`].
+const ARROW_KEYS: [KeyCode; 4] = [
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+];
+/// Always barks, alongside [`KeyBindings::bark`].
+const FIXED_BARK_KEY: KeyCode = KeyCode::Space;
+const BARK_BUTTON: GamepadButton = GamepadButton::South;
+/// With [`Modifier::Mitosis`], the chance a sheep that hears a bark splits in two. Doubled with
+/// [`Modifier::FeverDream`].
+const MITOSIS_SPLIT_CHANCE: f64 = 0.15;
+/// Sheep stop splitting once there are this many, so the physics doesn't get bogged down.
+const MITOSIS_MAX_SHEEP: usize = 120;
+/// How far from the original sheep its copy appears.
+const MITOSIS_SPLIT_OFFSET: f32 = 1.0;
+
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<PlayerAssets>();
+    app.init_resource::<KeyBindings>();
 
     app.add_systems(
         Update,
@@ -27,11 +57,15 @@ pub(super) fn plugin(app: &mut App) {
     );
     app.add_systems(
         Update,
-        tick_player_timers
+        (tick_player_timers, tick_bark_shockwaves)
             .run_if(in_state(GamePhase::Herding))
             .in_set(AppSystems::TickTimers)
             .in_set(PausableSystems),
     );
+    app.add_systems(
+        Update,
+        draw_bark_shockwaves.run_if(in_state(GamePhase::Herding)),
+    );
     app.add_systems(
         Update,
         init_player_gear_visuals
@@ -40,8 +74,51 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// The keys the player moves and barks with, changed from the controls menu. The arrow keys and
+/// Space work too, whatever is bound here.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct KeyBindings {
+    pub up: KeyCode,
+    pub down: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub bark: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            up: KeyCode::KeyW,
+            down: KeyCode::KeyS,
+            left: KeyCode::KeyA,
+            right: KeyCode::KeyD,
+            bark: KeyCode::KeyE,
+        }
+    }
+}
+
+impl KeyBindings {
+    fn bark_keys(&self) -> [KeyCode; 2] {
+        [self.bark, FIXED_BARK_KEY]
+    }
+
+    /// The bound movement keys followed by the arrow keys, in the order up, down, left, right.
+    fn move_keys(&self) -> [KeyCode; 8] {
+        let [up, down, left, right] = ARROW_KEYS;
+        [
+            self.up, self.down, self.left, self.right, up, down, left, right,
+        ]
+    }
+}
+
 /// The player character.
-pub fn player(player_assets: &PlayerAssets, bark_radius: f32, is_sphere: bool) -> impl Bundle {
+pub fn player(
+    player_assets: &PlayerAssets,
+    config: &PlayerConfig,
+    bark_radius: f32,
+    is_sphere: bool,
+) -> impl Bundle {
     let scene = if is_sphere {
         player_assets.scene_sphere.clone()
     } else {
@@ -49,7 +126,7 @@ pub fn player(player_assets: &PlayerAssets, bark_radius: f32, is_sphere: bool) -
     };
     (
         Name::new("Player"),
-        Player::new(bark_radius),
+        Player::new(config, bark_radius),
         SceneRoot(scene),
         Transform::from_xyz(0.0, 0.0, 0.0),
         MovementController::new(3.0),
@@ -63,51 +140,166 @@ pub struct Player {
     pub bark_radius: f32,
     pub sheep_interact_radius: f32,
     pub bark_cooldown: Timer,
+    /// Used up by barking, and regained over time. See [`PlayerConfig::bark_stamina_cost`].
+    pub stamina: f32,
+    pub max_stamina: f32,
+    bark_stamina_cost: f32,
+    stamina_regen_per_second: f32,
 }
 impl Player {
-    pub fn new(bark_radius: f32) -> Self {
+    pub fn new(config: &PlayerConfig, bark_radius: f32) -> Self {
         Self {
             bark_radius,
-            sheep_interact_radius: 7.0,
-            bark_cooldown: Timer::from_seconds(2.0, TimerMode::Once),
+            sheep_interact_radius: config.sheep_interact_radius,
+            bark_cooldown: Timer::from_seconds(config.bark_cooldown_seconds, TimerMode::Once),
+            stamina: config.max_stamina,
+            max_stamina: config.max_stamina,
+            bark_stamina_cost: config.bark_stamina_cost,
+            stamina_regen_per_second: config.stamina_regen_per_second,
         }
     }
+
+    pub fn has_bark_stamina(&self) -> bool {
+        self.stamina >= self.bark_stamina_cost
+    }
+
+    pub fn can_bark(&self) -> bool {
+        self.bark_cooldown.is_finished() && self.has_bark_stamina()
+    }
+}
+
+/// How long a bark's shockwave ring takes to reach the edge of the bark radius.
+const BARK_SHOCKWAVE_SECONDS: f32 = 0.35;
+/// Just above the ground, so the ring isn't hidden by it.
+const BARK_SHOCKWAVE_HEIGHT: f32 = 0.1;
+
+/// A ring expanding out from where the player barked, fading as it reaches the bark radius.
+#[derive(Component, Debug)]
+struct BarkShockwave {
+    origin: Vec3,
+    radius: f32,
+    timer: Timer,
 }
 
 fn tick_player_timers(time: Res<Time>, player_query: Query<&mut Player>) {
     for mut player in player_query {
-        player.bark_cooldown.tick(time.delta());
+        // Stamina only comes back once the cooldown is over, so tapping faster than the cooldown
+        // doesn't earn any regen in between.
+        if player.bark_cooldown.tick(time.delta()).is_finished() {
+            let regen = player.stamina_regen_per_second * time.delta_secs();
+            player.stamina = (player.stamina + regen).min(player.max_stamina);
+        }
     }
 }
 
+/// How much further barks reach thanks to [`Charm::Echo`]. Builds up as sheep are counted, and
+/// goes back to nothing with the round stats at the start of each round.
+fn echo_bark_radius_bonus(game_state: &GameState, round_stats: &RoundStats) -> f32 {
+    if !game_state.is_charm_active(Charm::Echo) {
+        return 0.0;
+    }
+    (f32::from(round_stats.sheep_counted) * ECHO_BARK_RADIUS_PER_SHEEP)
+        .min(ECHO_MAX_BARK_RADIUS_BONUS)
+}
+
 fn handle_bark(
     player_query: Query<(Entity, &mut Player, &Transform)>,
     mut sheep_query: Query<(Entity, &mut Sheep, &Transform), Without<Player>>,
+    mut ufo_query: Query<(&mut Ufo, &Transform), (Without<Player>, Without<Sheep>)>,
     input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
     mut commands: Commands,
     game_state: Res<GameState>,
+    round_stats: Res<RoundStats>,
+    mut run_stats: ResMut<RunStatistics>,
     assets: Res<PlayerAssets>,
+    sheep_assets: Res<SheepAssets>,
+    bounds: Res<LevelBounds>,
+    accessibility: Res<AccessibilityConfig>,
+    budget: Res<PerformanceBudget>,
 ) {
-    if input.just_pressed(KeyCode::KeyE) || input.just_pressed(KeyCode::Space) {
+    let radius_bonus = echo_bark_radius_bonus(&game_state, &round_stats);
+    let wants_bark = if accessibility.auto_bark {
+        input.any_pressed(bindings.bark_keys())
+            || gamepads.iter().any(|pad| pad.pressed(BARK_BUTTON))
+    } else {
+        input.any_just_pressed(bindings.bark_keys())
+            || gamepads.iter().any(|pad| pad.just_pressed(BARK_BUTTON))
+    };
+    if wants_bark {
         for (entity, mut player, player_transform) in player_query {
-            if player.bark_cooldown.is_finished() {
+            if player.can_bark() {
                 let player_pos = player_transform.translation.xz();
                 player.bark_cooldown.reset();
+                player.stamina -= player.bark_stamina_cost;
+                run_stats.barks += 1;
+                let bark_radius = player.bark_radius + radius_bonus;
+                let mut sheep_count = sheep_query.iter().count();
                 for (sheep_entity, mut sheep, sheep_transform) in sheep_query.iter_mut() {
                     let sheep_pos = sheep_transform.translation.xz();
-                    if player_pos.distance_squared(sheep_pos)
-                        <= player.bark_radius * player.bark_radius
-                    {
+                    if player_pos.distance_squared(sheep_pos) <= bark_radius * bark_radius {
                         if game_state.is_modifier_active(Modifier::SheepTeleport) {
                             commands.trigger(RandomTeleport {
                                 entity: sheep_entity,
                             });
                         } else {
+                            if sheep_count < mitosis_sheep_cap(&budget)
+                                && rolls_mitosis_split(&game_state, &sheep)
+                            {
+                                let offset = Vec2::from_angle(rand::random_range(
+                                    0.0..std::f32::consts::TAU,
+                                )) * MITOSIS_SPLIT_OFFSET;
+                                let pos = bounds.clamp_to_bounds(sheep_pos + offset);
+                                let copy = sheep::sheep(
+                                    &mut commands,
+                                    &sheep_assets,
+                                    Vec3::new(pos.x, 0.0, pos.y),
+                                    &game_state,
+                                    sheep.color().clone(),
+                                    false,
+                                );
+                                // The copy runs from the bark along with the original.
+                                commands
+                                    .entity(copy)
+                                    .insert(DespawnOnExit(GamePhase::Herding))
+                                    .entry::<Sheep>()
+                                    .and_modify(move |mut copy| copy.become_spooked(player_pos));
+                                sheep_count += 1;
+                            }
                             sheep.become_spooked(player_pos);
                         }
                     }
                 }
+                if game_state.is_modifier_active(Modifier::Ufo) {
+                    // Barking at a UFO mid-abduction makes it drop the sheep.
+                    for (mut ufo, ufo_transform) in &mut ufo_query {
+                        let ufo_pos = ufo_transform.translation.xz();
+                        if player_pos.distance_squared(ufo_pos) > bark_radius * bark_radius {
+                            continue;
+                        }
+                        let Some(target) = ufo.abducting() else {
+                            continue;
+                        };
+                        if let Ok((_, mut sheep, _)) = sheep_query.get_mut(target)
+                            && sheep::drop_abducted_sheep(&mut commands, target, &mut sheep)
+                        {
+                            ufo.drop_sheep();
+                        }
+                    }
+                }
                 commands.spawn(sound_effect(assets.bark.clone()));
+                if !accessibility.reduced_motion && budget.visual_effects {
+                    commands.spawn((
+                        Name::new("Bark Shockwave"),
+                        BarkShockwave {
+                            origin: Vec3::new(player_pos.x, BARK_SHOCKWAVE_HEIGHT, player_pos.y),
+                            radius: bark_radius,
+                            timer: Timer::from_seconds(BARK_SHOCKWAVE_SECONDS, TimerMode::Once),
+                        },
+                        DespawnOnExit(GamePhase::Herding),
+                    ));
+                }
                 if game_state.is_modifier_active(Modifier::TeleportingBark) {
                     commands.trigger(RandomTeleport { entity });
                 }
@@ -116,29 +308,127 @@ fn handle_bark(
     }
 }
 
+/// The most sheep [`Modifier::Mitosis`] can split up to, lowered to the performance budget's cap.
+fn mitosis_sheep_cap(budget: &PerformanceBudget) -> usize {
+    budget
+        .max_sheep
+        .map_or(MITOSIS_MAX_SHEEP, |max| max.min(MITOSIS_MAX_SHEEP))
+}
+
+/// Whether a sheep that just heard a bark splits in two under [`Modifier::Mitosis`]. Only calm
+/// sheep split. Gold and green sheep never do, since copies would print money and time.
+fn rolls_mitosis_split(game_state: &GameState, sheep: &Sheep) -> bool {
+    if !game_state.is_modifier_active(Modifier::Mitosis)
+        || !matches!(
+            sheep.state(),
+            SheepState::Wander(_) | SheepState::Evading(_)
+        )
+        || matches!(sheep.color(), SheepColor::Gold | SheepColor::Green)
+    {
+        return false;
+    }
+    let chance = if game_state.is_modifier_active(Modifier::FeverDream) {
+        MITOSIS_SPLIT_CHANCE * 2.0
+    } else {
+        MITOSIS_SPLIT_CHANCE
+    };
+    rand::random_bool(chance)
+}
+
+fn tick_bark_shockwaves(
+    time: Res<Time>,
+    mut commands: Commands,
+    shockwave_query: Query<(Entity, &mut BarkShockwave)>,
+) {
+    for (entity, mut shockwave) in shockwave_query {
+        shockwave.timer.tick(time.delta());
+        if shockwave.timer.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn draw_bark_shockwaves(shockwave_query: Query<&BarkShockwave>, mut gizmos: Gizmos) {
+    for shockwave in &shockwave_query {
+        let progress = shockwave.timer.fraction();
+        // Ease out, so the ring bursts outward and slows as it reaches the edge.
+        let expansion = 1.0 - (1.0 - progress).powi(2);
+        gizmos.circle(
+            Isometry3d::new(shockwave.origin, Quat::from_rotation_x(FRAC_PI_2)),
+            shockwave.radius * expansion,
+            Color::WHITE.with_alpha(1.0 - progress),
+        );
+    }
+}
+
+/// Which device last started giving movement input, to decide between the two while both are.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum MoveInputDevice {
+    #[default]
+    Keyboard,
+    Gamepad,
+}
+
 fn record_player_directional_input(
     input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    gamepads: Query<&Gamepad>,
     time: Res<Time>,
+    mut last_device: Local<MoveInputDevice>,
+    mut stick_was_active: Local<bool>,
     mut controller_query: Query<&mut MovementController, With<Player>>,
 ) {
     // Collect directional input.
+    let move_keys = bindings.move_keys();
+    let [
+        up,
+        down,
+        left,
+        right,
+        arrow_up,
+        arrow_down,
+        arrow_left,
+        arrow_right,
+    ] = move_keys;
     let mut intent = Vec2::ZERO;
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+    if input.pressed(up) || input.pressed(arrow_up) {
         intent.y -= 1.0;
     }
-    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+    if input.pressed(down) || input.pressed(arrow_down) {
         intent.y += 1.0;
     }
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+    if input.pressed(left) || input.pressed(arrow_left) {
         intent.x -= 1.0;
     }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+    if input.pressed(right) || input.pressed(arrow_right) {
         intent.x += 1.0;
     }
 
     // Normalize intent so that diagonal movement is the same speed as horizontal / vertical.
-    // This should be omitted if the input comes from an analog stick instead.
-    let intent = intent.normalize_or_zero();
+    let keyboard_intent = intent.normalize_or_zero();
+
+    // The stick isn't normalized, so a partial tilt moves the player slower. Stick up is +y, but
+    // up the screen is -y in the world. A disconnected gamepad simply stops being listed.
+    let stick_intent = gamepads
+        .iter()
+        .map(Gamepad::left_stick)
+        .find(|stick| stick.length() > STICK_DEADZONE)
+        .map_or(Vec2::ZERO, |stick| {
+            Vec2::new(stick.x, -stick.y).clamp_length_max(1.0)
+        });
+
+    let stick_active = stick_intent != Vec2::ZERO;
+    if input.any_just_pressed(move_keys) {
+        *last_device = MoveInputDevice::Keyboard;
+    } else if stick_active && !*stick_was_active {
+        *last_device = MoveInputDevice::Gamepad;
+    }
+    *stick_was_active = stick_active;
+    let intent = match (keyboard_intent != Vec2::ZERO, stick_active) {
+        (true, true) if *last_device == MoveInputDevice::Gamepad => stick_intent,
+        (true, _) => keyboard_intent,
+        (false, _) => stick_intent,
+    };
 
     // Apply movement intent to controllers.
     for mut controller in &mut controller_query {
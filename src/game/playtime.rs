@@ -0,0 +1,88 @@
+//! Tracks how long the player has spent playing, both for the current run and across sessions.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PausableSystems,
+    game::state::GamePhase,
+    persistence::{PersistResource, Persistent},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.persist_resource::<LifetimePlaytime>();
+    app.init_resource::<Playtime>();
+
+    app.add_systems(
+        Update,
+        (
+            tick_run_playtime.in_set(PausableSystems),
+            tick_lifetime_playtime,
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    // Saving writes to disk, so only flush the lifetime total at natural breaks.
+    app.add_systems(OnEnter(GamePhase::Shop), flush_lifetime_playtime);
+    app.add_systems(OnExit(Screen::Gameplay), flush_lifetime_playtime);
+    // Before `Last`, where changed resources get saved.
+    app.add_systems(
+        PostUpdate,
+        flush_lifetime_playtime.run_if(on_message::<AppExit>),
+    );
+}
+
+/// Total time spent in gameplay across every session, including time spent paused.
+#[derive(Resource, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct LifetimePlaytime {
+    pub total: Duration,
+}
+
+impl Persistent for LifetimePlaytime {
+    const FILE_NAME: &'static str = "playtime.ron";
+}
+
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct Playtime {
+    /// Time spent in the current run, not counting time paused. Frozen once the run ends.
+    pub run: Duration,
+    /// Lifetime playtime that hasn't been added to [`LifetimePlaytime`] yet.
+    unsaved: Duration,
+}
+
+impl Playtime {
+    pub fn lifetime(&self, saved: &LifetimePlaytime) -> Duration {
+        saved.total + self.unsaved
+    }
+}
+
+/// Formats a duration as `HH:MM:SS`.
+pub fn format_hms(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
+
+fn tick_run_playtime(time: Res<Time<Real>>, mut playtime: ResMut<Playtime>) {
+    playtime.run += time.delta();
+}
+
+fn tick_lifetime_playtime(time: Res<Time<Real>>, mut playtime: ResMut<Playtime>) {
+    playtime.unsaved += time.delta();
+}
+
+fn flush_lifetime_playtime(mut playtime: ResMut<Playtime>, mut lifetime: ResMut<LifetimePlaytime>) {
+    if playtime.unsaved.is_zero() {
+        return;
+    }
+    lifetime.total += std::mem::take(&mut playtime.unsaved);
+}
@@ -0,0 +1,40 @@
+//! Seeded randomness for run-level decisions, so a run can be replayed from its seed.
+
+use bevy::prelude::*;
+use rand::{SeedableRng, rngs::StdRng};
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(GameRng::from_seed(rand::random()));
+}
+
+/// The random number generator for everything that decides the shape of a run, like which
+/// modifiers and shop items are offered. Reseeded at the start of every run.
+///
+/// Moment-to-moment randomness (sheep wandering, spawn positions) doesn't go through this, since
+/// it depends on how the player plays anyway.
+#[derive(Resource, Debug, Deref, DerefMut)]
+pub struct GameRng {
+    seed: u64,
+    #[deref]
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Randomness for choices made from a run's `seed` before the run starts, like the draft pool.
+    /// It's kept apart from the run's own sequence, so making those choices doesn't change what
+    /// comes up later.
+    pub fn pre_run(seed: u64) -> StdRng {
+        StdRng::seed_from_u64(seed ^ 0x5eed_d4af_7000_0000)
+    }
+}
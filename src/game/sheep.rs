@@ -2,51 +2,186 @@
 
 use std::{collections::HashMap, time::Duration};
 
-use bevy::{gltf::GltfMaterialName, math::ops::floor, prelude::*, scene::SceneInstanceReady};
-use rand::Rng;
+use bevy::{gltf::GltfMaterialName, light::NotShadowCaster, prelude::*, scene::SceneInstanceReady};
+use rand::{Rng, seq::IteratorRandom};
 
 use crate::{
     AppSystems, PausableSystems,
     asset_tracking::LoadResource,
     game::{
-        level::{GOAL_POSITION, GOAL_RADIUS, GoalLocation, GoalTextMessage, LevelBounds},
+        config::{GameConfig, HerdConfig},
+        level::{GOAL_POSITION, GOAL_RADIUS, GoalLocation, GoalTextMessage, LevelBounds, goal_for},
         modifiers::Modifier,
         movement::{HopMovementController, MovementController, SphereMovementController},
         player::Player,
-        state::{GamePhase, GameState, RoundStats, shop::items::Charm},
+        state::{
+            GameMode, GamePhase, GameState, RoundStats, RunStatistics, gentle_mode,
+            ranked::CountPace,
+            shop::items::{Charm, EVOLUTION_WHITE_SHEEP_PER_BLUE},
+        },
         ufo::UFO_HEIGHT,
     },
+    performance::PerformanceBudget,
     screens::Screen,
+    settings::{AccessibilityConfig, GameplaySettings},
 };
 
 const ABDUCTION_ASCENT_SPEED: f32 = 6.0;
-const HERD_RADIUS: f32 = 10.0;
-const HERD_RADIUS_SQ: f32 = HERD_RADIUS * HERD_RADIUS;
-const HERD_SEPARATION_RADIUS: f32 = 2.4;
-const HERD_SEPARATION_RADIUS_SQ: f32 = HERD_SEPARATION_RADIUS * HERD_SEPARATION_RADIUS;
-const HERD_CELL_SIZE: f32 = HERD_RADIUS;
-const HERD_COHESION_WEIGHT: f32 = 0.9;
-const HERD_SEPARATION_WEIGHT: f32 = 1.5;
-const HERD_EVADE_BLEND: f32 = 0.55;
-const HERD_WANDER_JITTER: f32 = 0.35;
-const HERD_UPDATE_INTERVAL_SECS: f32 = 0.10;
-const HERD_UPDATE_BUCKETS: u64 = 4;
-const HERD_MAX_NEIGHBORS: usize = 20;
+/// How long a sheep dropped by a UFO waits after landing before it hops again.
+const LANDED_HOP_DELAY_SECS: f32 = 0.5;
+/// How high above a sheep its colorblind symbol floats.
+const COLOR_SYMBOL_HEIGHT: f32 = 1.4;
+const HERD_LEADER_SCALE: f32 = 1.15;
+/// A sheep needs this many flockmates nearby to calm down.
+const CALM_MIN_NEIGHBORS: usize = 3;
+/// How quickly a sheep calms down with the flock, so it takes 4 seconds to go from 0 to fully calm.
+const CALM_GAIN_PER_SEC: f32 = 0.25;
+/// How far outside its goal a sheep's calm meter starts showing.
+const CALM_METER_SHOW_DISTANCE: f32 = 8.0;
+const CALM_METER_HALF_WIDTH: f32 = 0.6;
+const CALM_METER_HEIGHT: f32 = 1.8;
+/// Points scored by counting a blue sheep.
+pub const BLUE_SHEEP_POINTS: u32 = 5;
+/// Time put back on the clock by counting a green sheep.
+pub const GREEN_SHEEP_BONUS: Duration = Duration::from_secs(5);
+/// Contrarian sheep steer away from the goal when closer than this.
+const CONTRARIAN_AVOID_RADIUS: f32 = 14.0;
+const CONTRARIAN_AVOID_WEIGHT: f32 = 1.2;
+/// Chances, each time a wandering sheep would move on, that it idles instead. Grazing just waits
+/// a while longer; looking around and shaking also play a small animation.
+const GRAZE_CHANCE: f64 = 0.15;
+const LOOK_AROUND_CHANCE: f64 = 0.08;
+const SHAKE_CHANCE: f64 = 0.05;
+/// At most this fraction of the flock plays an idle animation at once.
+const MAX_IDLE_ANIMATING_FRACTION: f32 = 0.1;
+const LOOK_AROUND_SECS: f32 = 1.6;
+/// How far a sheep turns its body either way while looking around, in radians.
+const LOOK_AROUND_ANGLE: f32 = 0.7;
+const SHAKE_SECS: f32 = 0.6;
+const SHAKE_ANGLE: f32 = 0.2;
+/// The little "Z" floating over a sleeping sheep: its size, how high it starts above the sheep,
+/// and how far and fast it drifts upwards before starting again.
+const SLEEP_Z_SIZE: f32 = 0.3;
+const SLEEP_Z_HEIGHT: f32 = 1.2;
+const SLEEP_Z_RISE: f32 = 0.6;
+const SLEEP_Z_SECS: f32 = 2.0;
+/// Names given to sheep at random, shown above them when enabled in the settings.
+const SHEEP_NAMES: [&str; 32] = [
+    "Woolbert",
+    "Baabara",
+    "Fluffington",
+    "Lambert",
+    "Shaun",
+    "Dolly",
+    "Cotton",
+    "Nimbus",
+    "Mutton",
+    "Bleatrice",
+    "Woolfgang",
+    "Fleecy",
+    "Ramsay",
+    "Curly",
+    "Marshmallow",
+    "Puff",
+    "Sir Baa",
+    "Lamb Chop",
+    "Tumbleweed",
+    "Wooliam",
+    "Shearlock",
+    "Pom Pom",
+    "Dumpling",
+    "Ewenice",
+    "Baaxter",
+    "Cloud",
+    "Noodle",
+    "Biscuit",
+    "Haggis",
+    "Loafy",
+    "Doughnut",
+    "Mopsy",
+];
+/// How long sheep given a herd command keep heading for the goal.
+const GOAL_NUDGE_SECS: f32 = 2.5;
+/// How strongly a herd command pulls evading sheep towards the goal.
+const GOAL_NUDGE_EVADE_WEIGHT: f32 = 0.8;
+/// How long a counted sheep takes to shrink away.
+const COUNTED_SHRINK_SECS: f32 = 0.3;
+const COUNTED_PUFF_COUNT: usize = 6;
+const COUNTED_PUFF_SECS: f32 = 0.45;
+const COUNTED_PUFF_SPEED: f32 = 3.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<SheepAssets>();
+    app.init_resource::<HerdMetrics>();
     app.add_observer(apply_wool_material_on_scene_ready);
     app.add_systems(
         Update,
         (
             sheep_goal_check,
             sheep_state_update,
-            (sheep_wander, sheep_herding, sheep_abduction_update),
+            (
+                sheep_wander,
+                sheep_herding,
+                sheep_abduction_update,
+                sheep_fall_update,
+            ),
         )
             .chain()
             .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
+    app.add_systems(
+        Update,
+        (
+            shrink_counted_sheep,
+            update_counted_puffs,
+            animate_idle_sheep.after(sheep_wander),
+        )
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+    app.add_systems(
+        Update,
+        apply_dynamic_modifiers
+            .before(sheep_state_update)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(
+                in_state(GamePhase::Herding)
+                    .and(|settings: Res<GameplaySettings>| settings.dynamic_modifiers),
+            ),
+    );
+    // Not pausable, so flipping the setting from the pause menu shows up straight away.
+    app.add_systems(
+        Update,
+        show_color_symbols.run_if(resource_changed::<AccessibilityConfig>),
+    );
+    app.add_systems(
+        PostUpdate,
+        keep_color_symbols_upright
+            .before(TransformSystems::Propagate)
+            .run_if(|accessibility: Res<AccessibilityConfig>| accessibility.colorblind_mode),
+    );
+    app.add_systems(
+        Update,
+        draw_sleep_cues
+            .in_set(AppSystems::Update)
+            .run_if(in_state(GamePhase::Herding)),
+    );
+    app.add_systems(
+        Update,
+        draw_calm_meters
+            .in_set(AppSystems::Update)
+            .run_if(in_state(GamePhase::Herding).and(gentle_mode)),
+    );
+    app.add_systems(
+        Update,
+        update_herd_leader
+            .before(sheep_herding)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::Herding)),
+    );
 }
 
 #[derive(Debug, Clone, PartialEq, Reflect)]
@@ -72,6 +207,24 @@ pub enum SheepColor {
     Blue,
     Red,
     Gold,
+    /// Puts time back on the clock when counted.
+    Green,
+    /// Actively avoids the goal, so it has to be cornered.
+    Contrarian,
+}
+
+impl SheepColor {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SheepColor::White => "White",
+            SheepColor::Black => "Black",
+            SheepColor::Blue => "Blue",
+            SheepColor::Red => "Red",
+            SheepColor::Gold => "Gold",
+            SheepColor::Green => "Green",
+            SheepColor::Contrarian => "Contrarian",
+        }
+    }
 }
 
 #[derive(Component, Debug, Clone, PartialEq, Reflect)]
@@ -84,7 +237,17 @@ pub struct Sheep {
     max_wait: f32,
     default_speed_mult: f32,
     spooked_speed_mult: f32,
+    /// Hop cadence while calm. Sped up while spooked so the hops keep pace with the movement.
+    hop_speed_mult: f32,
     herd_dir: Vec2,
+    /// How many flockmates were within [`HerdConfig::radius`] at the last herding update.
+    neighbor_count: usize,
+    name: &'static str,
+    /// Counts down while the sheep is following a herd command towards the goal.
+    goal_nudge: Timer,
+    /// How settled the sheep is, from 0 to 1. Builds up while it wanders calmly with the flock and
+    /// drops to 0 when spooked. In [`GameMode::Gentle`] only fully calm sheep can be counted.
+    calm: f32,
 }
 
 impl Sheep {
@@ -97,7 +260,12 @@ impl Sheep {
             max_wait: 7.0,
             default_speed_mult: 1.2,
             spooked_speed_mult: 1.9,
+            hop_speed_mult: 1.0,
             herd_dir: Vec2::ZERO,
+            neighbor_count: 0,
+            name: SHEEP_NAMES[rand::rng().random_range(0..SHEEP_NAMES.len())],
+            goal_nudge: Timer::default(),
+            calm: 0.0,
         };
         sheep.reset_timer();
         sheep
@@ -113,6 +281,11 @@ impl Sheep {
         self
     }
 
+    fn hop_speed_mult(mut self, mult: f32) -> Self {
+        self.hop_speed_mult = mult;
+        self
+    }
+
     fn step_distance(mut self, dist: f32) -> Self {
         self.step_distance = dist;
         self
@@ -122,6 +295,12 @@ impl Sheep {
         self.state = SheepState::Sleeping;
     }
 
+    /// Goes back to wandering after a short pause.
+    fn return_to_wander(&mut self) {
+        self.state = SheepState::Wander(Timer::from_seconds(0.5, TimerMode::Once));
+        self.reset_timer();
+    }
+
     fn reset_timer(&mut self) {
         if let SheepState::Wander(timer) = &mut self.state {
             let rng = &mut rand::rng();
@@ -131,15 +310,62 @@ impl Sheep {
         }
     }
 
+    /// Sends the sheep running from a bark at `danger_pos`, waking it if it's asleep. Black sheep
+    /// are unflappable: barks don't scatter them or break their calm, so they stay with the flock
+    /// and only step away when the player gets close. A bark still wakes them up.
     pub fn become_spooked(&mut self, danger_pos: Vec2) {
+        if self.color == SheepColor::Black {
+            if self.state == SheepState::Sleeping {
+                self.return_to_wander();
+            }
+            return;
+        }
         match self.state {
-            SheepState::Wander(_) | SheepState::Evading(_) => {
+            SheepState::Wander(_) | SheepState::Evading(_) | SheepState::Sleeping => {
+                // A bark always takes priority over a herd command.
+                self.goal_nudge = Timer::default();
+                self.calm = 0.0;
                 self.state = SheepState::Spooked(danger_pos);
             }
             _ => {}
         }
     }
 
+    /// Sends a wandering or evading sheep towards its goal for a little while.
+    pub fn nudge_toward_goal(&mut self) {
+        if matches!(self.state, SheepState::Wander(_) | SheepState::Evading(_)) {
+            self.goal_nudge = Timer::from_seconds(GOAL_NUDGE_SECS, TimerMode::Once);
+        }
+    }
+
+    fn is_nudged(&self) -> bool {
+        self.goal_nudge.remaining_secs() > 0.0
+    }
+
+    pub fn state(&self) -> &SheepState {
+        &self.state
+    }
+
+    pub fn color(&self) -> &SheepColor {
+        &self.color
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn neighbor_count(&self) -> usize {
+        self.neighbor_count
+    }
+
+    /// Whether the player can still herd this sheep, i.e. it isn't already being counted or abducted.
+    pub fn is_herdable(&self) -> bool {
+        !matches!(
+            self.state,
+            SheepState::BeingCounted | SheepState::BeingAbducted
+        )
+    }
+
     pub fn is_being_abducted(&self) -> bool {
         matches!(self.state, SheepState::BeingAbducted)
     }
@@ -151,6 +377,76 @@ impl Sheep {
         self.state = SheepState::BeingAbducted;
         true
     }
+
+    /// Lets go of a sheep a UFO was carrying off, so it wanders again once it's back on the
+    /// ground. Returns whether it was being abducted.
+    fn cancel_abduction(&mut self) -> bool {
+        if !self.is_being_abducted() {
+            return false;
+        }
+        self.return_to_wander();
+        true
+    }
+}
+
+/// A sheep a UFO let go of, floating back down. It gets its movement back once it lands.
+#[derive(Component, Debug)]
+struct FallingSheep;
+
+/// Makes a UFO drop the sheep it's abducting, floating it back down to the ground. Returns whether
+/// the sheep was still being abducted.
+pub fn drop_abducted_sheep(commands: &mut Commands, entity: Entity, sheep: &mut Sheep) -> bool {
+    if !sheep.cancel_abduction() {
+        return false;
+    }
+    commands.entity(entity).insert(FallingSheep);
+    true
+}
+
+/// The sheep the rest of the flock gravitates towards most strongly. There is at most one per round,
+/// and a new one is promoted when the current leader is counted or abducted.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct HerdLeader;
+
+/// The flock as a whole, for tuning flocking by eye. Updated every herd tick from the sheep that
+/// are flocking, i.e. wandering or evading.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct HerdMetrics {
+    pub centroid: Vec2,
+    /// The average of the flocking sheep's herding directions, normalized. Zero when they cancel
+    /// out.
+    pub heading: Vec2,
+    pub count: usize,
+    /// The average distance of a flocking sheep from the centroid.
+    pub spread: f32,
+}
+
+/// The average position of `sheep`, or zero if there are none.
+pub fn herd_centroid(sheep: &[(Entity, Vec2)]) -> Vec2 {
+    if sheep.is_empty() {
+        return Vec2::ZERO;
+    }
+    sheep.iter().map(|(_, pos)| *pos).sum::<Vec2>() / sheep.len() as f32
+}
+
+/// A plain colored box shown in place of a sheep whose model hasn't loaded yet, so sheep are never
+/// invisible. Removed once the sheep's scene is ready.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+struct SheepPlaceholder;
+
+/// A sheep that has just been counted, shrinking away before it's despawned. It no longer has a
+/// [`Sheep`] component, so nothing else can count, herd or abduct it.
+#[derive(Component, Debug)]
+struct CountedSheep(Timer);
+
+/// A puff of wool thrown out when a sheep is counted.
+#[derive(Component, Debug)]
+struct CountedPuff {
+    velocity: Vec3,
+    timer: Timer,
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -163,13 +459,62 @@ pub struct SheepAssets {
     pub wool_blue: Handle<StandardMaterial>,
     pub wool_red: Handle<StandardMaterial>,
     pub wool_gold: Handle<StandardMaterial>,
+    pub wool_green: Handle<StandardMaterial>,
+    pub wool_contrarian: Handle<StandardMaterial>,
+    /// Stand-in shape shown until the sheep model has loaded.
+    pub placeholder_mesh: Handle<Mesh>,
+    pub puff_mesh: Handle<Mesh>,
+    /// Shapes floating above sheep in colorblind mode, see [`SheepAssets::color_symbol`].
+    pub symbol_dot: Handle<Mesh>,
+    pub symbol_triangle: Handle<Mesh>,
+    pub symbol_square: Handle<Mesh>,
+    pub symbol_ring: Handle<Mesh>,
+    pub symbol_material: Handle<StandardMaterial>,
+}
+
+impl SheepAssets {
+    pub fn wool_material(&self, color: &SheepColor) -> Handle<StandardMaterial> {
+        match color {
+            SheepColor::White => self.wool_white.clone(),
+            SheepColor::Black => self.wool_black.clone(),
+            SheepColor::Blue => self.wool_blue.clone(),
+            SheepColor::Red => self.wool_red.clone(),
+            SheepColor::Gold => self.wool_gold.clone(),
+            SheepColor::Green => self.wool_green.clone(),
+            SheepColor::Contrarian => self.wool_contrarian.clone(),
+        }
+    }
+
+    /// The shape shown above sheep of `color` in colorblind mode, for the colors that are easy to
+    /// mix up: a dot for blue, a triangle for red, a square for black and a ring for green.
+    pub fn color_symbol(&self, color: &SheepColor) -> Option<Handle<Mesh>> {
+        match color {
+            SheepColor::Blue => Some(self.symbol_dot.clone()),
+            SheepColor::Red => Some(self.symbol_triangle.clone()),
+            SheepColor::Black => Some(self.symbol_square.clone()),
+            SheepColor::Green => Some(self.symbol_ring.clone()),
+            SheepColor::White | SheepColor::Gold | SheepColor::Contrarian => None,
+        }
+    }
 }
 
 impl FromWorld for SheepAssets {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
         let scene = assets.load("obj/sheep.glb#Scene0");
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let placeholder_mesh = meshes.add(Cuboid::new(0.8, 0.7, 1.1));
+        let puff_mesh = meshes.add(Sphere::new(0.25));
+        let symbol_dot = meshes.add(Sphere::new(0.18));
+        let symbol_triangle = meshes.add(Cone::new(0.24, 0.4));
+        let symbol_square = meshes.add(Cuboid::from_length(0.34));
+        let symbol_ring = meshes.add(Torus::new(0.1, 0.22));
         let mut mats = world.resource_mut::<Assets<StandardMaterial>>();
+        let symbol_material = mats.add(StandardMaterial {
+            base_color: Color::WHITE,
+            unlit: true,
+            ..Default::default()
+        });
         Self {
             scene,
             wool_white: mats.add(StandardMaterial {
@@ -198,6 +543,23 @@ impl FromWorld for SheepAssets {
                 metallic: 0.6,
                 ..Default::default()
             }),
+            wool_green: mats.add(StandardMaterial {
+                base_color: Color::srgb(0.35, 0.8, 0.35),
+                perceptual_roughness: 0.9,
+                ..Default::default()
+            }),
+            wool_contrarian: mats.add(StandardMaterial {
+                base_color: Color::srgb(0.6, 0.35, 0.85),
+                perceptual_roughness: 0.9,
+                ..Default::default()
+            }),
+            placeholder_mesh,
+            puff_mesh,
+            symbol_dot,
+            symbol_triangle,
+            symbol_square,
+            symbol_ring,
+            symbol_material,
         }
     }
 }
@@ -211,46 +573,14 @@ pub fn sheep(
     sleeping: bool,
 ) -> Entity {
     let rng = &mut rand::rng();
-    let p = if state.is_charm_active(Charm::Ink) {
-        0.1
-    } else {
-        0.05
-    };
-    let color = if matches!(color, SheepColor::White) && rng.random_bool(p) {
-        SheepColor::Black
-    } else {
-        color
-    };
-
-    let mut move_speed_mult = 2.0;
-    let mut hop_speed_mult = 2.5;
-    let mut time_between_hops = 0.2;
-    let mut hop_time_length = 0.3;
-    let mut jump_height_mult = 1.0;
-
-    if state.is_modifier_active(Modifier::MoonGravity) {
-        hop_speed_mult *= 0.5;
-        // move_speed_mult *= 0.8;
-        hop_time_length += 0.5;
-        jump_height_mult *= 6.0;
-        if state.is_modifier_active(Modifier::FeverDream) {
-            jump_height_mult *= 1.5;
-            hop_time_length += 0.5;
-        }
-    }
-    if state.is_modifier_active(Modifier::HyperSheep) {
-        hop_speed_mult *= 2.0;
-        move_speed_mult *= 1.3;
-        time_between_hops *= 0.1;
-        if state.is_modifier_active(Modifier::FeverDream) {
-            hop_speed_mult *= 1.5;
-        }
-    }
+    let movement = SheepMovement::for_state(state, |_| 1.0);
 
+    let placeholder_material = sheep_assets.wool_material(&color);
     let mut sheep_c = Sheep::new(color)
-        .default_speed_mult(move_speed_mult)
-        .spooked_speed_mult(move_speed_mult * 2.0)
-        .step_distance(move_speed_mult);
+        .default_speed_mult(movement.move_speed_mult)
+        .spooked_speed_mult(movement.move_speed_mult * 2.0)
+        .hop_speed_mult(movement.hop_speed_mult)
+        .step_distance(movement.move_speed_mult);
     if sleeping {
         sheep_c.sleeping();
         position.y -= 0.5;
@@ -270,52 +600,184 @@ pub fn sheep(
     }
 
     let yaw = rng.random_range(0.0..std::f32::consts::TAU);
-    let entity = commands
-        .spawn((
-            Name::new("Sheep"),
-            MovementController::new(move_speed_mult),
-            sheep_c,
-            SceneRoot(sheep_assets.scene.clone()),
-            Transform::from_translation(position).with_rotation(Quat::from_rotation_y(yaw)),
-            DespawnOnExit(Screen::Gameplay),
-        ))
-        .id();
+    // Start each sheep somewhere in its hop cycle, so a flock spawned together doesn't hop in
+    // unison. Only the wait before the first hop changes, not how the sheep hops or moves.
+    let first_hop_delay =
+        rng.random_range(0.0..movement.time_between_hops + movement.hop_time_length);
+    let mut entity = commands.spawn((
+        Name::new("Sheep"),
+        sheep_c,
+        SceneRoot(sheep_assets.scene.clone()),
+        Transform::from_translation(position).with_rotation(Quat::from_rotation_y(yaw)),
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            Name::new("Sheep Placeholder"),
+            SheepPlaceholder,
+            Mesh3d(sheep_assets.placeholder_mesh.clone()),
+            MeshMaterial3d(placeholder_material),
+            Transform::from_xyz(0.0, 0.45, 0.0),
+        )],
+    ));
+    movement.insert_controllers(&mut entity, state, first_hop_delay);
+    entity.id()
+}
 
-    if state.is_modifier_active(Modifier::SheepSphere) {
-        commands
-            .entity(entity)
-            .insert(SphereMovementController::new(move_speed_mult, 1.0, 1.0));
+/// A sheep's movement parameters once the active modifiers are applied.
+struct SheepMovement {
+    move_speed_mult: f32,
+    hop_speed_mult: f32,
+    time_between_hops: f32,
+    hop_time_length: f32,
+    jump_height_mult: f32,
+}
+
+impl SheepMovement {
+    /// `intensity` gives how strongly each modifier applies, from 0 to 1.
+    fn for_state(state: &GameState, intensity: impl Fn(Modifier) -> f32) -> Self {
+        let mut movement = Self {
+            move_speed_mult: 2.0,
+            hop_speed_mult: 2.5,
+            time_between_hops: 0.2,
+            hop_time_length: 0.3,
+            jump_height_mult: 1.0,
+        };
+        let fever_dream = state.is_modifier_active(Modifier::FeverDream);
+        for active in &state.active_modifiers {
+            if let Some(effect) = active.modifier.sheep_movement_effect(fever_dream) {
+                let effect = effect.scaled(intensity(active.modifier));
+                movement.move_speed_mult *= effect.move_speed_mult;
+                movement.hop_speed_mult *= effect.hop_speed_mult;
+                movement.time_between_hops *= effect.hop_interval_mult;
+                movement.hop_time_length += effect.extra_hop_secs;
+                movement.jump_height_mult *= effect.jump_height_mult;
+            }
+        }
+        movement
+    }
+
+    /// Like [`Self::for_state`], with each modifier at its current intensity when dynamic
+    /// modifiers are on.
+    fn current(state: &GameState, dynamic_modifiers: bool) -> Self {
+        if dynamic_modifiers {
+            let progress = round_progress(state);
+            Self::for_state(state, |modifier| modifier.intensity(progress))
+        } else {
+            Self::for_state(state, |_| 1.0)
+        }
+    }
+
+    /// Gives a sheep its movement controllers: rolling with [`Modifier::SheepSphere`], hopping
+    /// otherwise, starting with a wait of `first_hop_delay` seconds.
+    fn insert_controllers(
+        &self,
+        sheep: &mut EntityCommands,
+        state: &GameState,
+        first_hop_delay: f32,
+    ) {
+        sheep.insert(MovementController::new(self.move_speed_mult));
+        if state.is_modifier_active(Modifier::SheepSphere) {
+            sheep.insert(SphereMovementController::new(
+                self.move_speed_mult,
+                1.0,
+                1.0,
+            ));
+        } else {
+            sheep.insert(HopMovementController {
+                hop_speed_mult: self.hop_speed_mult,
+                time_between_hops: self.time_between_hops,
+                hop_time_length: self.hop_time_length,
+                jump_height_mult: self.jump_height_mult,
+                timer: Timer::from_seconds(first_hop_delay, TimerMode::Once),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// How far through the round it is, from 0 to 1.
+fn round_progress(state: &GameState) -> f32 {
+    let countdown = &state.countdown;
+    if countdown.duration().is_zero() {
+        1.0
     } else {
-        commands.entity(entity).insert(HopMovementController {
-            hop_speed_mult,
-            time_between_hops,
-            hop_time_length,
-            jump_height_mult,
-            ..Default::default()
-        });
+        countdown.fraction()
     }
+}
 
-    entity
+/// With dynamic modifiers on, reapplies the movement modifiers every frame at their intensity for
+/// how far through the round it is.
+fn apply_dynamic_modifiers(
+    state: Res<GameState>,
+    mut sheep_query: Query<(&mut Sheep, Option<&mut HopMovementController>)>,
+) {
+    let movement = SheepMovement::current(&state, true);
+    for (mut sheep, hop) in &mut sheep_query {
+        sheep.default_speed_mult = movement.move_speed_mult;
+        sheep.spooked_speed_mult = movement.move_speed_mult * 2.0;
+        sheep.hop_speed_mult = movement.hop_speed_mult;
+        sheep.step_distance = movement.move_speed_mult;
+        if let Some(mut hop) = hop {
+            hop.time_between_hops = movement.time_between_hops;
+            hop.hop_time_length = movement.hop_time_length;
+            hop.jump_height_mult = movement.jump_height_mult;
+        }
+    }
 }
 
 fn sheep_wander(
     time: Res<Time>,
+    mut commands: Commands,
     bounds: Res<LevelBounds>,
-    mut sheep_query: Query<(&mut MovementController, &Transform, &mut Sheep)>,
+    config: Res<GameConfig>,
+    mut sheep_query: Query<(Entity, &mut MovementController, &Transform, &mut Sheep)>,
+    idle_query: Query<(), With<IdleAnimation>>,
 ) {
-    for (mut movement, transform, mut sheep) in &mut sheep_query {
+    let max_animating =
+        (sheep_query.iter().count() as f32 * MAX_IDLE_ANIMATING_FRACTION).ceil() as usize;
+    let mut animating = idle_query.iter().count();
+    for (entity, mut movement, transform, mut sheep) in &mut sheep_query {
+        // Heading for the goal instead, see `sheep_state_update`.
+        if sheep.is_nudged() {
+            continue;
+        }
         if let SheepState::Wander(timer) = &mut sheep.state {
             timer.tick(time.delta());
             if timer.just_finished() {
                 let rng = &mut rand::rng();
+                let roll: f64 = rng.random();
+                if roll < GRAZE_CHANCE {
+                    sheep.reset_timer();
+                    continue;
+                }
+                let idle = if roll < GRAZE_CHANCE + LOOK_AROUND_CHANCE {
+                    Some((IdleKind::LookAround, LOOK_AROUND_SECS))
+                } else if roll < GRAZE_CHANCE + LOOK_AROUND_CHANCE + SHAKE_CHANCE {
+                    Some((IdleKind::Shake, SHAKE_SECS))
+                } else {
+                    None
+                };
+                if let Some((kind, secs)) = idle
+                    && animating < max_animating
+                {
+                    commands.entity(entity).insert(IdleAnimation {
+                        kind,
+                        base_rotation: transform.rotation,
+                        timer: Timer::from_seconds(secs, TimerMode::Once),
+                    });
+                    animating += 1;
+                    sheep.reset_timer();
+                    continue;
+                }
                 let angle = rng.random_range(0.0..std::f32::consts::TAU);
                 let random_dir = Vec2::from_angle(angle);
                 let herd_dir = sheep.herd_dir;
                 let dir = if herd_dir == Vec2::ZERO {
                     random_dir
                 } else {
-                    (herd_dir + random_dir * HERD_WANDER_JITTER).normalize_or(random_dir)
+                    (herd_dir + random_dir * config.herd.wander_jitter).normalize_or(random_dir)
                 };
+                let pos = transform.translation.xz();
+                let dir = (dir + goal_avoidance(&sheep, pos)).normalize_or(dir);
                 let target =
                     bounds.clamp_to_bounds(transform.translation.xz() + dir * sheep.step_distance);
                 movement.intent = target;
@@ -325,71 +787,146 @@ fn sheep_wander(
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum IdleKind {
+    LookAround,
+    Shake,
+}
+
+/// A short animation played by a sheep standing still while wandering.
+#[derive(Component, Debug)]
+struct IdleAnimation {
+    kind: IdleKind,
+    base_rotation: Quat,
+    timer: Timer,
+}
+
+fn animate_idle_sheep(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut sheep_query: Query<(Entity, &Sheep, &mut IdleAnimation, &mut Transform)>,
+) {
+    for (entity, sheep, mut idle, mut transform) in &mut sheep_query {
+        // Whatever interrupted the idle also takes over the sheep's rotation, e.g. by hopping.
+        if !matches!(sheep.state, SheepState::Wander(_)) {
+            commands.entity(entity).remove::<IdleAnimation>();
+            continue;
+        }
+        if idle.timer.tick(time.delta()).is_finished() {
+            transform.rotation = idle.base_rotation;
+            commands.entity(entity).remove::<IdleAnimation>();
+            continue;
+        }
+        let t = idle.timer.fraction();
+        let offset = match idle.kind {
+            IdleKind::LookAround => {
+                Quat::from_rotation_y((t * std::f32::consts::TAU).sin() * LOOK_AROUND_ANGLE)
+            }
+            IdleKind::Shake => Quat::from_rotation_z(
+                (t * 4.0 * std::f32::consts::TAU).sin() * SHAKE_ANGLE * (1.0 - t),
+            ),
+        };
+        transform.rotation = idle.base_rotation * offset;
+    }
+}
+
 fn sheep_state_update(
     time: Res<Time>,
-    mut sheep_query: Query<(&mut MovementController, &Transform, &mut Sheep)>,
+    mut sheep_query: Query<(
+        &mut MovementController,
+        &Transform,
+        &mut Sheep,
+        Option<&mut HopMovementController>,
+    )>,
     player_query: Query<(&Transform, &Player)>,
-    goal_query: Query<&Transform, (With<GoalLocation>, Without<Player>)>,
+    goal_query: Query<(&Transform, &GoalLocation), Without<Player>>,
     bounds: Res<LevelBounds>,
     game_state: Res<GameState>,
+    config: Res<GameConfig>,
 ) {
-    for (mut movement, transform, mut sheep) in &mut sheep_query {
+    // Each player's position and how close they can get before sheep react to them.
+    let players: Vec<(Vec2, f32)> = player_query
+        .iter()
+        .map(|(transform, player)| (transform.translation.xz(), player.sheep_interact_radius))
+        .collect();
+
+    for (mut movement, transform, mut sheep, hop) in &mut sheep_query {
         let pos = transform.translation.xz();
+        sheep.goal_nudge.tick(time.delta());
+        let goal_nudge = if sheep.is_nudged() {
+            goal_for(&sheep.color, goal_query.iter()).map_or(Vec2::ZERO, |(goal_pos, _)| {
+                (goal_pos.xz() - pos).normalize_or_zero()
+            })
+        } else {
+            Vec2::ZERO
+        };
+        let nearest_player = players.iter().copied().min_by(|(a, _), (b, _)| {
+            pos.distance_squared(*a)
+                .total_cmp(&pos.distance_squared(*b))
+        });
         match sheep.state {
             SheepState::Wander(_) => {
                 movement.move_speed_mult = sheep.default_speed_mult;
-                for (player_transform, player) in player_query {
-                    let player_pos = player_transform.translation.xz();
-                    if pos.distance(player_pos) < player.sheep_interact_radius {
-                        sheep.state = SheepState::Evading(player_pos);
-                    }
+                if let Some((player_pos, interact_radius)) = nearest_player
+                    && pos.distance(player_pos) < interact_radius
+                {
+                    sheep.state = SheepState::Evading(player_pos);
+                }
+                if goal_nudge != Vec2::ZERO {
+                    movement.apply_movement(goal_nudge * time.delta_secs() * sheep.step_distance);
                 }
             }
             SheepState::Evading(mut danger_pos) => {
-                for (player_transform, player) in player_query {
-                    let player_pos = player_transform.translation.xz();
-                    if pos.distance(player_pos) < player.sheep_interact_radius {
-                        danger_pos = player_pos;
-                    }
-                    if pos.distance(danger_pos) >= player.sheep_interact_radius {
-                        sheep.state = SheepState::Wander(Timer::from_seconds(0.5, TimerMode::Once));
-                        sheep.reset_timer();
-                    } else {
-                        let preferred = (pos - danger_pos).normalize_or(Vec2::X);
-                        let dir = pick_evasion_dir(pos, preferred, &bounds);
-                        let steer = (dir + sheep.herd_dir * HERD_EVADE_BLEND).normalize_or(dir);
-                        movement.move_speed_mult = sheep.default_speed_mult;
-                        movement.apply_movement(steer * time.delta_secs() * sheep.step_distance);
-                    }
+                // With no player around (e.g. mid phase change) there's nothing left to evade.
+                let Some((player_pos, interact_radius)) = nearest_player else {
+                    sheep.return_to_wander();
+                    continue;
+                };
+                if pos.distance(player_pos) < interact_radius {
+                    danger_pos = player_pos;
+                }
+                if pos.distance(danger_pos) >= interact_radius {
+                    sheep.return_to_wander();
+                } else {
+                    let preferred = (pos - danger_pos).normalize_or(Vec2::X);
+                    let dir = pick_evasion_dir(pos, preferred, &bounds);
+                    let steer = (dir
+                        + sheep.herd_dir * config.herd.evade_blend
+                        + goal_avoidance(&sheep, pos)
+                        + goal_nudge * GOAL_NUDGE_EVADE_WEIGHT)
+                        .normalize_or(dir);
+                    movement.move_speed_mult = sheep.default_speed_mult;
+                    movement.apply_movement(steer * time.delta_secs() * sheep.step_distance);
                 }
             }
             SheepState::Spooked(danger_pos) => {
                 sheep.herd_dir = Vec2::ZERO;
-                for (_, player) in player_query {
-                    if game_state.is_charm_active(Charm::WellTrained) {
-                        if pos.distance(danger_pos) < player.sheep_interact_radius {
-                            sheep.state =
-                                SheepState::Wander(Timer::from_seconds(0.5, TimerMode::Once));
-                            sheep.reset_timer();
-                        } else {
-                            let dir = (danger_pos - pos).normalize_or(Vec2::X);
-                            movement.move_speed_mult = sheep.default_speed_mult;
-                            movement.apply_movement(dir * time.delta_secs() * sheep.step_distance);
-                        }
-                    } else if pos.distance(danger_pos) >= player.sheep_interact_radius + 8.0 {
-                        sheep.state = SheepState::Wander(Timer::from_seconds(0.5, TimerMode::Once));
-                        sheep.reset_timer();
+                let Some((_, interact_radius)) = nearest_player else {
+                    sheep.return_to_wander();
+                    continue;
+                };
+                if game_state.is_charm_active(Charm::WellTrained) {
+                    if pos.distance(danger_pos) < interact_radius {
+                        sheep.return_to_wander();
                     } else {
-                        let dir = (pos - danger_pos).normalize_or(Vec2::X);
-                        movement.move_speed_mult = sheep.spooked_speed_mult;
+                        let dir = (danger_pos - pos).normalize_or(Vec2::X);
+                        movement.move_speed_mult = sheep.default_speed_mult;
                         movement.apply_movement(dir * time.delta_secs() * sheep.step_distance);
                     }
+                } else if pos.distance(danger_pos) >= interact_radius + 8.0 {
+                    sheep.return_to_wander();
+                } else {
+                    let dir = (pos - danger_pos).normalize_or(Vec2::X);
+                    movement.move_speed_mult = sheep.spooked_speed_mult;
+                    movement.apply_movement(dir * time.delta_secs() * sheep.step_distance);
                 }
             }
             SheepState::BeingCounted => {
                 sheep.herd_dir = Vec2::ZERO;
-                let goal_pos = goal_query.single().unwrap().translation.xz();
-                let dir = (goal_pos - pos).normalize_or(Vec2::X);
+                let Some((goal_pos, _)) = goal_for(&sheep.color, goal_query.iter()) else {
+                    continue;
+                };
+                let dir = (goal_pos.xz() - pos).normalize_or(Vec2::X);
                 // movement.move_speed_mult = 0.8;
                 movement.apply_movement(dir * time.delta_secs() * sheep.step_distance);
             }
@@ -398,14 +935,24 @@ fn sheep_state_update(
                 movement.intent = transform.translation.xz();
             }
             SheepState::Sleeping => {
-                for (player_transform, player) in player_query {
-                    let player_pos = player_transform.translation.xz();
-                    if pos.distance(player_pos) < player.sheep_interact_radius {
-                        sheep.state = SheepState::Evading(player_pos);
-                    }
+                if let Some((player_pos, interact_radius)) = nearest_player
+                    && pos.distance(player_pos) < interact_radius
+                {
+                    sheep.state = SheepState::Evading(player_pos);
                 }
             }
         }
+
+        // Match the hop cadence to how much faster than usual the sheep is moving, and go back to
+        // the calm cadence in any other state.
+        if let Some(mut hop) = hop {
+            let cadence = if matches!(sheep.state, SheepState::Spooked(_)) {
+                movement.move_speed_mult / sheep.default_speed_mult
+            } else {
+                1.0
+            };
+            hop.hop_speed_mult = sheep.hop_speed_mult * cadence;
+        }
     }
 }
 
@@ -413,6 +960,9 @@ fn sheep_abduction_update(
     time: Res<Time>,
     mut commands: Commands,
     mut sheep_query: Query<(Entity, &mut Transform, &Sheep)>,
+    mut round_stats: ResMut<RoundStats>,
+    mut game_state: ResMut<GameState>,
+    mut writer: MessageWriter<GoalTextMessage>,
 ) {
     for (entity, mut transform, sheep) in &mut sheep_query {
         if !sheep.is_being_abducted() {
@@ -423,57 +973,316 @@ fn sheep_abduction_update(
             (transform.translation.y + ABDUCTION_ASCENT_SPEED * time.delta_secs()).min(UFO_HEIGHT);
 
         if transform.translation.y >= UFO_HEIGHT - 2.0 {
+            round_stats.sheep_abducted += 1;
+            if game_state.insurance_level > 0 {
+                let refund = game_state.insurance_level as u32;
+                game_state.money = game_state.money.saturating_add(refund);
+                writer.write(GoalTextMessage {
+                    text: format!("+{refund} money (insured)"),
+                    color: Some(Color::srgb(0.9, 0.8, 0.3)),
+                    position: Some(transform.translation.with_y(0.0)),
+                });
+            }
             commands.entity(entity).despawn();
         }
     }
 }
 
+fn sheep_fall_update(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut sheep_query: Query<(Entity, &mut Transform, &Sheep), With<FallingSheep>>,
+    state: Res<GameState>,
+    settings: Res<GameplaySettings>,
+) {
+    for (entity, mut transform, sheep) in &mut sheep_query {
+        // Snatched up again on the way down.
+        if sheep.is_being_abducted() {
+            commands.entity(entity).remove::<FallingSheep>();
+            continue;
+        }
+
+        transform.translation.y =
+            (transform.translation.y - ABDUCTION_ASCENT_SPEED * time.delta_secs()).max(0.0);
+        if transform.translation.y > 0.0 {
+            continue;
+        }
+        let mut sheep = commands.entity(entity);
+        sheep.remove::<FallingSheep>();
+        SheepMovement::current(&state, settings.dynamic_modifiers).insert_controllers(
+            &mut sheep,
+            &state,
+            LANDED_HOP_DELAY_SECS,
+        );
+    }
+}
+
+/// A "Z" drifting up from each sleeping sheep, so it's clear they're asleep rather than stuck.
+/// Holds still with reduced motion.
+fn draw_sleep_cues(
+    time: Res<Time>,
+    accessibility: Res<AccessibilityConfig>,
+    sheep_query: Query<(Entity, &GlobalTransform, &Sheep)>,
+    mut gizmos: Gizmos,
+) {
+    for (entity, transform, sheep) in &sheep_query {
+        if sheep.state != SheepState::Sleeping {
+            continue;
+        }
+        // Offset each sheep's cycle so the flock doesn't breathe in unison.
+        let phase = (entity.index_u32() % 16) as f32 / 16.0;
+        let t = if accessibility.reduced_motion {
+            0.5
+        } else {
+            (time.elapsed_secs() / SLEEP_Z_SECS + phase).fract()
+        };
+        let center = transform.translation() + Vec3::Y * (SLEEP_Z_HEIGHT + SLEEP_Z_RISE * t);
+        let half = SLEEP_Z_SIZE / 2.0;
+        let top_left = center + Vec3::new(-half, half, 0.0);
+        let top_right = center + Vec3::new(half, half, 0.0);
+        let bottom_left = center + Vec3::new(-half, -half, 0.0);
+        let bottom_right = center + Vec3::new(half, -half, 0.0);
+        // Fades out as it rises.
+        let color = Color::srgba(0.85, 0.9, 1.0, 1.0 - t);
+        gizmos.linestrip([top_left, top_right, bottom_left, bottom_right], color);
+    }
+}
+
+/// A bar over each sheep near its goal in [`GameMode::Gentle`], filling as the sheep calms down.
+fn draw_calm_meters(
+    sheep_query: Query<(&GlobalTransform, &Sheep)>,
+    goal_query: Query<(&Transform, &GoalLocation)>,
+    mut gizmos: Gizmos,
+) {
+    for (transform, sheep) in &sheep_query {
+        if !matches!(sheep.state, SheepState::Wander(_) | SheepState::Evading(_)) {
+            continue;
+        }
+        let position = transform.translation();
+        let Some((goal_translation, goal_radius)) = goal_for(&sheep.color, goal_query.iter())
+        else {
+            continue;
+        };
+        let show_radius = goal_radius + CALM_METER_SHOW_DISTANCE;
+        if position.xz().distance_squared(goal_translation.xz()) > show_radius * show_radius {
+            continue;
+        }
+        let left = position + Vec3::new(-CALM_METER_HALF_WIDTH, CALM_METER_HEIGHT, 0.0);
+        let right = position + Vec3::new(CALM_METER_HALF_WIDTH, CALM_METER_HEIGHT, 0.0);
+        gizmos.line(left, right, Color::BLACK.with_alpha(0.5));
+        if sheep.calm > 0.0 {
+            let color = if sheep.calm >= 1.0 {
+                Color::srgb(0.4, 1.0, 0.4)
+            } else {
+                Color::srgb(0.6, 0.8, 1.0)
+            };
+            gizmos.line(left, left.lerp(right, sheep.calm), color);
+        }
+    }
+}
+
+/// Takes a counted sheep out of play and starts it shrinking, with a puff of wool in its color if
+/// `puffs` is set.
+fn start_counted_animation(
+    commands: &mut Commands,
+    entity: Entity,
+    position: Vec3,
+    color: &SheepColor,
+    sheep_assets: &SheepAssets,
+    puffs: bool,
+) {
+    commands
+        .entity(entity)
+        .remove::<(
+            Sheep,
+            HerdLeader,
+            MovementController,
+            HopMovementController,
+            SphereMovementController,
+            IdleAnimation,
+        )>()
+        .insert(CountedSheep(Timer::from_seconds(
+            COUNTED_SHRINK_SECS,
+            TimerMode::Once,
+        )));
+
+    if !puffs {
+        return;
+    }
+    let material = sheep_assets.wool_material(color);
+    let rng = &mut rand::rng();
+    for _ in 0..COUNTED_PUFF_COUNT {
+        let direction = Vec2::from_angle(rng.random_range(0.0..std::f32::consts::TAU));
+        let velocity =
+            Vec3::new(direction.x, rng.random_range(0.8..1.5), direction.y) * COUNTED_PUFF_SPEED;
+        commands.spawn((
+            Name::new("Counted Puff"),
+            CountedPuff {
+                velocity,
+                timer: Timer::from_seconds(COUNTED_PUFF_SECS, TimerMode::Once),
+            },
+            Mesh3d(sheep_assets.puff_mesh.clone()),
+            MeshMaterial3d(material.clone()),
+            Transform::from_translation(position + Vec3::Y * 0.5),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn shrink_counted_sheep(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut counted_query: Query<(Entity, &mut Transform, &mut CountedSheep)>,
+) {
+    for (entity, mut transform, mut counted) in &mut counted_query {
+        counted.0.tick(time.delta());
+        transform.scale = Vec3::splat(counted.0.fraction_remaining());
+        if counted.0.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_counted_puffs(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut puff_query: Query<(Entity, &mut Transform, &mut CountedPuff)>,
+) {
+    for (entity, mut transform, mut puff) in &mut puff_query {
+        puff.timer.tick(time.delta());
+        transform.translation += puff.velocity * time.delta_secs();
+        transform.scale = Vec3::splat(puff.timer.fraction_remaining());
+        if puff.timer.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_herd_leader(
+    mut commands: Commands,
+    mut leader_query: Query<(Entity, &Sheep, &mut Transform), With<HerdLeader>>,
+    mut candidate_query: Query<(Entity, &Sheep, &mut Transform), Without<HerdLeader>>,
+) {
+    let can_lead = |sheep: &Sheep| {
+        !matches!(
+            sheep.state,
+            SheepState::BeingCounted | SheepState::BeingAbducted
+        )
+    };
+
+    let mut has_leader = false;
+    for (entity, sheep, mut transform) in &mut leader_query {
+        if can_lead(sheep) {
+            has_leader = true;
+        } else {
+            commands.entity(entity).remove::<HerdLeader>();
+            transform.scale = Vec3::ONE;
+        }
+    }
+    if has_leader {
+        return;
+    }
+
+    let rng = &mut rand::rng();
+    let new_leader = candidate_query
+        .iter()
+        .filter(|(_, sheep, _)| can_lead(sheep))
+        .map(|(entity, _, _)| entity)
+        .choose(rng);
+    if let Some(entity) = new_leader
+        && let Ok((_, _, mut transform)) = candidate_query.get_mut(entity)
+    {
+        commands.entity(entity).insert(HerdLeader);
+        transform.scale = Vec3::splat(HERD_LEADER_SCALE);
+    }
+}
+
 fn sheep_herding(
     time: Res<Time>,
+    budget: Res<PerformanceBudget>,
+    config: Res<GameConfig>,
     mut herd_timer: Local<Timer>,
     mut herd_bucket: Local<u64>,
+    mut metrics: ResMut<HerdMetrics>,
     mut set: ParamSet<(
-        Query<(Entity, &Transform, &Sheep)>,
+        Query<(Entity, &Transform, &Sheep, Has<HerdLeader>)>,
         Query<(Entity, &Transform, &mut Sheep)>,
     )>,
 ) {
-    if herd_timer.duration().is_zero() {
-        *herd_timer = Timer::from_seconds(HERD_UPDATE_INTERVAL_SECS, TimerMode::Repeating);
+    let interval = Duration::from_secs_f32(budget.herd_update_interval_secs);
+    if herd_timer.duration() != interval {
+        *herd_timer = Timer::new(interval, TimerMode::Repeating);
     }
     if !herd_timer.tick(time.delta()).just_finished() {
         return;
     }
 
-    *herd_bucket = (*herd_bucket + 1) % HERD_UPDATE_BUCKETS;
+    *herd_bucket = (*herd_bucket + 1) % budget.herd_update_buckets;
     let active_bucket = *herd_bucket;
+    // Each sheep only gets updated once every full cycle through the buckets.
+    let calm_step =
+        CALM_GAIN_PER_SEC * budget.herd_update_interval_secs * budget.herd_update_buckets as f32;
 
-    let snapshot: Vec<(Entity, Vec2)> = set
+    let herd = &config.herd;
+    let radius_sq = herd.radius * herd.radius;
+    let separation_radius_sq = herd.separation_radius * herd.separation_radius;
+
+    let snapshot: Vec<(Entity, Vec2, bool)> = set
         .p0()
         .iter()
-        .filter(|(_, _, sheep)| {
+        .filter(|(_, _, sheep, _)| {
             matches!(sheep.state, SheepState::Wander(_) | SheepState::Evading(_))
         })
-        .map(|(entity, transform, _)| (entity, transform.translation.xz()))
+        .map(|(entity, transform, _, is_leader)| (entity, transform.translation.xz(), is_leader))
         .collect();
+
+    let positions: Vec<(Entity, Vec2)> = snapshot
+        .iter()
+        .map(|(entity, pos, _)| (*entity, *pos))
+        .collect();
+    let centroid = herd_centroid(&positions);
+    let heading: Vec2 = set
+        .p0()
+        .iter()
+        .filter(|(_, _, sheep, _)| {
+            matches!(sheep.state, SheepState::Wander(_) | SheepState::Evading(_))
+        })
+        .map(|(_, _, sheep, _)| sheep.herd_dir)
+        .sum();
+    let spread = positions
+        .iter()
+        .map(|(_, pos)| pos.distance(centroid))
+        .sum::<f32>()
+        / positions.len().max(1) as f32;
+    *metrics = HerdMetrics {
+        centroid,
+        heading: heading.normalize_or_zero(),
+        count: positions.len(),
+        spread,
+    };
+
     if snapshot.len() < 2 {
         return;
     }
 
     let mut grid: HashMap<IVec2, Vec<usize>> = HashMap::default();
-    for (index, (_, position)) in snapshot.iter().enumerate() {
-        grid.entry(spatial_cell(*position)).or_default().push(index);
+    for (index, (_, position, _)) in snapshot.iter().enumerate() {
+        grid.entry(spatial_cell(*position, herd))
+            .or_default()
+            .push(index);
     }
 
     for (entity, transform, mut sheep) in &mut set.p1() {
         if !matches!(sheep.state, SheepState::Wander(_) | SheepState::Evading(_)) {
+            sheep.neighbor_count = 0;
             continue;
         }
-        if entity.to_bits() % HERD_UPDATE_BUCKETS != active_bucket {
+        if entity.to_bits() % budget.herd_update_buckets != active_bucket {
             continue;
         }
 
         let pos = transform.translation.xz();
-        let cell = spatial_cell(pos);
+        let cell = spatial_cell(pos, herd);
         let mut center = Vec2::ZERO;
         let mut nearby_count = 0.0;
         let mut separation = Vec2::ZERO;
@@ -487,42 +1296,52 @@ fn sheep_herding(
                 };
 
                 for &index in indices {
-                    let (other_entity, other_pos): (Entity, Vec2) = snapshot[index];
+                    let (other_entity, other_pos, other_is_leader): (Entity, Vec2, bool) =
+                        snapshot[index];
                     if other_entity == entity {
                         continue;
                     }
 
                     let offset = other_pos - pos;
                     let dist_sq = offset.length_squared();
-                    if dist_sq > HERD_RADIUS_SQ {
+                    if dist_sq > radius_sq {
                         continue;
                     }
 
-                    center += other_pos;
-                    nearby_count += 1.0;
+                    let weight = if other_is_leader {
+                        herd.leader_weight
+                    } else {
+                        1.0
+                    };
+                    center += other_pos * weight;
+                    nearby_count += weight;
                     sampled_neighbors += 1;
 
-                    if dist_sq > 0.0 && dist_sq < HERD_SEPARATION_RADIUS_SQ {
+                    if dist_sq > 0.0 && dist_sq < separation_radius_sq {
                         let dist = dist_sq.sqrt();
                         let push_strength =
-                            (HERD_SEPARATION_RADIUS - dist) / HERD_SEPARATION_RADIUS;
+                            (herd.separation_radius - dist) / herd.separation_radius;
                         separation += (pos - other_pos).normalize_or(Vec2::X) * push_strength;
                     }
 
-                    if sampled_neighbors >= HERD_MAX_NEIGHBORS {
+                    if sampled_neighbors >= herd.max_neighbors {
                         break 'neighbor_cells;
                     }
                 }
             }
         }
 
+        sheep.neighbor_count = sampled_neighbors;
+        if matches!(sheep.state, SheepState::Wander(_)) && sampled_neighbors >= CALM_MIN_NEIGHBORS {
+            sheep.calm = (sheep.calm + calm_step).min(1.0);
+        }
         if nearby_count <= 0.0 {
             sheep.herd_dir = Vec2::ZERO;
             continue;
         }
 
-        let cohesion = ((center / nearby_count) - pos).normalize_or_zero() * HERD_COHESION_WEIGHT;
-        let avoid = separation.normalize_or_zero() * HERD_SEPARATION_WEIGHT;
+        let cohesion = ((center / nearby_count) - pos).normalize_or_zero() * herd.cohesion_weight;
+        let avoid = separation.normalize_or_zero() * herd.separation_weight;
         sheep.herd_dir = (cohesion + avoid).normalize_or_zero();
     }
 }
@@ -530,119 +1349,77 @@ fn sheep_herding(
 fn sheep_goal_check(
     mut commands: Commands,
     sheep_query: Query<(Entity, &Transform, &mut Sheep)>,
-    goal_query: Single<&Transform, With<GoalLocation>>,
+    goal_query: Query<(&Transform, &GoalLocation)>,
     mut state: ResMut<GameState>,
     mut round_stats: ResMut<RoundStats>,
+    mut run_stats: ResMut<RunStatistics>,
     sheep_assets: Res<SheepAssets>,
     bounds: Res<LevelBounds>,
+    budget: Res<PerformanceBudget>,
+    game_mode: Res<GameMode>,
     mut writer: MessageWriter<GoalTextMessage>,
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut count_pace: ResMut<CountPace>,
 ) {
-    let goal_pos = goal_query.translation.xz();
+    let needs_calm = *game_mode == GameMode::Gentle;
     for (entity, sheep_transform, mut sheep_c) in sheep_query {
         let pos = sheep_transform.translation.xz();
+        let Some((goal_translation, goal_radius)) = goal_for(&sheep_c.color, goal_query.iter())
+        else {
+            continue;
+        };
+        let goal_pos = goal_translation.xz();
+        let text_position = Some(goal_translation);
         match sheep_c.state {
             SheepState::BeingAbducted => {}
             SheepState::BeingCounted => {
                 if pos.distance_squared(goal_pos) < 2.5 {
                     let is_first_counted = round_stats.sheep_counted == 0;
-                    if is_first_counted && state.is_charm_active(Charm::Cloning) {
-                        state.sheep_count += 1;
-                        match sheep_c.color {
-                            SheepColor::Blue => state.blue_sheep_count += 1,
-                            SheepColor::Red => state.red_sheep_count += 1,
-                            SheepColor::Black => state.black_sheep_count += 1,
-                            SheepColor::Gold => state.gold_sheep_count += 1,
-                            _ => {}
-                        }
-                        writer.write(GoalTextMessage {
-                            text: "Cloned".to_string(),
-                            color: Some(Color::srgb(0.55, 0.85, 0.95)),
-                        });
-                    }
-
-                    match sheep_c.color {
-                        SheepColor::White => {
-                            if state.is_charm_active(Charm::Evolution) {
-                                round_stats.white_sheep_counted += 1;
-                                if round_stats.white_sheep_counted.is_multiple_of(5) {
-                                    state.blue_sheep_count += 1;
-                                    writer.write(GoalTextMessage {
-                                        text: "Evolved to blue".to_string(),
-                                        color: Some(Color::srgb(0.3, 0.4, 0.8)),
-                                    });
-                                } else {
-                                    writer.write(GoalTextMessage {
-                                        text: "0 points".to_string(),
-                                        color: None,
-                                    });
-                                }
-                            } else {
-                                state.points += 1;
-                                writer.write(GoalTextMessage {
-                                    text: "+1 point".to_string(),
-                                    color: None,
-                                });
-                            }
-                        }
-                        SheepColor::Blue => {
-                            state.points += 5;
-                            writer.write(GoalTextMessage {
-                                text: "+5 points".to_string(),
-                                color: Some(Color::srgb(0.3, 0.4, 0.8)),
-                            });
-                        }
-                        SheepColor::Red => {
-                            if is_first_counted && state.is_charm_active(Charm::RedToGold) {
-                                state.red_sheep_count -= 1;
-                                state.gold_sheep_count += 1;
-                            }
-                            state.points = floor(state.points as f32 * 1.5) as u32;
+                    let points_before = state.points;
+                    let effects = apply_count(
+                        &sheep_c.color,
+                        is_first_counted,
+                        &mut state,
+                        &mut round_stats,
+                    );
+                    run_stats.record_sheep_counted(&sheep_c.color);
+                    let mut points_scaled = false;
+                    if *game_mode == GameMode::Ranked {
+                        let multiplier = count_pace.record(time.elapsed_secs(), &config.ranked);
+                        if multiplier < 1.0 {
+                            let gained = state.points.saturating_sub(points_before);
+                            let scaled = (gained as f32 * multiplier) as u32;
+                            state.points = points_before + scaled;
+                            points_scaled = true;
                             writer.write(GoalTextMessage {
-                                text: "points x1.5".to_string(),
-                                color: Some(Color::srgb(1.0, 0.3, 0.3)),
+                                text: format!("Too fast! +{scaled} (x{multiplier:.2})"),
+                                color: Some(Color::srgb(0.95, 0.35, 0.35)),
+                                position: text_position,
                             });
                         }
-                        SheepColor::Black => {
-                            if state.is_charm_active(Charm::BlackInc) {
-                                let points = 1 + round_stats.black_sheep_counted as u32;
-                                state.points += points;
-                                writer.write(GoalTextMessage {
-                                    text: format!("+{} points", points),
-                                    color: None,
-                                });
-                            } else {
-                                state.points += 1;
+                    }
+                    for effect in effects {
+                        match effect {
+                            CountEffect::PointsText { .. } if points_scaled => {}
+                            CountEffect::Text { text, color }
+                            | CountEffect::PointsText { text, color } => {
                                 writer.write(GoalTextMessage {
-                                    text: "+1 point".to_string(),
-                                    color: None,
+                                    text,
+                                    color,
+                                    position: text_position,
                                 });
                             }
-                            round_stats.black_sheep_counted += 1;
-                            if state.is_charm_active(Charm::Exponential) {
+                            CountEffect::SpawnSheep(color) => {
                                 let rng = &mut rand::rng();
                                 let x = rng.random_range(bounds.min.x..=bounds.max.x);
                                 let z = rng.random_range(bounds.min.y..=bounds.max.y);
-                                let pos = Vec3::new(x, 0.0, z);
                                 let entity = sheep(
                                     &mut commands,
                                     &sheep_assets,
-                                    pos,
+                                    Vec3::new(x, 0.0, z),
                                     &state,
-                                    SheepColor::Black,
-                                    false,
-                                );
-                                commands
-                                    .entity(entity)
-                                    .insert(DespawnOnExit(GamePhase::Herding));
-                                let x = rng.random_range(bounds.min.x..=bounds.max.x);
-                                let z = rng.random_range(bounds.min.y..=bounds.max.y);
-                                let pos = Vec3::new(x, 0.0, z);
-                                let entity = sheep(
-                                    &mut commands,
-                                    &sheep_assets,
-                                    pos,
-                                    &state,
-                                    SheepColor::Black,
+                                    color,
                                     false,
                                 );
                                 commands
@@ -650,20 +1427,20 @@ fn sheep_goal_check(
                                     .insert(DespawnOnExit(GamePhase::Herding));
                             }
                         }
-                        SheepColor::Gold => {
-                            state.money += 1;
-                            writer.write(GoalTextMessage {
-                                text: "+1 gold".to_string(),
-                                color: Some(Color::srgb(1.0, 0.82, 0.2)),
-                            });
-                        }
                     }
-                    round_stats.sheep_counted += 1;
-                    commands.entity(entity).despawn();
+                    start_counted_animation(
+                        &mut commands,
+                        entity,
+                        sheep_transform.translation,
+                        &sheep_c.color,
+                        &sheep_assets,
+                        budget.visual_effects,
+                    );
                 }
             }
             _ => {
-                if pos.distance_squared(goal_pos) < GOAL_RADIUS * GOAL_RADIUS {
+                let calm_enough = !needs_calm || sheep_c.calm >= 1.0;
+                if calm_enough && pos.distance_squared(goal_pos) < goal_radius * goal_radius {
                     sheep_c.state = SheepState::BeingCounted;
                 }
             }
@@ -671,6 +1448,159 @@ fn sheep_goal_check(
     }
 }
 
+/// Something that happens because a sheep was counted, other than the change to the score.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CountEffect {
+    /// Floating text over the goal.
+    Text { text: String, color: Option<Color> },
+    /// Floating text over the goal about the points scored. Left out in favor of a single scaled
+    /// total when ranked pacing cuts the points down.
+    PointsText { text: String, color: Option<Color> },
+    /// A new sheep of this color, spawned somewhere random in the level.
+    SpawnSheep(SheepColor),
+}
+
+fn count_text(text: impl Into<String>, color: Option<Color>) -> CountEffect {
+    CountEffect::Text {
+        text: text.into(),
+        color,
+    }
+}
+
+fn points_text(text: impl Into<String>, color: Option<Color>) -> CountEffect {
+    CountEffect::PointsText {
+        text: text.into(),
+        color,
+    }
+}
+
+/// Scores a sheep of `color` being counted, applying the active charms and modifiers to `state`
+/// and `round_stats`. `is_first` is whether it's the first sheep counted this round. Returns what
+/// the game should show or spawn as a result, in order.
+pub fn apply_count(
+    color: &SheepColor,
+    is_first: bool,
+    state: &mut GameState,
+    round_stats: &mut RoundStats,
+) -> Vec<CountEffect> {
+    let mut effects = Vec::new();
+
+    if state.is_modifier_active(Modifier::SortingPens) {
+        // Applied before the color's own scoring so red sheep multiply it too.
+        state.points = state.points.saturating_add(1);
+        effects.push(points_text(
+            "Sorted +1",
+            Some(Color::srgb(0.55, 0.95, 0.55)),
+        ));
+    }
+
+    if is_first && state.is_charm_active(Charm::Cloning) {
+        state.sheep_count += 1;
+        match color {
+            SheepColor::Blue => state.blue_sheep_count += 1,
+            SheepColor::Red => state.red_sheep_count += 1,
+            SheepColor::Black => state.black_sheep_count += 1,
+            SheepColor::Gold => state.gold_sheep_count += 1,
+            SheepColor::Green => state.green_sheep_count += 1,
+            _ => {}
+        }
+        effects.push(count_text("Cloned", Some(Color::srgb(0.55, 0.85, 0.95))));
+    }
+
+    match color {
+        SheepColor::White => {
+            if state.is_charm_active(Charm::Evolution) {
+                round_stats.white_sheep_counted += 1;
+                if round_stats
+                    .white_sheep_counted
+                    .is_multiple_of(EVOLUTION_WHITE_SHEEP_PER_BLUE)
+                {
+                    state.blue_sheep_count += 1;
+                    effects.push(count_text(
+                        "Evolved to blue",
+                        Some(Color::srgb(0.3, 0.4, 0.8)),
+                    ));
+                } else {
+                    effects.push(points_text("0 points", None));
+                }
+            } else {
+                state.points = state.points.saturating_add(1);
+                effects.push(points_text("+1 point", None));
+            }
+        }
+        SheepColor::Blue => {
+            state.points = state.points.saturating_add(BLUE_SHEEP_POINTS);
+            effects.push(points_text(
+                format!("+{BLUE_SHEEP_POINTS} points"),
+                Some(Color::srgb(0.3, 0.4, 0.8)),
+            ));
+        }
+        SheepColor::Red => {
+            if is_first && state.is_charm_active(Charm::RedToGold) {
+                state.red_sheep_count -= 1;
+                state.gold_sheep_count += 1;
+            }
+            // Same as flooring points * 1.5, without going through a float.
+            state.points = state.points.saturating_add(state.points / 2);
+            effects.push(points_text("points x1.5", Some(Color::srgb(1.0, 0.3, 0.3))));
+        }
+        SheepColor::Black => {
+            let points = if state.is_charm_active(Charm::BlackInc) {
+                1 + round_stats.black_sheep_counted as u32
+            } else {
+                1
+            };
+            state.points = state.points.saturating_add(points);
+            if points == 1 {
+                effects.push(points_text("+1 point", None));
+            } else {
+                effects.push(points_text(format!("+{points} points"), None));
+            }
+            round_stats.black_sheep_counted += 1;
+            if state.is_charm_active(Charm::Exponential) {
+                effects.push(CountEffect::SpawnSheep(SheepColor::Black));
+                effects.push(CountEffect::SpawnSheep(SheepColor::Black));
+            }
+        }
+        SheepColor::Gold => {
+            state.money += 1;
+            effects.push(count_text("+1 gold", Some(Color::srgb(1.0, 0.82, 0.2))));
+        }
+        SheepColor::Green => {
+            // Extending the duration of a `TimerMode::Once` timer keeps it running rather than
+            // resetting it, so this just adds time on top of what's left.
+            let duration = state.countdown.duration() + GREEN_SHEEP_BONUS;
+            state.countdown.set_duration(duration);
+            effects.push(count_text(
+                format!("+{} seconds", GREEN_SHEEP_BONUS.as_secs()),
+                Some(Color::srgb(0.35, 0.8, 0.35)),
+            ));
+        }
+        SheepColor::Contrarian => {
+            state.points = state.points.saturating_add(3);
+            effects.push(points_text("+3 points", Some(Color::srgb(0.6, 0.35, 0.85))));
+        }
+    }
+
+    round_stats.sheep_counted += 1;
+    effects
+}
+
+/// How strongly a contrarian sheep at `pos` wants to move away from the goal. Zero for every other
+/// sheep, and for contrarians that are far enough away.
+fn goal_avoidance(sheep: &Sheep, pos: Vec2) -> Vec2 {
+    if sheep.color != SheepColor::Contrarian {
+        return Vec2::ZERO;
+    }
+    let away = pos - GOAL_POSITION.xz();
+    let distance = away.length();
+    if distance >= CONTRARIAN_AVOID_RADIUS {
+        return Vec2::ZERO;
+    }
+    let strength = (CONTRARIAN_AVOID_RADIUS - distance) / CONTRARIAN_AVOID_RADIUS;
+    away.normalize_or(Vec2::X) * strength * CONTRARIAN_AVOID_WEIGHT
+}
+
 // To prevent sheep getting stuck in corners
 fn pick_evasion_dir(pos: Vec2, preferred: Vec2, bounds: &LevelBounds) -> Vec2 {
     let candidates = [preferred.perp(), -preferred.perp(), -preferred];
@@ -693,10 +1623,11 @@ fn pick_evasion_dir(pos: Vec2, preferred: Vec2, bounds: &LevelBounds) -> Vec2 {
     best_dir
 }
 
-fn spatial_cell(position: Vec2) -> IVec2 {
+/// Cells are as wide as the herd radius, so every flockmate is in a cell next to the sheep's own.
+fn spatial_cell(position: Vec2, herd: &HerdConfig) -> IVec2 {
     IVec2::new(
-        (position.x / HERD_CELL_SIZE).floor() as i32,
-        (position.y / HERD_CELL_SIZE).floor() as i32,
+        (position.x / herd.radius).floor() as i32,
+        (position.y / herd.radius).floor() as i32,
     )
 }
 
@@ -705,20 +1636,36 @@ fn apply_wool_material_on_scene_ready(
     mut commands: Commands,
     sheep_q: Query<&Sheep>,
     children: Query<&Children>,
+    placeholders: Query<(), With<SheepPlaceholder>>,
     mesh_materials: Query<(&MeshMaterial3d<StandardMaterial>, &GltfMaterialName)>,
     sheep_assets: Res<SheepAssets>,
+    accessibility: Res<AccessibilityConfig>,
 ) {
     let Ok(sheep) = sheep_q.get(scene_ready.entity) else {
         return;
     };
 
-    let material = match sheep.color {
-        SheepColor::White => sheep_assets.wool_white.clone(),
-        SheepColor::Black => sheep_assets.wool_black.clone(),
-        SheepColor::Blue => sheep_assets.wool_blue.clone(),
-        SheepColor::Red => sheep_assets.wool_red.clone(),
-        SheepColor::Gold => sheep_assets.wool_gold.clone(),
-    };
+    // Always added, and hidden while colorblind mode is off, so the setting can be flipped
+    // mid-round.
+    if let Some(symbol) = sheep_assets.color_symbol(&sheep.color) {
+        commands.entity(scene_ready.entity).with_child((
+            Name::new("Color Symbol"),
+            ColorSymbol,
+            Mesh3d(symbol),
+            MeshMaterial3d(sheep_assets.symbol_material.clone()),
+            Transform::from_xyz(0.0, COLOR_SYMBOL_HEIGHT, 0.0),
+            color_symbol_visibility(&accessibility),
+            NotShadowCaster,
+        ));
+    }
+
+    let material = sheep_assets.wool_material(&sheep.color);
+
+    for child in children.iter_descendants(scene_ready.entity) {
+        if placeholders.contains(child) {
+            commands.entity(child).despawn();
+        }
+    }
 
     for descendant in children.iter_descendants(scene_ready.entity) {
         let Ok((_mat_handle, mat_name)) = mesh_materials.get(descendant) else {
@@ -734,3 +1681,217 @@ fn apply_wool_material_on_scene_ready(
             .insert(MeshMaterial3d(material.clone()));
     }
 }
+
+/// A shape above a sheep telling its color apart without relying on the wool color. Only shown
+/// with [`AccessibilityConfig::colorblind_mode`] on.
+#[derive(Component, Debug)]
+struct ColorSymbol;
+
+fn color_symbol_visibility(accessibility: &AccessibilityConfig) -> Visibility {
+    if accessibility.colorblind_mode {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn show_color_symbols(
+    accessibility: Res<AccessibilityConfig>,
+    mut symbols: Query<&mut Visibility, With<ColorSymbol>>,
+) {
+    for mut visibility in &mut symbols {
+        visibility.set_if_neq(color_symbol_visibility(&accessibility));
+    }
+}
+
+/// Undoes the sheep's rotation and scale on its color symbol, so the symbol stays upright and the
+/// same size directly above the sheep while it rolls, shakes, looks around or leads the herd.
+fn keep_color_symbols_upright(
+    sheep_query: Query<
+        (&Transform, Option<&SphereMovementController>),
+        (With<Sheep>, Without<ColorSymbol>),
+    >,
+    mut symbols: Query<(&ChildOf, &mut Transform), With<ColorSymbol>>,
+) {
+    for (child_of, mut transform) in &mut symbols {
+        let Ok((sheep_transform, sphere)) = sheep_query.get(child_of.parent()) else {
+            continue;
+        };
+        // A rolling sheep turns about the center of its sphere rather than its feet.
+        let pivot = sphere.map_or(Vec3::ZERO, SphereMovementController::center_offset);
+        let inverse_rotation = sheep_transform.rotation.inverse();
+        let inverse_scale = sheep_transform.scale.recip();
+        let above_pivot = Vec3::Y * (COLOR_SYMBOL_HEIGHT - pivot.y);
+        transform.rotation = inverse_rotation;
+        transform.scale = inverse_scale;
+        transform.translation = (pivot + inverse_rotation * above_pivot) * inverse_scale;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::state::ActiveModifier;
+
+    fn state_with(charms: &[Charm]) -> GameState {
+        GameState {
+            points: 10,
+            charms: charms.to_vec(),
+            ..default()
+        }
+    }
+
+    fn count(color: SheepColor, is_first: bool, state: &mut GameState) -> Vec<CountEffect> {
+        apply_count(&color, is_first, state, &mut RoundStats::default())
+    }
+
+    #[test]
+    fn color_symbol_stays_upright_above_a_rolling_sheep() {
+        let mut app = App::new();
+        app.add_systems(Update, keep_color_symbols_upright);
+        let sheep_transform = Transform::from_xyz(3.0, 0.0, -2.0)
+            .with_rotation(Quat::from_euler(EulerRot::XYZ, 0.7, 1.2, -0.4))
+            .with_scale(Vec3::splat(HERD_LEADER_SCALE));
+        let sheep = app
+            .world_mut()
+            .spawn((Sheep::new(SheepColor::Blue), sheep_transform))
+            .id();
+        let symbol = app
+            .world_mut()
+            .spawn((ColorSymbol, Transform::default(), ChildOf(sheep)))
+            .id();
+        app.update();
+
+        let local = *app.world().get::<Transform>(symbol).unwrap();
+        let world = sheep_transform.mul_transform(local);
+        let expected = sheep_transform.translation + Vec3::Y * COLOR_SYMBOL_HEIGHT;
+        assert!(world.translation.distance(expected) < 1e-4);
+        assert!(world.rotation.angle_between(Quat::IDENTITY) < 1e-4);
+        assert!(world.scale.distance(Vec3::ONE) < 1e-4);
+    }
+
+    #[test]
+    fn each_color_scores_as_described() {
+        let mut state = state_with(&[]);
+        count(SheepColor::White, false, &mut state);
+        assert_eq!(state.points, 11);
+
+        let mut state = state_with(&[]);
+        count(SheepColor::Blue, false, &mut state);
+        assert_eq!(state.points, 15);
+
+        let mut state = state_with(&[]);
+        count(SheepColor::Red, false, &mut state);
+        assert_eq!(state.points, 15);
+
+        let mut state = state_with(&[]);
+        count(SheepColor::Black, false, &mut state);
+        assert_eq!(state.points, 11);
+
+        let mut state = state_with(&[]);
+        count(SheepColor::Gold, false, &mut state);
+        assert_eq!((state.points, state.money), (10, 1));
+
+        let mut state = state_with(&[]);
+        let duration = state.countdown.duration();
+        count(SheepColor::Green, false, &mut state);
+        assert_eq!(state.points, 10);
+        assert_eq!(state.countdown.duration(), duration + GREEN_SHEEP_BONUS);
+
+        let mut state = state_with(&[]);
+        count(SheepColor::Contrarian, false, &mut state);
+        assert_eq!(state.points, 13);
+    }
+
+    #[test]
+    fn every_count_is_tallied() {
+        let mut state = state_with(&[]);
+        let mut round_stats = RoundStats::default();
+        for color in [SheepColor::White, SheepColor::Black, SheepColor::Gold] {
+            apply_count(&color, false, &mut state, &mut round_stats);
+        }
+        assert_eq!(round_stats.sheep_counted, 3);
+        assert_eq!(round_stats.black_sheep_counted, 1);
+    }
+
+    #[test]
+    fn sorting_pens_add_a_point_before_red_multiplies() {
+        let mut state = state_with(&[]);
+        state.active_modifiers.push(ActiveModifier {
+            modifier: Modifier::SortingPens,
+            rounds_left: 1,
+            drafted: false,
+        });
+        count(SheepColor::Red, false, &mut state);
+        assert_eq!(state.points, 16);
+    }
+
+    #[test]
+    fn evolution_turns_every_fifth_white_sheep_blue() {
+        let mut state = state_with(&[Charm::Evolution]);
+        let blue_before = state.blue_sheep_count;
+        let mut round_stats = RoundStats::default();
+        for _ in 0..5 {
+            apply_count(&SheepColor::White, false, &mut state, &mut round_stats);
+        }
+        assert_eq!(state.points, 10);
+        assert_eq!(state.blue_sheep_count, blue_before + 1);
+    }
+
+    #[test]
+    fn black_inc_scores_more_for_each_black_sheep() {
+        let mut state = state_with(&[Charm::BlackInc]);
+        let mut round_stats = RoundStats::default();
+        for _ in 0..3 {
+            apply_count(&SheepColor::Black, false, &mut state, &mut round_stats);
+        }
+        assert_eq!(state.points, 10 + 1 + 2 + 3);
+    }
+
+    #[test]
+    fn exponential_spawns_two_black_sheep() {
+        let mut state = state_with(&[Charm::Exponential]);
+        let effects = count(SheepColor::Black, false, &mut state);
+        let spawned = effects
+            .iter()
+            .filter(|effect| **effect == CountEffect::SpawnSheep(SheepColor::Black))
+            .count();
+        assert_eq!(spawned, 2);
+    }
+
+    #[test]
+    fn cloning_only_copies_the_first_sheep() {
+        let mut state = state_with(&[Charm::Cloning]);
+        let (sheep_before, blue_before) = (state.sheep_count, state.blue_sheep_count);
+        count(SheepColor::Blue, true, &mut state);
+        count(SheepColor::Blue, false, &mut state);
+        assert_eq!(state.sheep_count, sheep_before + 1);
+        assert_eq!(state.blue_sheep_count, blue_before + 1);
+    }
+
+    #[test]
+    fn red_to_gold_only_converts_the_first_red_sheep() {
+        let mut state = state_with(&[Charm::RedToGold]);
+        let red_before = state.red_sheep_count;
+        count(SheepColor::Red, true, &mut state);
+        count(SheepColor::Red, false, &mut state);
+        assert_eq!(state.red_sheep_count, red_before - 1);
+        assert_eq!(state.gold_sheep_count, 1);
+    }
+
+    /// Cloning copies the red sheep before Red to Gold turns one red sheep gold, so the flock keeps
+    /// the same number of red sheep and gains a gold one.
+    #[test]
+    fn cloning_applies_before_red_to_gold() {
+        let mut state = state_with(&[Charm::Cloning, Charm::RedToGold]);
+        let (sheep_before, red_before) = (state.sheep_count, state.red_sheep_count);
+        let effects = count(SheepColor::Red, true, &mut state);
+        assert_eq!(state.sheep_count, sheep_before + 1);
+        assert_eq!(state.red_sheep_count, red_before);
+        assert_eq!(state.gold_sheep_count, 1);
+        assert_eq!(
+            effects.first(),
+            Some(&count_text("Cloned", Some(Color::srgb(0.55, 0.85, 0.95))))
+        );
+    }
+}
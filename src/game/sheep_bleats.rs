@@ -0,0 +1,188 @@
+//! Sheep bleating now and then, heard from where they stand. The bleats are synthesized rather
+//! than loaded, so each variant is just a pitch and length.
+
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::{
+    audio::{AddAudioSource, Decodable, Source},
+    prelude::*,
+};
+use rand::{Rng, seq::IteratorRandom};
+
+use crate::{
+    AppSystems, PausableSystems,
+    audio::pitched_sound_effect_3d,
+    game::{
+        sheep::{Sheep, SheepState},
+        state::GamePhase,
+    },
+};
+
+/// At most one bleat starts per interval, however big the flock is.
+const BLEAT_MIN_INTERVAL_SECS: f32 = 0.3;
+/// Roughly how often each calm sheep bleats, until the flock is big enough to hit the global limit.
+const BLEAT_SECS_PER_SHEEP: f32 = 12.0;
+/// Spooked sheep are this many times as likely to be the one bleating, and bleat faster.
+const SPOOKED_BLEAT_WEIGHT: f32 = 4.0;
+const SPOOKED_BLEAT_SPEED: f32 = 1.3;
+const BLEAT_SAMPLE_RATE: u32 = 22_050;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_audio_source::<Bleat>();
+    app.init_resource::<BleatAssets>();
+    app.add_systems(
+        Update,
+        sheep_bleats
+            .run_if(in_state(GamePhase::Herding))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// A synthesized "baa": a buzzy tone with a wobble, fading in quickly and out slowly.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct Bleat {
+    pub frequency: f32,
+    pub duration: Duration,
+}
+
+impl Decodable for Bleat {
+    type DecoderItem = f32;
+    type Decoder = BleatDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        BleatDecoder {
+            frequency: self.frequency,
+            total_samples: (self.duration.as_secs_f32() * BLEAT_SAMPLE_RATE as f32) as u32,
+            sample: 0,
+            phase: 0.0,
+        }
+    }
+}
+
+pub struct BleatDecoder {
+    frequency: f32,
+    total_samples: u32,
+    sample: u32,
+    phase: f32,
+}
+
+impl Iterator for BleatDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        if self.sample >= self.total_samples {
+            return None;
+        }
+        let t = self.sample as f32 / BLEAT_SAMPLE_RATE as f32;
+        let progress = self.sample as f32 / self.total_samples as f32;
+        self.sample += 1;
+
+        // A fast vibrato is what makes a tone sound like a bleat rather than a beep.
+        let vibrato = 1.0 + 0.04 * (t * 28.0).sin();
+        self.phase = (self.phase + self.frequency * vibrato / BLEAT_SAMPLE_RATE as f32).fract();
+        let tone = (self.phase * TAU).sin()
+            + 0.5 * (self.phase * 2.0 * TAU).sin()
+            + 0.3 * (self.phase * 3.0 * TAU).sin();
+        let envelope = (progress / 0.08).min(1.0) * (1.0 - progress).powi(2);
+        Some(tone * envelope * 0.3)
+    }
+}
+
+impl Source for BleatDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        BLEAT_SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(
+            self.total_samples as f32 / BLEAT_SAMPLE_RATE as f32,
+        ))
+    }
+}
+
+/// A few bleats of different pitches and lengths, picked at random so the flock doesn't sound
+/// like one sheep.
+#[derive(Resource, Debug)]
+struct BleatAssets {
+    variants: Vec<Handle<Bleat>>,
+}
+
+impl FromWorld for BleatAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut bleats = world.resource_mut::<Assets<Bleat>>();
+        let variants = [(300.0, 0.45), (340.0, 0.5), (380.0, 0.4), (260.0, 0.55)]
+            .into_iter()
+            .map(|(frequency, secs)| {
+                bleats.add(Bleat {
+                    frequency,
+                    duration: Duration::from_secs_f32(secs),
+                })
+            })
+            .collect();
+        Self { variants }
+    }
+}
+
+fn sheep_bleats(
+    time: Res<Time>,
+    mut timer: Local<Timer>,
+    mut commands: Commands,
+    assets: Res<BleatAssets>,
+    sheep_query: Query<(&Transform, &Sheep)>,
+) {
+    if timer.duration().is_zero() {
+        *timer = Timer::from_seconds(BLEAT_MIN_INTERVAL_SECS, TimerMode::Repeating);
+    }
+    if !timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let rng = &mut rand::rng();
+    let total_weight: f32 = sheep_query
+        .iter()
+        .map(|(_, sheep)| bleat_weight(sheep))
+        .sum();
+    let chance = (total_weight * BLEAT_MIN_INTERVAL_SECS / BLEAT_SECS_PER_SHEEP).min(1.0);
+    if chance <= 0.0 || !rng.random_bool(chance as f64) {
+        return;
+    }
+
+    // Weighted pick: spooked sheep get several tickets each.
+    let mut ticket = rng.random_range(0.0..total_weight);
+    let Some((transform, sheep)) = sheep_query.iter().find(|(_, sheep)| {
+        ticket -= bleat_weight(sheep);
+        ticket < 0.0
+    }) else {
+        return;
+    };
+    let Some(bleat) = assets.variants.iter().choose(rng) else {
+        return;
+    };
+    let speed = if matches!(sheep.state(), SheepState::Spooked(_)) {
+        SPOOKED_BLEAT_SPEED
+    } else {
+        1.0
+    } * rng.random_range(0.9..1.1);
+    commands.spawn((
+        Name::new("Bleat"),
+        pitched_sound_effect_3d(bleat.clone(), transform.translation, speed),
+    ));
+}
+
+/// How likely a sheep is to be the next to bleat, relative to a calm one.
+fn bleat_weight(sheep: &Sheep) -> f32 {
+    match sheep.state() {
+        SheepState::Spooked(_) => SPOOKED_BLEAT_WEIGHT,
+        SheepState::Wander(_) | SheepState::Evading(_) => 1.0,
+        SheepState::BeingCounted | SheepState::BeingAbducted | SheepState::Sleeping => 0.0,
+    }
+}
@@ -0,0 +1,98 @@
+//! Optional name tags floating above sheep, for players who like to narrate their herding.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems,
+    game::{camera::MainCamera, sheep::Sheep},
+    screens::Screen,
+    settings::GameplaySettings,
+};
+
+/// Big flocks only get this many name tags, so the field doesn't turn into a wall of text.
+const MAX_NAME_TAGS: usize = 24;
+const NAME_TAG_HEIGHT_OFFSET: f32 = 1.6;
+const NAME_TAG_FONT_SIZE: f32 = 16.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (sync_name_tags, position_name_tags)
+            .chain()
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// A name tag following `0`.
+#[derive(Component, Debug)]
+struct SheepNameTag(Entity);
+
+/// Adds name tags to sheep that don't have one (up to [`MAX_NAME_TAGS`]), and removes those whose
+/// sheep have been counted or abducted.
+fn sync_name_tags(
+    mut commands: Commands,
+    settings: Res<GameplaySettings>,
+    sheep_query: Query<(Entity, &Sheep)>,
+    tags: Query<(Entity, &SheepNameTag)>,
+) {
+    let mut tagged = Vec::with_capacity(MAX_NAME_TAGS);
+    for (tag_entity, tag) in &tags {
+        let keep = settings.show_sheep_names
+            && sheep_query
+                .get(tag.0)
+                .is_ok_and(|(_, sheep)| !sheep.is_being_abducted());
+        if keep {
+            tagged.push(tag.0);
+        } else {
+            commands.entity(tag_entity).despawn();
+        }
+    }
+    if !settings.show_sheep_names {
+        return;
+    }
+
+    let untagged = sheep_query
+        .iter()
+        .filter(|(entity, sheep)| !sheep.is_being_abducted() && !tagged.contains(entity));
+    for (entity, sheep) in untagged.take(MAX_NAME_TAGS.saturating_sub(tagged.len())) {
+        commands.spawn((
+            Name::new("Sheep Name Tag"),
+            Node {
+                position_type: PositionType::Absolute,
+                display: Display::None,
+                ..default()
+            },
+            Text::new(sheep.name()),
+            TextFont::from_font_size(NAME_TAG_FONT_SIZE),
+            TextColor(Color::WHITE),
+            Pickable::IGNORE,
+            SheepNameTag(entity),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn position_name_tags(
+    camera: Single<(&Camera, &GlobalTransform), With<MainCamera>>,
+    sheep_query: Query<&GlobalTransform, With<Sheep>>,
+    mut tags: Query<(&mut Node, &ComputedNode, &SheepNameTag)>,
+) {
+    let (camera, camera_transform) = *camera;
+    for (mut node, computed, tag) in &mut tags {
+        let viewport_pos = sheep_query.get(tag.0).ok().and_then(|transform| {
+            let world_pos = transform.translation() + Vec3::Y * NAME_TAG_HEIGHT_OFFSET;
+            camera.world_to_viewport(camera_transform, world_pos).ok()
+        });
+        match viewport_pos {
+            Some(viewport_pos) => {
+                // Centre the tag over the sheep.
+                let half_width = computed.size().x * computed.inverse_scale_factor() / 2.0;
+                node.left = px(viewport_pos.x - half_width);
+                node.top = px(viewport_pos.y);
+                node.display = Display::DEFAULT;
+            }
+            None => node.display = Display::None,
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! An optional gamble offered once a round's points target is met: count a few more sheep against
+//! a short timer to double the money from the next modifier, or get none at all.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems, Pause,
+    game::state::{GamePhase, GameState, RoundStats},
+    theme::prelude::*,
+};
+
+/// Extra sheep that have to be counted to win.
+pub const BONUS_SHEEP: u16 = 5;
+const BONUS_SECONDS: f32 = 15.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<DoubleOrNothing>();
+    app.add_systems(OnEnter(GamePhase::Herding), reset_double_or_nothing);
+    app.add_systems(
+        Update,
+        (tick_bonus_challenge, update_bonus_challenge_ui)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::Herding)),
+    );
+}
+
+#[derive(Resource, Debug, Default)]
+pub enum DoubleOrNothing {
+    #[default]
+    NotOffered,
+    /// Waiting for the player to accept or decline. The game is paused meanwhile.
+    Offered,
+    Active {
+        timer: Timer,
+        counted_at_start: u16,
+    },
+    Won,
+    Lost,
+    Declined,
+}
+
+impl DoubleOrNothing {
+    /// Whether the end of the round should wait for the gamble to play out.
+    pub fn holds_round_end(&self) -> bool {
+        matches!(self, Self::Offered | Self::Active { .. })
+    }
+
+    /// How much the money for this round's modifier is multiplied by.
+    pub fn money_multiplier(&self) -> u32 {
+        match self {
+            Self::Won => 2,
+            Self::Lost => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// The main round timer stops while the gamble is being offered or played.
+pub fn round_timer_running(double_or_nothing: Res<DoubleOrNothing>) -> bool {
+    !double_or_nothing.holds_round_end()
+}
+
+#[derive(Component)]
+struct DoubleOrNothingOffer;
+
+#[derive(Component)]
+struct BonusChallengeText;
+
+fn reset_double_or_nothing(mut double_or_nothing: ResMut<DoubleOrNothing>) {
+    *double_or_nothing = DoubleOrNothing::default();
+}
+
+/// Pause the round and ask the player whether they want to gamble.
+pub(super) fn offer_double_or_nothing(
+    commands: &mut Commands,
+    double_or_nothing: &mut DoubleOrNothing,
+    next_pause: &mut NextState<Pause>,
+) {
+    *double_or_nothing = DoubleOrNothing::Offered;
+    next_pause.set(Pause(true));
+    commands.spawn((
+        widget::ui_root("Double or Nothing Offer"),
+        GlobalZIndex(2),
+        DoubleOrNothingOffer,
+        DespawnOnExit(GamePhase::Herding),
+        children![(
+            widget::panel(),
+            children![
+                widget::header("Double or Nothing?"),
+                widget::label(format!(
+                    "Count {BONUS_SHEEP} more sheep in {BONUS_SECONDS:.0} seconds to double the money from your next modifier."
+                )),
+                widget::label("Fail and you get nothing."),
+                widget::button("Accept", accept_double_or_nothing),
+                widget::button("Decline", decline_double_or_nothing),
+            ],
+        )],
+    ));
+}
+
+fn accept_double_or_nothing(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    offer: Single<Entity, With<DoubleOrNothingOffer>>,
+    round_stats: Res<RoundStats>,
+    mut double_or_nothing: ResMut<DoubleOrNothing>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    commands.entity(*offer).despawn();
+    *double_or_nothing = DoubleOrNothing::Active {
+        timer: Timer::from_seconds(BONUS_SECONDS, TimerMode::Once),
+        counted_at_start: round_stats.sheep_counted,
+    };
+    next_pause.set(Pause(false));
+    commands.spawn((
+        Name::new("Bonus Challenge HUD"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            top: px(16),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+        DespawnOnExit(GamePhase::Herding),
+        children![(widget::hud_label(""), BonusChallengeText)],
+    ));
+}
+
+fn decline_double_or_nothing(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    offer: Single<Entity, With<DoubleOrNothingOffer>>,
+    mut double_or_nothing: ResMut<DoubleOrNothing>,
+    mut game_state: ResMut<GameState>,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    commands.entity(*offer).despawn();
+    *double_or_nothing = DoubleOrNothing::Declined;
+    next_pause.set(Pause(false));
+    // Let the points check end the round now that it's no longer on hold.
+    game_state.set_changed();
+}
+
+fn tick_bonus_challenge(
+    time: Res<Time>,
+    round_stats: Res<RoundStats>,
+    mut double_or_nothing: ResMut<DoubleOrNothing>,
+    mut game_state: ResMut<GameState>,
+) {
+    let DoubleOrNothing::Active {
+        timer,
+        counted_at_start,
+    } = &mut *double_or_nothing
+    else {
+        return;
+    };
+    timer.tick(time.delta());
+    if round_stats.sheep_counted - *counted_at_start >= BONUS_SHEEP {
+        *double_or_nothing = DoubleOrNothing::Won;
+        game_state.double_or_nothing_won += 1;
+    } else if timer.is_finished() {
+        *double_or_nothing = DoubleOrNothing::Lost;
+        game_state.double_or_nothing_lost += 1;
+    }
+}
+
+fn update_bonus_challenge_ui(
+    round_stats: Res<RoundStats>,
+    double_or_nothing: Res<DoubleOrNothing>,
+    mut label: Query<&mut Text, With<BonusChallengeText>>,
+) {
+    let DoubleOrNothing::Active {
+        timer,
+        counted_at_start,
+    } = &*double_or_nothing
+    else {
+        return;
+    };
+    let counted = round_stats.sheep_counted - counted_at_start;
+    let seconds = timer.remaining_secs().ceil() as u32;
+    if let Ok(mut text) = label.single_mut() {
+        text.0 = format!("Double or Nothing: {counted}/{BONUS_SHEEP} sheep, {seconds}s left");
+    }
+}
@@ -0,0 +1,187 @@
+//! A grade for how quickly each round's points target was met, on top of the plain pass or fail.
+//!
+//! A round's pace is its points per second, divided by the pace needed to just meet the target in
+//! the time given. So a pace of 1 means the target was met on the final second, and 2 means it was
+//! met halfway through. The pace is then graded against [`EfficiencyConfig`]'s thresholds, which
+//! are lowered while harder modifiers are active.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::{config::EfficiencyConfig, modifiers::ModifierDifficulty, state::GameState},
+    theme::{palette::LABEL_TEXT, widget},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+pub enum EfficiencyGrade {
+    C,
+    B,
+    A,
+    S,
+}
+
+impl EfficiencyGrade {
+    pub fn letter(&self) -> &'static str {
+        match self {
+            EfficiencyGrade::C => "C",
+            EfficiencyGrade::B => "B",
+            EfficiencyGrade::A => "A",
+            EfficiencyGrade::S => "S",
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            EfficiencyGrade::C => Color::srgb(0.7, 0.7, 0.7),
+            EfficiencyGrade::B => Color::srgb(0.55, 0.85, 0.95),
+            EfficiencyGrade::A => Color::srgb(0.55, 0.95, 0.55),
+            EfficiencyGrade::S => Color::srgb(1.0, 0.82, 0.2),
+        }
+    }
+}
+
+/// How efficiently a round was won.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct EfficiencyRating {
+    pub points_per_second: f32,
+    /// Points per second relative to the pace needed to just meet the target.
+    pub pace: f32,
+    pub grade: EfficiencyGrade,
+}
+
+/// The moment a round's points target was first met. Herding on afterwards, during bonus time or
+/// with rounds that don't end automatically, doesn't change the round's rating.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TargetMet {
+    /// Seconds into the round.
+    pub seconds: f32,
+    pub points: u32,
+}
+
+/// Rates a round from when its target was met. `None` if no time had passed.
+pub fn rate_round(
+    state: &GameState,
+    config: &EfficiencyConfig,
+    target_met: TargetMet,
+) -> Option<EfficiencyRating> {
+    let round_seconds = state.countdown.duration().as_secs_f32();
+    if target_met.seconds <= 0.0 || round_seconds <= 0.0 || state.point_target == 0 {
+        return None;
+    }
+    let points_per_second = target_met.points as f32 / target_met.seconds;
+    let par = state.point_target as f32 / round_seconds;
+    let pace = points_per_second / par;
+
+    let difficulty: u32 = state
+        .active_modifiers
+        .iter()
+        .map(|active| match active.modifier.difficulty() {
+            ModifierDifficulty::Easy => 0,
+            ModifierDifficulty::Medium => 1,
+            ModifierDifficulty::Hard => 2,
+        })
+        .sum();
+    let scale = 1.0 + config.slack_per_difficulty * difficulty as f32;
+    let grade = if pace >= config.s_pace / scale {
+        EfficiencyGrade::S
+    } else if pace >= config.a_pace / scale {
+        EfficiencyGrade::A
+    } else if pace >= config.b_pace / scale {
+        EfficiencyGrade::B
+    } else {
+        EfficiencyGrade::C
+    };
+    Some(EfficiencyRating {
+        points_per_second,
+        pace,
+        grade,
+    })
+}
+
+/// The grade letter on a colored badge, followed by `text`.
+pub fn grade_badge(rating: EfficiencyRating, text: impl Into<String>) -> impl Bundle {
+    (
+        Name::new("Efficiency Grade"),
+        Node {
+            align_items: AlignItems::Center,
+            column_gap: px(12),
+            ..default()
+        },
+        children![
+            (
+                Node {
+                    width: px(44),
+                    height: px(44),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border_radius: BorderRadius::all(px(8)),
+                    ..default()
+                },
+                BackgroundColor(rating.grade.color()),
+                children![(
+                    Text::new(rating.grade.letter()),
+                    TextFont::from_font_size(30.0),
+                    TextColor(LABEL_TEXT),
+                )],
+            ),
+            widget::label(text),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_state(points: u32) -> GameState {
+        GameState {
+            points,
+            point_target: 10,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn meeting_the_target_on_the_final_second_is_par() {
+        let state = round_state(10);
+        let seconds = state.countdown.duration().as_secs_f32();
+        let rating = rate_round(
+            &state,
+            &EfficiencyConfig::default(),
+            TargetMet {
+                seconds,
+                points: 10,
+            },
+        )
+        .unwrap();
+        assert!((rating.pace - 1.0).abs() < 1e-4);
+        assert_eq!(rating.grade, EfficiencyGrade::C);
+    }
+
+    #[test]
+    fn rating_ignores_herding_after_the_target_was_met() {
+        let config = EfficiencyConfig::default();
+        let round_seconds = round_state(0).countdown.duration().as_secs_f32();
+        let target_met = TargetMet {
+            seconds: round_seconds / 3.0,
+            points: 10,
+        };
+        // However many points are scored after, the rating is the same.
+        let at_target = rate_round(&round_state(10), &config, target_met);
+        let much_later = rate_round(&round_state(40), &config, target_met);
+        assert_eq!(at_target, much_later);
+        assert_eq!(at_target.unwrap().grade, EfficiencyGrade::S);
+    }
+
+    #[test]
+    fn no_rating_without_time_passing() {
+        let target_met = TargetMet {
+            seconds: 0.0,
+            points: 10,
+        };
+        assert_eq!(
+            rate_round(&round_state(10), &EfficiencyConfig::default(), target_met),
+            None
+        );
+    }
+}
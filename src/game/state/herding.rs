@@ -1,50 +1,345 @@
-use bevy::prelude::*;
-use rand::Rng;
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use rand::{Rng, seq::SliceRandom};
 
 use crate::{
-    AppSystems, PausableSystems,
+    AppSystems, PausableSystems, Pause,
     audio::BgmConfig,
     game::{
         camera::CameraTarget,
-        level::{LevelBounds, spawn_level},
+        config::GameConfig,
+        level::{GoalTextMessage, LevelBounds, level_ready, spawn_level},
         modifiers::Modifier,
         movement::{HopMovementController, SpaceMovementController, SphereMovementController},
-        player::{PlayerAssets, player},
-        sheep::{SheepAssets, SheepColor, sheep},
-        state::{GamePhase, GameState, RoundStats, shop::items::Charm},
+        player::{Player, PlayerAssets, player},
+        rng::GameRng,
+        sheep::{Sheep, SheepAssets, SheepColor, sheep},
+        state::{
+            GameMode, GamePhase, GameState, RoundCompleted, RoundFailed, RoundStats,
+            double_or_nothing::{
+                BONUS_SHEEP, DoubleOrNothing, offer_double_or_nothing, round_timer_running,
+            },
+            efficiency::{self, TargetMet},
+            shop::items::{Charm, OVERFLOW_MAX_COINS, OVERFLOW_POINTS_PER_COIN},
+            zen_mode,
+        },
     },
+    menus::Menu,
+    performance::PerformanceBudget,
     post_processing::DreamCloudVignette,
     screens::Screen,
+    settings::{AccessibilityConfig, GameplaySettings},
     theme::prelude::*,
 };
 
+/// Chance for a white sheep to stray and spawn black instead.
+pub const BLACK_SHEEP_CHANCE: f64 = 0.05;
+/// [`BLACK_SHEEP_CHANCE`] with [`Charm::Ink`].
+pub const INK_BLACK_SHEEP_CHANCE: f64 = 0.1;
+/// Chance for a white sheep that didn't turn black to spawn as a contrarian instead.
+const CONTRARIAN_CHANCE: f64 = 0.04;
+const MONEY_PULSE_SECS: f32 = 0.4;
+/// How much bigger the money label grows at the start of a pulse.
+const MONEY_PULSE_SCALE: f32 = 0.3;
+const STAMINA_BAR_COLOR: Color = Color::srgb(0.35, 0.75, 0.4);
+/// The stamina meter turns this color while there isn't enough stamina left to bark.
+const STAMINA_BAR_LOW_COLOR: Color = Color::srgb(0.85, 0.4, 0.3);
+
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(GamePhase::Herding), (on_herding, spawn_level));
+    app.init_resource::<EndRoundRequested>();
+    app.add_systems(
+        OnEnter(GamePhase::Herding),
+        (queue_round_start, spawn_level),
+    );
+    app.add_systems(
+        Update,
+        on_herding.run_if(
+            in_state(GamePhase::Herding)
+                .and(resource_exists::<RoundStartPending>)
+                .and(level_ready),
+        ),
+    );
     app.add_systems(
         Update,
         tick_countdown
             .in_set(AppSystems::TickTimers)
             .in_set(PausableSystems)
+            .run_if(
+                in_state(GamePhase::Herding)
+                    .and(round_timer_running)
+                    // The clock stops during bonus time, so Early Bird pays out for the time that
+                    // was left when the target was met.
+                    .and(not(resource_exists::<BonusTime>))
+                    .and(not(resource_exists::<RoundStartPending>))
+                    .and(not(zen_mode)),
+            ),
+    );
+    app.add_systems(
+        Update,
+        release_reserve_sheep
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::Herding).and(resource_exists::<ReserveSheep>)),
+    );
+    app.add_systems(OnExit(GamePhase::Herding), |mut commands: Commands| {
+        commands.remove_resource::<ReserveSheep>();
+    });
+    app.add_systems(
+        Update,
+        respawn_zen_flock.run_if(
+            in_state(GamePhase::Herding)
+                .and(zen_mode)
+                .and(not(resource_exists::<RoundStartPending>))
+                .and(not(resource_exists::<ReserveSheep>)),
+        ),
+    );
+    app.add_systems(
+        Update,
+        tick_bonus_time
+            .in_set(AppSystems::TickTimers)
+            .in_set(PausableSystems)
+            .run_if(in_state(GamePhase::Herding).and(resource_exists::<BonusTime>)),
+    );
+    app.add_systems(
+        Update,
+        (update_herding_ui, update_money_text, update_stamina_bar)
             .run_if(in_state(GamePhase::Herding)),
     );
     app.add_systems(
         Update,
-        update_herding_ui.run_if(in_state(GamePhase::Herding)),
+        request_end_round.run_if(
+            in_state(GamePhase::Herding)
+                .and(input_just_pressed(KeyCode::Enter))
+                .and(in_state(Menu::None)),
+        ),
     );
+    add_check_points_goal(app);
+}
+
+/// Split out so tests can run the round end on its own, with the same run condition as the game.
+fn add_check_points_goal(app: &mut App) {
     app.add_systems(
         Update,
-        check_points_goal.run_if(resource_changed::<GameState>),
+        check_points_goal.run_if(
+            in_state(GamePhase::Herding)
+                .and(resource_changed::<GameState>.or(resource_changed::<EndRoundRequested>))
+                .and(not(zen_mode)),
+        ),
     );
 }
 
+/// Set when the player chooses to end the round early, once the points target is met. Only
+/// needed when [`GameplaySettings::auto_end_round`] is off.
+#[derive(Resource, Debug, Default)]
+pub struct EndRoundRequested(pub bool);
+
+/// Present while the round keeps going after the points target was met, for
+/// [`RoundConfig::bonus_time_seconds`](crate::game::config::RoundConfig::bonus_time_seconds).
+/// Ending the round early skips the rest of it. The round's countdown is paused meanwhile.
+#[derive(Resource, Debug)]
+pub struct BonusTime {
+    timer: Timer,
+    /// [`RoundStats::sheep_counted`] when the bonus time started.
+    counted_before: u16,
+}
+
+fn tick_bonus_time(
+    time: Res<Time>,
+    mut bonus_time: ResMut<BonusTime>,
+    mut end_round: ResMut<EndRoundRequested>,
+) {
+    if bonus_time.timer.tick(time.delta()).just_finished() {
+        end_round.0 = true;
+    }
+}
+
 pub fn tick_countdown(
     time: Res<Time>,
     mut state: ResMut<GameState>,
+    round_stats: Res<RoundStats>,
+    mut end_round: ResMut<EndRoundRequested>,
     mut next_state: ResMut<NextState<Screen>>,
+    mut round_failed: MessageWriter<RoundFailed>,
 ) {
     state.countdown.tick(time.delta());
     if state.countdown.just_finished() {
-        next_state.set(Screen::GameOver);
+        if state.points >= state.point_target {
+            // The player chose to keep herding after reaching the target, so time running out
+            // just ends the round.
+            end_round.0 = true;
+        } else {
+            round_failed.write(RoundFailed {
+                round: state.completed_rounds + 1,
+                points: state.points,
+                point_target: state.point_target,
+                sheep_lost: round_stats.sheep_abducted,
+            });
+            next_state.set(Screen::GameOver);
+        }
+    }
+}
+
+/// Present from entering [`GamePhase::Herding`] until the level has finished spawning and the
+/// sheep and player have been placed in it.
+#[derive(Resource, Debug)]
+struct RoundStartPending;
+
+fn queue_round_start(mut commands: Commands) {
+    commands.insert_resource(RoundStartPending);
+}
+
+/// In zen mode rounds never end, so once the whole flock has been counted a fresh one is brought
+/// in. Only the sheep are respawned; the level and the player stay put.
+fn respawn_zen_flock(
+    mut commands: Commands,
+    sheep_query: Query<(), With<Sheep>>,
+    sheep_assets: Res<SheepAssets>,
+    game_state: Res<GameState>,
+    bounds: Res<LevelBounds>,
+    budget: Res<PerformanceBudget>,
+    mut rng: ResMut<GameRng>,
+) {
+    if sheep_query.is_empty() {
+        spawn_flock(
+            &mut commands,
+            &sheep_assets,
+            &game_state,
+            &bounds,
+            &budget,
+            &mut rng,
+        );
+    }
+}
+
+/// Ends the round on Enter, once the points target is met. An early press is ignored rather than
+/// held onto, so it can't end the round the moment the target is reached later.
+fn request_end_round(
+    input: Res<ButtonInput<KeyCode>>,
+    game_state: Res<GameState>,
+    mut end_round: ResMut<EndRoundRequested>,
+) {
+    // Alt+Enter toggles fullscreen instead.
+    if input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]) {
+        return;
+    }
+    if game_state.points >= game_state.point_target {
+        end_round.0 = true;
+    }
+}
+
+/// Sheep left over once the performance budget's [`max_sheep`](PerformanceBudget::max_sheep) are on
+/// the field. They join the round one by one as other sheep leave it, so the whole flock still gets
+/// herded.
+#[derive(Resource, Debug, Default)]
+pub struct ReserveSheep(Vec<SheepColor>);
+
+impl ReserveSheep {
+    pub fn count_of(&self, color: SheepColor) -> usize {
+        self.0.iter().filter(|reserve| **reserve == color).count()
+    }
+}
+
+fn release_reserve_sheep(
+    mut commands: Commands,
+    mut reserve: ResMut<ReserveSheep>,
+    sheep_assets: Res<SheepAssets>,
+    game_state: Res<GameState>,
+    bounds: Res<LevelBounds>,
+    budget: Res<PerformanceBudget>,
+    sheep_query: Query<(), With<Sheep>>,
+) {
+    let max_sheep = budget.max_sheep.unwrap_or(usize::MAX);
+    let free = max_sheep.saturating_sub(sheep_query.iter().count());
+    let rng = &mut rand::rng();
+    for _ in 0..free {
+        let Some(color) = reserve.0.pop() else {
+            break;
+        };
+        spawn_round_sheep(
+            &mut commands,
+            &sheep_assets,
+            &game_state,
+            &bounds,
+            color,
+            rng,
+        );
+    }
+    if reserve.0.is_empty() {
+        commands.remove_resource::<ReserveSheep>();
+    }
+}
+
+/// Spawns the round's flock, holding back any sheep over the performance budget as
+/// [`ReserveSheep`]. Which sheep stray is decided by the run's seed, but where they land isn't.
+fn spawn_flock(
+    commands: &mut Commands,
+    sheep_assets: &SheepAssets,
+    game_state: &GameState,
+    bounds: &LevelBounds,
+    budget: &PerformanceBudget,
+    game_rng: &mut GameRng,
+) {
+    let mut sheep_colors = build_sheep_colors(game_state);
+    roll_stray_colors(&mut sheep_colors, game_state, &mut **game_rng);
+    let rng = &mut rand::rng();
+    if game_state.is_charm_active(Charm::GoldenSheep) {
+        sheep_colors.push(SheepColor::Gold);
+    }
+    if let Some(reserve) = hold_back_reserve(&mut sheep_colors, budget.max_sheep, rng) {
+        commands.insert_resource(ReserveSheep(reserve));
+    }
+
+    for color in sheep_colors {
+        spawn_round_sheep(commands, sheep_assets, game_state, bounds, color, rng);
+    }
+}
+
+/// Takes the sheep over `max_sheep` out of `colors` to wait in reserve, picked at random so no
+/// color is more likely than another to be held back.
+///
+/// This doesn't draw from [`GameRng`], since the budget depends on the machine and a seeded run
+/// should come out the same on any of them.
+fn hold_back_reserve(
+    colors: &mut Vec<SheepColor>,
+    max_sheep: Option<usize>,
+    rng: &mut impl Rng,
+) -> Option<Vec<SheepColor>> {
+    let max_sheep = max_sheep.filter(|max_sheep| colors.len() > *max_sheep)?;
+    colors.shuffle(rng);
+    Some(colors.split_off(max_sheep))
+}
+
+/// Spawns a sheep for the current round somewhere random in the level.
+fn spawn_round_sheep(
+    commands: &mut Commands,
+    sheep_assets: &SheepAssets,
+    game_state: &GameState,
+    bounds: &LevelBounds,
+    color: SheepColor,
+    rng: &mut impl Rng,
+) {
+    let x = rng.random_range(bounds.min.x..=bounds.max.x);
+    let z = rng.random_range(bounds.min.y..=bounds.max.y);
+    let sleeping = game_state.is_modifier_active(Modifier::Night);
+    let entity = sheep(
+        commands,
+        sheep_assets,
+        Vec3::new(x, 0.0, z),
+        game_state,
+        color,
+        sleeping,
+    );
+    commands
+        .entity(entity)
+        .insert(DespawnOnExit(GamePhase::Herding));
+}
+
+fn request_end_round_on_click(
+    _: On<Pointer<Click>>,
+    game_state: Res<GameState>,
+    mut end_round: ResMut<EndRoundRequested>,
+) {
+    if game_state.points >= game_state.point_target {
+        end_round.0 = true;
     }
 }
 
@@ -54,53 +349,51 @@ pub fn on_herding(
     player_assets: Res<PlayerAssets>,
     mut game_state: ResMut<GameState>,
     mut round_stats: ResMut<RoundStats>,
+    mut end_round: ResMut<EndRoundRequested>,
     bounds: Res<LevelBounds>,
     mut camera_target: ResMut<CameraTarget>,
     mut vignette: ResMut<DreamCloudVignette>,
     mut bgm_config: ResMut<BgmConfig>,
+    budget: Res<PerformanceBudget>,
+    game_mode: Res<GameMode>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
 ) {
-    *round_stats = RoundStats::default();
+    commands.remove_resource::<RoundStartPending>();
+    commands.remove_resource::<BonusTime>();
+    commands.remove_resource::<ReserveSheep>();
+    commands.remove_resource::<TargetMet>();
+    *round_stats = RoundStats {
+        money_at_start: game_state.money,
+        ..default()
+    };
+    end_round.0 = false;
 
     bgm_config.base_enabled = true;
     bgm_config.extra_enabled = true;
-    bgm_config.percussion_enabled = true;
+    // Leave out the driving percussion in zen mode, where there's no clock to race.
+    bgm_config.percussion_enabled = *game_mode != GameMode::Zen;
 
     let total_sheep = game_state.sheep_count as usize;
     if total_sheep == 0 {
         return;
     }
 
-    let mut sheep_colors = build_sheep_colors(&game_state);
-    let rng = &mut rand::rng();
-
-    if game_state.is_charm_active(Charm::GoldenSheep) {
-        sheep_colors.push(SheepColor::Gold);
-    }
-    let sleeping = game_state.is_modifier_active(Modifier::Night);
-
-    // spawn sheep
-    for color in sheep_colors {
-        let x = rng.random_range(bounds.min.x..=bounds.max.x);
-        let z = rng.random_range(bounds.min.y..=bounds.max.y);
-        let pos = Vec3::new(x, 0.0, z);
-        let entity = sheep(
-            &mut commands,
-            &sheep_assets,
-            pos,
-            &game_state,
-            color,
-            sleeping,
-        );
-        commands
-            .entity(entity)
-            .insert(DespawnOnExit(GamePhase::Herding));
-    }
+    spawn_flock(
+        &mut commands,
+        &sheep_assets,
+        &game_state,
+        &bounds,
+        &budget,
+        &mut rng,
+    );
 
     // spawn player
     let player = commands
         .spawn((
             player(
                 &player_assets,
+                &config.player,
                 game_state.player_bark_radius,
                 game_state.is_modifier_active(Modifier::DogSphere),
             ),
@@ -134,10 +427,12 @@ pub fn on_herding(
         vignette.target_coverage = 0.2;
     }
 
-    game_state.reset_timer();
+    game_state.reset_timer(&config.round);
 }
 
-fn build_sheep_colors(game_state: &GameState) -> Vec<SheepColor> {
+/// The colors of the sheep to spawn next round, before any white sheep stray to another color in
+/// [`roll_stray_colors`]. Colored sheep come first, then white ones pad out the flock.
+pub(super) fn build_sheep_colors(game_state: &GameState) -> Vec<SheepColor> {
     let total_sheep = if game_state.is_charm_active(Charm::HalfTimeDoubleSheep) {
         game_state.sheep_count as usize * 2
     } else {
@@ -150,6 +445,7 @@ fn build_sheep_colors(game_state: &GameState) -> Vec<SheepColor> {
         (SheepColor::Red, game_state.red_sheep_count as usize),
         (SheepColor::Black, game_state.black_sheep_count as usize),
         (SheepColor::Gold, game_state.gold_sheep_count as usize),
+        (SheepColor::Green, game_state.green_sheep_count as usize),
     ];
 
     for (color, mut count) in colored_counts {
@@ -168,10 +464,198 @@ fn build_sheep_colors(game_state: &GameState) -> Vec<SheepColor> {
     colors
 }
 
-fn check_points_goal(game_state: Res<GameState>, mut next_state: ResMut<NextState<GamePhase>>) {
-    if game_state.points >= game_state.point_target {
-        next_state.set(GamePhase::ModifierChoice);
+/// Turns some white sheep black, or contrarian, at random. Done once before spawning so every sheep
+/// spawns with its final color.
+fn roll_stray_colors(colors: &mut [SheepColor], game_state: &GameState, rng: &mut impl Rng) {
+    let black_chance = black_sheep_chance(game_state);
+    for color in colors {
+        if *color != SheepColor::White {
+            continue;
+        }
+        if rng.random_bool(black_chance) {
+            *color = SheepColor::Black;
+        } else if rng.random_bool(CONTRARIAN_CHANCE) {
+            *color = SheepColor::Contrarian;
+        }
+    }
+}
+
+/// Like [`roll_stray_colors`], but turns the expected number of white sheep black or contrarian
+/// instead of rolling for each one, so estimates of the flock stay the same between calls.
+pub(super) fn expect_stray_colors(colors: &mut [SheepColor], game_state: &GameState) {
+    let white_count = colors
+        .iter()
+        .filter(|color| **color == SheepColor::White)
+        .count() as f64;
+    let black_chance = black_sheep_chance(game_state);
+    let black_count = (white_count * black_chance).round() as usize;
+    let contrarian_count =
+        (white_count * (1.0 - black_chance) * CONTRARIAN_CHANCE).round() as usize;
+    let strays = std::iter::repeat_n(SheepColor::Black, black_count).chain(std::iter::repeat_n(
+        SheepColor::Contrarian,
+        contrarian_count,
+    ));
+    for (color, stray) in colors
+        .iter_mut()
+        .filter(|color| **color == SheepColor::White)
+        .zip(strays)
+    {
+        *color = stray;
+    }
+}
+
+fn black_sheep_chance(game_state: &GameState) -> f64 {
+    if game_state.is_charm_active(Charm::Ink) {
+        INK_BLACK_SHEEP_CHANCE
+    } else {
+        BLACK_SHEEP_CHANCE
+    }
+}
+
+fn check_points_goal(
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    mut next_state: ResMut<NextState<GamePhase>>,
+    sheep_query: Query<(Entity, &Sheep)>,
+    mut writer: MessageWriter<GoalTextMessage>,
+    settings: Res<GameplaySettings>,
+    mut double_or_nothing: ResMut<DoubleOrNothing>,
+    mut next_pause: ResMut<NextState<Pause>>,
+    end_round: Res<EndRoundRequested>,
+    round_stats: Res<RoundStats>,
+    mut round_completed: MessageWriter<RoundCompleted>,
+    config: Res<GameConfig>,
+    bonus_time: Option<Res<BonusTime>>,
+    reserve: Option<Res<ReserveSheep>>,
+    target_met: Option<Res<TargetMet>>,
+) {
+    if game_state.points < game_state.point_target {
+        return;
+    }
+    let target_met = match target_met {
+        Some(target_met) => *target_met,
+        None => {
+            let target_met = TargetMet {
+                seconds: game_state.countdown.elapsed_secs(),
+                points: game_state.points,
+            };
+            commands.insert_resource(target_met);
+            target_met
+        }
+    };
+    if double_or_nothing.holds_round_end() {
+        return;
+    }
+    if !settings.auto_end_round && !end_round.0 {
+        return;
+    }
+
+    if settings.double_or_nothing && matches!(*double_or_nothing, DoubleOrNothing::NotOffered) {
+        let sheep_left = sheep_query
+            .iter()
+            .filter(|(_, sheep)| !sheep.is_being_abducted())
+            .count()
+            + reserve.as_ref().map_or(0, |reserve| reserve.0.len());
+        // Only offer the gamble when it can actually be won.
+        if sheep_left >= BONUS_SHEEP as usize {
+            offer_double_or_nothing(&mut commands, &mut double_or_nothing, &mut next_pause);
+            return;
+        }
+    }
+
+    if let Some(bonus_time) = bonus_time {
+        if !end_round.0 {
+            return;
+        }
+        let bonus = round_stats
+            .sheep_counted
+            .saturating_sub(bonus_time.counted_before);
+        if bonus > 0 {
+            game_state.money += bonus as u32;
+            writer.write(GoalTextMessage {
+                text: format!("+{bonus} bonus"),
+                color: Some(Color::srgb(1.0, 0.82, 0.2)),
+                position: None,
+            });
+        }
+        commands.remove_resource::<BonusTime>();
+    } else if !end_round.0 && config.round.bonus_time_seconds > 0.0 {
+        commands.insert_resource(BonusTime {
+            timer: Timer::from_seconds(config.round.bonus_time_seconds, TimerMode::Once),
+            counted_before: round_stats.sheep_counted,
+        });
+        writer.write(GoalTextMessage {
+            text: "Target met! Bonus time".to_string(),
+            color: None,
+            position: None,
+        });
+        return;
+    }
+
+    if game_state.is_charm_active(Charm::Vacuum) {
+        // Sheep already being carried off by a UFO are lost, so they can't be swept up.
+        for (entity, sheep) in &sheep_query {
+            if *sheep.color() != SheepColor::Gold || sheep.is_being_abducted() {
+                continue;
+            }
+            game_state.money += 1;
+            writer.write(GoalTextMessage {
+                text: "+1 gold".to_string(),
+                color: Some(Color::srgb(1.0, 0.82, 0.2)),
+                position: None,
+            });
+            commands.entity(entity).despawn();
+        }
+        let reserve_gold = reserve
+            .as_ref()
+            .map_or(0, |reserve| reserve.count_of(SheepColor::Gold));
+        for _ in 0..reserve_gold {
+            game_state.money += 1;
+            writer.write(GoalTextMessage {
+                text: "+1 gold".to_string(),
+                color: Some(Color::srgb(1.0, 0.82, 0.2)),
+                position: None,
+            });
+        }
+    }
+
+    if game_state.is_charm_active(Charm::TimeBonus) {
+        // A round that ran out of time has nothing left to convert.
+        let seconds_left = game_state.countdown.remaining_secs() as u32;
+        let coins = seconds_left / config.shop.time_bonus_seconds_per_coin;
+        if coins > 0 {
+            game_state.money += coins;
+            writer.write(GoalTextMessage {
+                text: format!("Early Bird +{coins}"),
+                color: Some(Color::srgb(1.0, 0.82, 0.2)),
+                position: None,
+            });
+        }
     }
+
+    if game_state.is_charm_active(Charm::Overflow) {
+        let surplus = game_state.points.saturating_sub(game_state.point_target);
+        let coins = (surplus / OVERFLOW_POINTS_PER_COIN).min(OVERFLOW_MAX_COINS);
+        if coins > 0 {
+            game_state.money += coins;
+            writer.write(GoalTextMessage {
+                text: format!("Overflow +{coins}"),
+                color: Some(Color::srgb(1.0, 0.82, 0.2)),
+                position: None,
+            });
+        }
+    }
+
+    let rating = efficiency::rate_round(&game_state, &config.efficiency, target_met);
+    game_state.record_round_rating(rating);
+
+    round_completed.write(RoundCompleted {
+        round: game_state.completed_rounds + 1,
+        points: game_state.points,
+        sheep_lost: round_stats.sheep_abducted,
+    });
+    // `DetectChangesMut::set_if_neq` would shadow this on `ResMut`.
+    NextState::set_if_neq(&mut next_state, GamePhase::RoundSummary);
 }
 
 fn draw_herding_ui(commands: &mut Commands) {
@@ -191,7 +675,17 @@ fn draw_herding_ui(commands: &mut Commands) {
             },
             Pickable::IGNORE,
             children![
-                (widget::hud_label("Time: 0"), HerdingTimerText),
+                (
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: px(6),
+                        ..default()
+                    },
+                    children![
+                        (widget::hud_label("Time: 0"), HerdingTimerText),
+                        widget::hud_bar(StaminaBarFill, STAMINA_BAR_COLOR),
+                    ],
+                ),
                 (
                     Node {
                         align_items: AlignItems::FlexEnd,
@@ -202,6 +696,26 @@ fn draw_herding_ui(commands: &mut Commands) {
                     children![
                         (widget::hud_label("Points: 0"), HerdingPointsText),
                         (widget::hud_label("Target: 0"), HerdingTargetText),
+                        (
+                            widget::hud_label("Money: 0"),
+                            HerdingMoneyText {
+                                shown: None,
+                                pulse: Timer::from_seconds(MONEY_PULSE_SECS, TimerMode::Once),
+                            },
+                        ),
+                        (widget::hud_label(""), BonusTimeText),
+                        (
+                            Name::new("End Round"),
+                            Node {
+                                display: Display::None,
+                                ..default()
+                            },
+                            EndRoundButton,
+                            children![widget::button_medium(
+                                "End round (Enter)",
+                                request_end_round_on_click
+                            )],
+                        ),
                     ],
                 ),
             ],
@@ -218,12 +732,38 @@ pub struct HerdingPointsText;
 #[derive(Component)]
 pub struct HerdingTargetText;
 
+#[derive(Component)]
+pub struct BonusTimeText;
+
+/// Pulses whenever the money goes up mid-round, e.g. from counting a gold sheep.
+#[derive(Component)]
+pub struct HerdingMoneyText {
+    /// The amount currently on the label, so increases can be spotted.
+    shown: Option<u32>,
+    pulse: Timer,
+}
+
+/// The inner part of the stamina meter, sized to the player's [`Player::stamina`].
+#[derive(Component)]
+pub struct StaminaBarFill;
+
+/// Only shown once the target is met when rounds don't end automatically, or during bonus time.
+#[derive(Component)]
+pub struct EndRoundButton;
+
 pub fn update_herding_ui(
     state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    settings: Res<GameplaySettings>,
+    bonus_time: Option<Res<BonusTime>>,
+    mut end_round_button: Query<&mut Node, With<EndRoundButton>>,
+    // Plain queries rather than `Single`, since the HUD may not exist yet (or any more) while the
+    // phase is changing.
     mut labels: ParamSet<(
-        Single<&mut Text, With<HerdingTimerText>>,
-        Single<&mut Text, With<HerdingPointsText>>,
-        Single<&mut Text, With<HerdingTargetText>>,
+        Query<&mut Text, With<HerdingTimerText>>,
+        Query<&mut Text, With<HerdingPointsText>>,
+        Query<&mut Text, With<HerdingTargetText>>,
+        Query<&mut Text, With<BonusTimeText>>,
     )>,
 ) {
     let remaining = state
@@ -231,7 +771,247 @@ pub fn update_herding_ui(
         .duration()
         .saturating_sub(state.countdown.elapsed());
     let seconds = remaining.as_secs_f32().ceil().max(0.0) as u32;
-    labels.p0().0 = format!("Time: {seconds}");
-    labels.p1().0 = format!("Points: {}", state.points);
-    labels.p2().0 = format!("Target: {}", state.point_target);
+    let zen = *game_mode == GameMode::Zen;
+    if let Ok(mut text) = labels.p0().single_mut() {
+        text.0 = if zen {
+            "Zen".to_string()
+        } else {
+            format!("Time: {seconds}")
+        };
+    }
+    if let Ok(mut text) = labels.p1().single_mut() {
+        text.0 = format!("Points: {}", state.points);
+    }
+    if let Ok(mut text) = labels.p2().single_mut() {
+        text.0 = if zen {
+            String::new()
+        } else {
+            format!("Target: {}", state.point_target)
+        };
+    }
+
+    if let Ok(mut text) = labels.p3().single_mut() {
+        text.0 = match &bonus_time {
+            Some(bonus_time) => format!(
+                "Target met! Bonus time: {}",
+                bonus_time.timer.remaining_secs().ceil() as u32
+            ),
+            None => String::new(),
+        };
+    }
+
+    let can_end_round = !zen
+        && (bonus_time.is_some() || !settings.auto_end_round && state.points >= state.point_target);
+    if let Ok(mut node) = end_round_button.single_mut() {
+        node.display = if can_end_round {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+fn update_stamina_bar(
+    player: Option<Single<&Player>>,
+    mut fill: Query<(&mut Node, &mut BackgroundColor), With<StaminaBarFill>>,
+) {
+    let (Some(player), Ok((mut node, mut background))) = (player, fill.single_mut()) else {
+        return;
+    };
+    node.width = percent(100.0 * player.stamina / player.max_stamina);
+    background.0 = if player.has_bark_stamina() {
+        STAMINA_BAR_COLOR
+    } else {
+        STAMINA_BAR_LOW_COLOR
+    };
+}
+
+fn update_money_text(
+    time: Res<Time>,
+    state: Res<GameState>,
+    accessibility: Res<AccessibilityConfig>,
+    mut label: Query<(&mut Text, &mut HerdingMoneyText, &mut UiTransform)>,
+) {
+    let Ok((mut text, mut money_text, mut transform)) = label.single_mut() else {
+        return;
+    };
+    if money_text.shown != Some(state.money) {
+        match money_text.shown {
+            Some(shown) if state.money > shown => money_text.pulse.reset(),
+            // Don't pulse when the HUD first appears.
+            None => money_text.pulse.finish(),
+            _ => {}
+        }
+        money_text.shown = Some(state.money);
+        text.0 = format!("Money: {}", state.money);
+    }
+
+    money_text.pulse.tick(time.delta());
+    let pulse = if accessibility.reduced_motion || money_text.pulse.is_finished() {
+        0.0
+    } else {
+        1.0 - money_text.pulse.fraction()
+    };
+    transform.scale = Vec2::splat(1.0 + MONEY_PULSE_SCALE * pulse);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::state::app::StatesPlugin;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    fn spawn_herding_ui(mut commands: Commands) {
+        draw_herding_ui(&mut commands);
+    }
+
+    #[test]
+    fn gold_sheep_isnt_always_held_back() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut gold_on_field = 0;
+        for _ in 0..100 {
+            let mut colors = vec![SheepColor::White; 9];
+            colors.push(SheepColor::Gold);
+            let reserve = hold_back_reserve(&mut colors, Some(5), &mut rng).unwrap();
+            assert_eq!((colors.len(), reserve.len()), (5, 5));
+            if colors.contains(&SheepColor::Gold) {
+                gold_on_field += 1;
+            } else {
+                assert!(reserve.contains(&SheepColor::Gold));
+            }
+        }
+        // Half the flock fits, so the gold sheep should start on the field about half the time.
+        assert!((30..=70).contains(&gold_on_field), "{gold_on_field}");
+
+        let mut colors = vec![SheepColor::Gold; 5];
+        assert!(hold_back_reserve(&mut colors, Some(5), &mut rng).is_none());
+        assert!(hold_back_reserve(&mut colors, None, &mut rng).is_none());
+    }
+
+    #[test]
+    fn strays_follow_the_run_seed() {
+        let game_state = GameState {
+            sheep_count: 200,
+            ..default()
+        };
+        let roll = |seed| {
+            let mut colors = build_sheep_colors(&game_state);
+            roll_stray_colors(&mut colors, &game_state, &mut *GameRng::from_seed(seed));
+            colors
+        };
+        assert_eq!(roll(7), roll(7));
+        assert!((0..10).any(|seed| roll(seed) != roll(7)));
+    }
+
+    #[test]
+    fn expected_strays_are_taken_from_white_sheep() {
+        let mut colors = vec![SheepColor::White; 100];
+        colors[0] = SheepColor::Blue;
+        expect_stray_colors(&mut colors, &GameState::default());
+        let count = |color| colors.iter().filter(|c| **c == color).count();
+        assert_eq!(count(SheepColor::Blue), 1);
+        assert_eq!(count(SheepColor::Black), 5);
+        assert_eq!(count(SheepColor::Contrarian), 4);
+        assert_eq!(count(SheepColor::White), 90);
+    }
+
+    #[derive(Resource, Default)]
+    struct CompletedCount(usize);
+
+    #[test]
+    fn round_end_pays_out_once_and_lets_the_summary_continue() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(Screen::Gameplay)
+            .add_sub_state::<GamePhase>()
+            .init_state::<Pause>()
+            .add_message::<GoalTextMessage>()
+            .add_message::<RoundCompleted>()
+            .init_resource::<GameMode>()
+            .init_resource::<EndRoundRequested>()
+            .init_resource::<RoundStats>()
+            .init_resource::<DoubleOrNothing>()
+            .insert_resource(GameplaySettings {
+                auto_end_round: true,
+                double_or_nothing: false,
+                ..default()
+            });
+        let mut config = GameConfig::default();
+        config.round.bonus_time_seconds = 0.0;
+        app.insert_resource(config);
+        let mut game_state = GameState::default();
+        game_state.charms.push(Charm::TimeBonus);
+        game_state.points = game_state.point_target;
+        let money_before = game_state.money;
+        app.insert_resource(game_state);
+        add_check_points_goal(&mut app);
+        app.init_resource::<CompletedCount>().add_systems(
+            Update,
+            |mut reader: MessageReader<RoundCompleted>, mut count: ResMut<CompletedCount>| {
+                count.0 += reader.read().count();
+            },
+        );
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let state = app.world().resource::<State<GamePhase>>();
+        assert_eq!(*state.get(), GamePhase::RoundSummary);
+        assert_eq!(app.world().resource::<CompletedCount>().0, 1);
+        let money = app.world().resource::<GameState>().money;
+        let config = GameConfig::default();
+        let timer_coins =
+            config.round.timer_seconds as u32 / config.shop.time_bonus_seconds_per_coin;
+        assert_eq!(money - money_before, timer_coins);
+
+        // What the summary's Continue button does.
+        app.world_mut()
+            .resource_mut::<NextState<GamePhase>>()
+            .set(GamePhase::ModifierChoice);
+        app.update();
+        app.update();
+        let state = app.world().resource::<State<GamePhase>>();
+        assert_eq!(*state.get(), GamePhase::ModifierChoice);
+    }
+
+    #[test]
+    fn rapid_phase_changes_dont_panic_the_hud() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(Screen::Gameplay)
+            .add_sub_state::<GamePhase>()
+            .insert_resource(GameState::default())
+            .init_resource::<GameMode>()
+            .init_resource::<GameplaySettings>()
+            .init_resource::<AccessibilityConfig>()
+            .add_systems(OnEnter(GamePhase::Herding), spawn_herding_ui)
+            .add_systems(
+                Update,
+                (update_herding_ui, update_money_text, update_stamina_bar)
+                    .run_if(in_state(GamePhase::Herding)),
+            );
+
+        // Flip in and out of herding every frame, including frames where the HUD is being
+        // despawned or hasn't been spawned yet.
+        for phase in [
+            GamePhase::Shop,
+            GamePhase::Herding,
+            GamePhase::RoundSummary,
+            GamePhase::Herding,
+            GamePhase::Herding,
+            GamePhase::ModifierChoice,
+            GamePhase::Herding,
+        ] {
+            app.world_mut()
+                .resource_mut::<NextState<GamePhase>>()
+                .set(phase);
+            app.update();
+        }
+        app.world_mut()
+            .resource_mut::<NextState<Screen>>()
+            .set(Screen::Title);
+        app.update();
+        app.update();
+    }
 }
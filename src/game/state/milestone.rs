@@ -0,0 +1,162 @@
+//! A celebration for reaching milestone rounds in a long run, shown over the modifier choice. The
+//! milestones come from [`RoundConfig::milestone_rounds`].
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        config::RoundConfig,
+        playtime::{Playtime, format_hms},
+        state::{GamePhase, GameState},
+    },
+    settings::AccessibilityConfig,
+    theme::prelude::*,
+};
+
+const CONFETTI_COUNT: usize = 80;
+const CONFETTI_SECS: f32 = 2.5;
+/// Downward acceleration of confetti, in percent of the window height per second squared.
+const CONFETTI_GRAVITY: f32 = 60.0;
+const CONFETTI_COLORS: [Color; 5] = [
+    Color::srgb(0.95, 0.35, 0.35),
+    Color::srgb(0.3, 0.4, 0.8),
+    Color::srgb(1.0, 0.82, 0.2),
+    Color::srgb(0.55, 0.95, 0.55),
+    Color::srgb(0.6, 0.35, 0.85),
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_message::<MilestoneReached>();
+    app.add_systems(
+        Update,
+        celebrate_milestone.run_if(in_state(GamePhase::ModifierChoice)),
+    );
+    app.add_systems(
+        Update,
+        update_confetti
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Sent when a round ends on one of the milestone rounds.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct MilestoneReached {
+    pub round: u32,
+}
+
+#[derive(Component, Debug)]
+struct MilestoneOverlay;
+
+/// A piece of confetti, positioned in percent of the window.
+#[derive(Component, Debug)]
+struct Confetti {
+    position: Vec2,
+    velocity: Vec2,
+    timer: Timer,
+}
+
+fn celebrate_milestone(
+    mut commands: Commands,
+    mut reached: MessageReader<MilestoneReached>,
+    game_state: Res<GameState>,
+    playtime: Res<Playtime>,
+    accessibility: Res<AccessibilityConfig>,
+) {
+    let Some(milestone) = reached.read().last() else {
+        return;
+    };
+
+    commands.spawn((
+        MilestoneOverlay,
+        widget::ui_root("Milestone Overlay"),
+        GlobalZIndex(3),
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        DespawnOnExit(GamePhase::ModifierChoice),
+        children![(
+            widget::panel(),
+            children![
+                widget::header(format!("Round {} reached!", milestone.round)),
+                widget::label(format!("Flock: {} sheep", game_state.sheep_count)),
+                widget::label(format!("Money: {}", game_state.money)),
+                widget::label(format!(
+                    "Charms: {}/{}",
+                    game_state.charms.len(),
+                    game_state.max_charms
+                )),
+                widget::label(format!("Run time: {}", format_hms(playtime.run))),
+                widget::button("Keep Going", close_milestone_overlay),
+            ]
+        )],
+    ));
+
+    if accessibility.reduced_motion {
+        return;
+    }
+    let rng = &mut rand::rng();
+    for _ in 0..CONFETTI_COUNT {
+        let color = CONFETTI_COLORS[rng.random_range(0..CONFETTI_COLORS.len())];
+        let position = Vec2::new(rng.random_range(35.0..65.0), rng.random_range(40.0..60.0));
+        commands.spawn((
+            Name::new("Confetti"),
+            Confetti {
+                position,
+                velocity: Vec2::new(
+                    rng.random_range(-40.0..40.0),
+                    rng.random_range(-70.0..-20.0),
+                ),
+                timer: Timer::from_seconds(CONFETTI_SECS, TimerMode::Once),
+            },
+            Node {
+                position_type: PositionType::Absolute,
+                left: percent(position.x),
+                top: percent(position.y),
+                width: px(8),
+                height: px(12),
+                ..default()
+            },
+            BackgroundColor(color),
+            GlobalZIndex(4),
+            Pickable::IGNORE,
+            DespawnOnExit(GamePhase::ModifierChoice),
+        ));
+    }
+}
+
+fn update_confetti(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut confetti_query: Query<(Entity, &mut Confetti, &mut Node, &mut BackgroundColor)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut confetti, mut node, mut background) in &mut confetti_query {
+        if confetti.timer.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        confetti.velocity.y += CONFETTI_GRAVITY * dt;
+        let velocity = confetti.velocity;
+        confetti.position += velocity * dt;
+        node.left = percent(confetti.position.x);
+        node.top = percent(confetti.position.y);
+        let alpha = 1.0 - confetti.timer.fraction().powi(3);
+        background.0.set_alpha(alpha);
+    }
+}
+
+fn close_milestone_overlay(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    overlay: Query<Entity, With<MilestoneOverlay>>,
+) {
+    for entity in &overlay {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Whether finishing round `completed_rounds` should be celebrated.
+pub fn is_milestone(config: &RoundConfig, completed_rounds: u32) -> bool {
+    config.milestone_rounds.contains(&completed_rounds)
+}
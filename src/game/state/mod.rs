@@ -2,35 +2,120 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    game::{modifiers::Modifier, state::shop::items::Charm},
+    AppSystems,
+    game::{
+        config::RoundConfig,
+        modifiers::Modifier,
+        playtime::Playtime,
+        sheep::{BLUE_SHEEP_POINTS, SheepColor},
+        state::{efficiency::EfficiencyRating, shop::items::Charm},
+    },
     screens::Screen,
 };
 
+pub mod double_or_nothing;
+pub mod efficiency;
 mod herding;
-mod modifier_choice;
+pub mod milestone;
+pub mod modifier_choice;
+pub mod perfect_herd;
+pub mod ranked;
+mod round_summary;
 pub mod shop;
 
-const TIMER_SECONDS: f32 = 70.0;
+/// How many rounds a dream modifier stays active for after being chosen.
+pub const MODIFIER_DURATION_ROUNDS: u32 = 3;
+/// Money paid at the end of each round for every modifier drafted in [`GameMode::Draft`].
+pub const DRAFTED_MODIFIER_MONEY: u32 = 2;
+/// How many modifiers have to be drafted before a [`GameMode::Draft`] run can start.
+pub const DRAFT_PICKS: usize = 2;
+/// Below this the points target compounds by roughly 12% a round. Above it the increase only grows
+/// logarithmically, so very long runs stay winnable.
+const POINT_TARGET_SOFT_CAP: u32 = 200;
 
 pub(super) fn plugin(app: &mut App) {
     app.add_sub_state::<GamePhase>();
     app.insert_resource(GameState::default());
     app.insert_resource(RoundStats::default());
-    app.add_plugins((herding::plugin, modifier_choice::plugin, shop::plugin));
+    app.init_resource::<RunStatistics>();
+    app.init_resource::<PreviousRun>();
+    app.init_resource::<NewGamePlus>();
+    app.init_resource::<GameMode>();
+    app.add_message::<RoundCompleted>();
+    app.add_message::<RoundFailed>();
+    app.add_plugins((
+        double_or_nothing::plugin,
+        herding::plugin,
+        milestone::plugin,
+        modifier_choice::plugin,
+        perfect_herd::plugin,
+        ranked::plugin,
+        round_summary::plugin,
+        shop::plugin,
+    ));
+    app.add_systems(
+        Update,
+        track_money_earned.run_if(in_state(Screen::Gameplay).and(resource_changed::<GameState>)),
+    );
+    // Not limited to gameplay, so a round that fails as the screen changes to game over is still
+    // logged.
+    app.add_systems(Update, log_round_results.in_set(AppSystems::Update));
     app.add_systems(OnEnter(Screen::Title), reset_run_state);
+    app.add_systems(OnEnter(Screen::GameOver), record_previous_run);
+    app.add_systems(
+        OnExit(Screen::GameOver),
+        start_new_game_plus.run_if(|new_game_plus: Res<NewGamePlus>| new_game_plus.0),
+    );
 }
 
+/// The phases of a round, in order: [`Herding`](Self::Herding) until the points target is met,
+/// then the [`RoundSummary`](Self::RoundSummary) of how it went, then
+/// [`ModifierChoice`](Self::ModifierChoice) to start the next round and pick a modifier, then the
+/// [`Shop`](Self::Shop), and back to herding. Running out of time while herding leaves
+/// gameplay for [`Screen::GameOver`] instead.
 #[derive(SubStates, Clone, Eq, PartialEq, Debug, Hash, Default)]
 #[source(Screen = Screen::Gameplay)]
 pub enum GamePhase {
     #[default]
     Herding,
+    RoundSummary,
     ModifierChoice,
     Shop,
 }
 
+/// How the current run is played, picked from the main menu.
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+pub enum GameMode {
+    #[default]
+    Normal,
+    /// Herding for fun: no timer, points target, UFOs, modifiers or shop. The flock respawns once
+    /// every sheep has been counted.
+    Zen,
+    /// The normal rules, except a sheep has to be calmed by keeping it with the flock before it
+    /// can be counted. Sheep calm down while wandering with at least a few flockmates nearby.
+    Gentle,
+    /// The normal rules, plus modifiers drafted before the run that stay active the whole run.
+    /// Each one pays [`DRAFTED_MODIFIER_MONEY`] every round.
+    Draft,
+    /// The normal rules, with diminishing returns on counting many sheep in a short time, so
+    /// results can be compared fairly. See [`ranked`].
+    Ranked,
+}
+
+/// Run condition for systems that only apply, or never apply, in [`GameMode::Zen`].
+pub fn zen_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Zen
+}
+
+/// Run condition for systems that only apply in [`GameMode::Gentle`].
+pub fn gentle_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Gentle
+}
+
 #[derive(Debug, Resource, Reflect)]
 #[reflect(Resource)]
 pub struct GameState {
@@ -40,14 +125,29 @@ pub struct GameState {
     pub red_sheep_count: u16,
     pub black_sheep_count: u16,
     pub gold_sheep_count: u16,
+    pub green_sheep_count: u16,
     pub countdown: Timer,
     pub points: u32,
     pub point_target: u32,
-    pub active_modifiers: Vec<Modifier>,
+    pub active_modifiers: Vec<ActiveModifier>,
     pub money: u32,
     pub charms: Vec<Charm>,
     pub max_charms: u8,
     pub player_bark_radius: f32,
+    /// Money refunded per sheep abducted, from [`shop::items::Boost::Insurance`].
+    pub insurance_level: u8,
+    pub double_or_nothing_won: u16,
+    pub double_or_nothing_lost: u16,
+    /// The modifiers the next round will offer, rolled in advance on entering the shop so
+    /// [`Charm::Clairvoyant`] can show them.
+    pub next_modifier_choices: Option<Vec<Modifier>>,
+    /// How efficiently the last round was won, if it could be rated.
+    pub last_round_rating: Option<EfficiencyRating>,
+    /// The best [`Self::last_round_rating`] this run.
+    pub best_round_rating: Option<EfficiencyRating>,
+    /// Whether this run started from [`Self::new_game_plus`], with part of the last run carried
+    /// over.
+    pub new_game_plus: bool,
 }
 
 impl Default for GameState {
@@ -57,7 +157,7 @@ impl Default for GameState {
             sheep_count: 10,
             blue_sheep_count: 1,
             red_sheep_count: 1,
-            countdown: Timer::from_seconds(TIMER_SECONDS, TimerMode::Once),
+            countdown: Timer::from_seconds(RoundConfig::default().timer_seconds, TimerMode::Once),
             points: 0,
             point_target: 3,
             active_modifiers: Vec::new(),
@@ -65,42 +165,230 @@ impl Default for GameState {
             charms: Vec::with_capacity(4),
             max_charms: 4,
             player_bark_radius: 12.0,
+            insurance_level: 0,
             black_sheep_count: 0,
             gold_sheep_count: 0,
+            green_sheep_count: 0,
+            double_or_nothing_won: 0,
+            double_or_nothing_lost: 0,
+            next_modifier_choices: None,
+            last_round_rating: None,
+            best_round_rating: None,
+            new_game_plus: false,
         }
     }
 }
 
 impl GameState {
-    pub fn new_round(&mut self) -> NewRoundInfo {
+    /// A fresh run that carries part of `previous`'s flock and money over, for New Game+.
+    ///
+    /// The new run starts with half the previous flock and a third of its money, rounded down,
+    /// but never less than a normal run. The points target starts at 60% of the one the previous
+    /// run ended on, so it stays ahead of the smaller flock. Charms, modifiers and the special
+    /// sheep all reset.
+    pub fn new_game_plus(previous: &RunSummary) -> Self {
+        let default = Self::default();
+        Self {
+            sheep_count: (previous.sheep_count / 2).max(default.sheep_count),
+            money: previous.money / 3,
+            point_target: (previous.point_target * 3 / 5).max(default.point_target),
+            new_game_plus: true,
+            ..default
+        }
+    }
+
+    /// Keeps the rating of the round just won, and the best one so far.
+    pub fn record_round_rating(&mut self, rating: Option<EfficiencyRating>) {
+        self.last_round_rating = rating;
+        if let Some(rating) = rating
+            && self
+                .best_round_rating
+                .is_none_or(|best| rating.pace > best.pace)
+        {
+            self.best_round_rating = Some(rating);
+        }
+    }
+
+    /// Starts the next round. `banned_modifiers` won't be offered as choices.
+    pub fn new_round(&mut self, banned_modifiers: &[Modifier], rng: &mut impl Rng) -> NewRoundInfo {
         self.completed_rounds += 1;
         self.points = 0;
-        self.point_target += 2 + (self.point_target / 10) + (self.point_target / 40);
-        let removed_modifier = if self.active_modifiers.len() > 2 {
-            Some(self.active_modifiers.remove(0))
-        } else {
-            None
+        self.point_target = next_point_target(self.point_target);
+        let mut removed_modifiers = Vec::new();
+        self.active_modifiers.retain_mut(|active| {
+            if active.drafted {
+                return true;
+            }
+            active.rounds_left = active.rounds_left.saturating_sub(1);
+            if active.rounds_left == 0 {
+                removed_modifiers.push(active.modifier);
+            }
+            active.rounds_left > 0
+        });
+        let remaining: Vec<Modifier> = self.active_modifiers.iter().map(|a| a.modifier).collect();
+        // Use the pre-rolled choices, unless the player has banned one of them since.
+        let modifier_choices = match self.next_modifier_choices.take() {
+            Some(choices)
+                if choices.iter().all(|modifier| {
+                    !remaining.contains(modifier) && !banned_modifiers.contains(modifier)
+                }) =>
+            {
+                choices
+            }
+            _ => Self::pick_random_modifiers(2, &remaining, banned_modifiers, rng),
         };
-        let modifier_choices = self.pick_random_modifiers(2);
         NewRoundInfo {
-            removed_modifier,
+            removed_modifiers,
             modifier_choices,
         }
     }
 
-    pub fn reset_timer(&mut self) {
+    /// Rolls the modifiers the next round will offer, as [`Self::new_round`] would after expiring
+    /// the modifiers on their last round.
+    pub fn roll_next_modifier_choices(
+        &mut self,
+        banned_modifiers: &[Modifier],
+        rng: &mut impl Rng,
+    ) {
+        let remaining: Vec<Modifier> = self
+            .active_modifiers
+            .iter()
+            .filter(|active| active.drafted || active.rounds_left > 1)
+            .map(|active| active.modifier)
+            .collect();
+        self.next_modifier_choices = Some(Self::pick_random_modifiers(
+            2,
+            &remaining,
+            banned_modifiers,
+            rng,
+        ));
+    }
+
+    /// Activates a newly chosen modifier for [`MODIFIER_DURATION_ROUNDS`] rounds.
+    pub fn add_modifier(&mut self, modifier: Modifier) {
+        self.active_modifiers.push(ActiveModifier {
+            modifier,
+            rounds_left: MODIFIER_DURATION_ROUNDS,
+            drafted: false,
+        });
+    }
+
+    /// Activates a modifier drafted in [`GameMode::Draft`] for the rest of the run.
+    pub fn add_drafted_modifier(&mut self, modifier: Modifier) {
+        self.active_modifiers.push(ActiveModifier {
+            modifier,
+            rounds_left: MODIFIER_DURATION_ROUNDS,
+            drafted: true,
+        });
+    }
+
+    /// The money paid each round for drafted modifiers.
+    pub fn drafted_modifier_money(&self) -> u32 {
+        let drafted = self.active_modifiers.iter().filter(|a| a.drafted).count() as u32;
+        drafted * DRAFTED_MODIFIER_MONEY
+    }
+
+    pub fn reset_timer(&mut self, config: &RoundConfig) {
+        let mut seconds = config.timer_seconds;
         if self.is_charm_active(Charm::HalfTimeDoubleSheep) {
-            self.countdown
-                .set_duration(Duration::from_secs_f32(TIMER_SECONDS - 20.0));
-        } else {
-            self.countdown
-                .set_duration(Duration::from_secs_f32(TIMER_SECONDS));
+            seconds -= config.half_time_penalty_seconds;
         }
+        self.countdown
+            .set_duration(Duration::from_secs_f32(seconds.max(1.0)));
         self.countdown.reset();
     }
 
+    /// How many sheep of `color` are in the flock. White sheep are the ones without a color of
+    /// their own. Contrarian sheep only come from white sheep straying, so there are never any
+    /// between rounds.
+    pub fn sheep_of_color(&self, color: &SheepColor) -> u16 {
+        match color {
+            SheepColor::White => self.sheep_count.saturating_sub(
+                self.blue_sheep_count
+                    + self.red_sheep_count
+                    + self.black_sheep_count
+                    + self.gold_sheep_count
+                    + self.green_sheep_count,
+            ),
+            SheepColor::Blue => self.blue_sheep_count,
+            SheepColor::Red => self.red_sheep_count,
+            SheepColor::Black => self.black_sheep_count,
+            SheepColor::Gold => self.gold_sheep_count,
+            SheepColor::Green => self.green_sheep_count,
+            SheepColor::Contrarian => 0,
+        }
+    }
+
+    /// Turns one `from` sheep into a `to` sheep, keeping the flock size the same. Returns whether
+    /// there was a `from` sheep to recolor. Sheep can't be dyed gold or contrarian.
+    pub fn recolor_sheep(&mut self, from: &SheepColor, to: &SheepColor) -> bool {
+        if from == to
+            || self.sheep_of_color(from) == 0
+            || matches!(to, SheepColor::Gold | SheepColor::Contrarian)
+        {
+            return false;
+        }
+        if let Some(count) = self.colored_sheep_count_mut(from) {
+            *count -= 1;
+        }
+        if let Some(count) = self.colored_sheep_count_mut(to) {
+            *count += 1;
+        }
+        true
+    }
+
+    /// The stored count for a color. White sheep aren't counted directly, see
+    /// [`Self::sheep_of_color`].
+    fn colored_sheep_count_mut(&mut self, color: &SheepColor) -> Option<&mut u16> {
+        match color {
+            SheepColor::Blue => Some(&mut self.blue_sheep_count),
+            SheepColor::Red => Some(&mut self.red_sheep_count),
+            SheepColor::Black => Some(&mut self.black_sheep_count),
+            SheepColor::Gold => Some(&mut self.gold_sheep_count),
+            SheepColor::Green => Some(&mut self.green_sheep_count),
+            SheepColor::White | SheepColor::Contrarian => None,
+        }
+    }
+
+    /// Roughly how many points the flock is worth if every sheep gets counted next round, including
+    /// the white sheep expected to stray black or contrarian.
+    ///
+    /// Red sheep multiply the points scored so far, so the real total depends on the order sheep
+    /// are counted in. This assumes the best case, with every red sheep counted last.
+    pub fn flock_value(&self) -> u32 {
+        let sorting = self.is_modifier_active(Modifier::SortingPens);
+        let mut colors = herding::build_sheep_colors(self);
+        herding::expect_stray_colors(&mut colors, self);
+        colors.sort_by_key(|color| *color == SheepColor::Red);
+
+        let mut points: u32 = 0;
+        let mut black_counted: u32 = 0;
+        for color in colors {
+            if sorting {
+                points = points.saturating_add(1);
+            }
+            let gained = match color {
+                SheepColor::White if self.is_charm_active(Charm::Evolution) => 0,
+                SheepColor::White => 1,
+                SheepColor::Blue => BLUE_SHEEP_POINTS,
+                SheepColor::Red => points / 2,
+                SheepColor::Black if self.is_charm_active(Charm::BlackInc) => {
+                    black_counted += 1;
+                    black_counted
+                }
+                SheepColor::Black => 1,
+                SheepColor::Gold | SheepColor::Green => 0,
+                SheepColor::Contrarian => 3,
+            };
+            points = points.saturating_add(gained);
+        }
+        points
+    }
+
     pub fn is_modifier_active(&self, modifier: Modifier) -> bool {
-        self.active_modifiers.contains(&modifier)
+        self.active_modifiers
+            .iter()
+            .any(|active| active.modifier == modifier)
     }
 
     pub fn is_charm_active(&self, charm: Charm) -> bool {
@@ -111,13 +399,20 @@ impl GameState {
         self.charms.len() >= self.max_charms as usize
     }
 
-    fn pick_random_modifiers(&self, count: usize) -> Vec<Modifier> {
+    fn pick_random_modifiers(
+        count: usize,
+        active: &[Modifier],
+        banned: &[Modifier],
+        rng: &mut impl Rng,
+    ) -> Vec<Modifier> {
         let mut choices = Vec::with_capacity(count);
-        let rng = &mut rand::rng();
         let mut attempts = 0;
         while choices.len() < count && attempts < 100 {
             let modifier: Modifier = rng.random();
-            if self.active_modifiers.contains(&modifier) || choices.contains(&modifier) {
+            if active.contains(&modifier)
+                || choices.contains(&modifier)
+                || banned.contains(&modifier)
+            {
                 attempts += 1;
                 continue;
             }
@@ -128,8 +423,27 @@ impl GameState {
     }
 }
 
+/// The points target for the round after one with a target of `current`.
+pub fn next_point_target(current: u32) -> u32 {
+    let increase = if current < POINT_TARGET_SOFT_CAP {
+        2 + (current / 10) + (current / 40)
+    } else {
+        2 + 3 * current.ilog2()
+    };
+    current.saturating_add(increase)
+}
+
+/// A dream modifier in effect, and how many more rounds it lasts including the current one.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ActiveModifier {
+    pub modifier: Modifier,
+    pub rounds_left: u32,
+    /// Drafted modifiers never expire, so `rounds_left` doesn't count down for them.
+    pub drafted: bool,
+}
+
 pub struct NewRoundInfo {
-    removed_modifier: Option<Modifier>,
+    removed_modifiers: Vec<Modifier>,
     modifier_choices: Vec<Modifier>,
 }
 
@@ -139,14 +453,221 @@ pub struct RoundStats {
     pub sheep_counted: u16,
     pub white_sheep_counted: u16,
     pub black_sheep_counted: u16,
+    /// Sheep carried off by a UFO.
+    pub sheep_abducted: u16,
+    /// [`GameState::money`] when the round started, to work out how much the round earned.
+    pub money_at_start: u32,
+}
+
+/// Totals for the whole run, shown on the game over screen.
+#[derive(Debug, Default, Resource, Reflect)]
+#[reflect(Resource)]
+pub struct RunStatistics {
+    /// How many sheep of each color have been counted, in the order they were first counted.
+    pub sheep_counted: Vec<(SheepColor, u32)>,
+    pub barks: u32,
+    /// Every increase in money, including selling charms. Spending doesn't take away from it.
+    pub money_earned: u32,
+    /// How many times each modifier was picked from the modifier choice.
+    pub modifiers_chosen: Vec<(Modifier, u32)>,
+    /// The money last seen, to work out how much was earned since.
+    money_seen: Option<u32>,
+}
+
+impl RunStatistics {
+    pub fn record_sheep_counted(&mut self, color: &SheepColor) {
+        match self.sheep_counted.iter_mut().find(|(c, _)| c == color) {
+            Some((_, count)) => *count += 1,
+            None => self.sheep_counted.push((color.clone(), 1)),
+        }
+    }
+
+    pub fn record_modifier_chosen(&mut self, modifier: Modifier) {
+        match self
+            .modifiers_chosen
+            .iter_mut()
+            .find(|(m, _)| *m == modifier)
+        {
+            Some((_, count)) => *count += 1,
+            None => self.modifiers_chosen.push((modifier, 1)),
+        }
+    }
+
+    pub fn total_sheep_counted(&self) -> u32 {
+        self.sheep_counted.iter().map(|(_, count)| count).sum()
+    }
+
+    /// The modifier picked most often and how many times, going with the one picked first on a
+    /// tie.
+    pub fn most_chosen_modifier(&self) -> Option<(Modifier, u32)> {
+        self.modifiers_chosen
+            .iter()
+            .copied()
+            .rev()
+            .max_by_key(|(_, count)| *count)
+    }
+}
+
+fn track_money_earned(game_state: Res<GameState>, mut stats: ResMut<RunStatistics>) {
+    if let Some(seen) = stats.money_seen {
+        stats.money_earned += game_state.money.saturating_sub(seen);
+    }
+    stats.money_seen = Some(game_state.money);
+}
+
+/// Sent when a round ends with its points target met, just before moving on to the next phase.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct RoundCompleted {
+    /// The round that just ended, starting from 1.
+    pub round: u32,
+    pub points: u32,
+    pub sheep_lost: u16,
+}
+
+/// Sent when time runs out on a round before its points target is met, ending the run.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct RoundFailed {
+    /// The round that just ended, starting from 1.
+    pub round: u32,
+    pub points: u32,
+    pub point_target: u32,
+    pub sheep_lost: u16,
+}
+
+fn log_round_results(
+    mut completed: MessageReader<RoundCompleted>,
+    mut failed: MessageReader<RoundFailed>,
+) {
+    for result in completed.read() {
+        info!(
+            "Round {} completed with {} points, {} sheep lost",
+            result.round, result.points, result.sheep_lost
+        );
+    }
+    for result in failed.read() {
+        info!(
+            "Round {} failed with {}/{} points, {} sheep lost",
+            result.round, result.points, result.point_target, result.sheep_lost
+        );
+    }
+}
+
+/// How a run ended, kept so the next run can carry some of it over with [`NewGamePlus`].
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct RunSummary {
+    pub completed_rounds: u32,
+    pub sheep_count: u16,
+    pub money: u32,
+    pub point_target: u32,
+}
+
+/// The summary of the last run to end, if one has ended since the game started.
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PreviousRun(pub Option<RunSummary>);
+
+/// Set when the player picks New Game+ on the game over screen, so the next run starts from
+/// [`GameState::new_game_plus`] instead of scratch.
+#[derive(Resource, Debug, Default)]
+pub struct NewGamePlus(pub bool);
+
+fn record_previous_run(game_state: Res<GameState>, mut previous_run: ResMut<PreviousRun>) {
+    previous_run.0 = Some(RunSummary {
+        completed_rounds: game_state.completed_rounds,
+        sheep_count: game_state.sheep_count,
+        money: game_state.money,
+        point_target: game_state.point_target,
+    });
+}
+
+fn start_new_game_plus(
+    mut new_game_plus: ResMut<NewGamePlus>,
+    previous_run: Res<PreviousRun>,
+    mut game_mode: ResMut<GameMode>,
+    mut game_state: ResMut<GameState>,
+    mut round_stats: ResMut<RoundStats>,
+    mut run_stats: ResMut<RunStatistics>,
+    mut playtime: ResMut<Playtime>,
+) {
+    new_game_plus.0 = false;
+    // New Game+ is always a normal run, whatever mode the previous one was played in.
+    reset_run(
+        &mut game_mode,
+        &mut game_state,
+        &mut round_stats,
+        &mut run_stats,
+        &mut playtime,
+    );
+    if let Some(previous) = &previous_run.0 {
+        *game_state = GameState::new_game_plus(previous);
+    }
 }
 
 fn reset_run_state(
+    mut game_mode: ResMut<GameMode>,
     mut game_state: ResMut<GameState>,
     mut round_stats: ResMut<RoundStats>,
+    mut run_stats: ResMut<RunStatistics>,
+    mut playtime: ResMut<Playtime>,
     mut next_phase: ResMut<NextState<GamePhase>>,
 ) {
+    reset_run(
+        &mut game_mode,
+        &mut game_state,
+        &mut round_stats,
+        &mut run_stats,
+        &mut playtime,
+    );
+    next_phase.set(GamePhase::Herding);
+}
+
+/// Clears everything kept for a single run, ready for a new one.
+fn reset_run(
+    game_mode: &mut GameMode,
+    game_state: &mut GameState,
+    round_stats: &mut RoundStats,
+    run_stats: &mut RunStatistics,
+    playtime: &mut Playtime,
+) {
+    *game_mode = GameMode::default();
     *game_state = GameState::default();
     *round_stats = RoundStats::default();
-    next_phase.set(GamePhase::Herding);
+    *run_stats = RunStatistics::default();
+    playtime.run = Duration::ZERO;
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    #[test]
+    fn point_target_stays_monotonic_and_bounded_over_100_rounds() {
+        let mut state = GameState::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut previous = state.point_target;
+        for _ in 0..100 {
+            state.new_round(&[], &mut rng);
+            assert!(state.point_target > previous);
+            previous = state.point_target;
+        }
+        // Past the soft cap the target only grows logarithmically, so a long run stays winnable
+        // with a flock in the low hundreds and a few red sheep.
+        assert!(state.point_target < 5_000, "target {}", state.point_target);
+    }
+
+    #[test]
+    fn sheep_cant_be_dyed_gold() {
+        let mut state = GameState::default();
+        assert!(!state.recolor_sheep(&SheepColor::White, &SheepColor::Gold));
+        assert_eq!(state.gold_sheep_count, 0);
+        assert!(state.recolor_sheep(&SheepColor::White, &SheepColor::Green));
+        assert_eq!(state.green_sheep_count, 1);
+    }
+
+    #[test]
+    fn next_point_target_saturates() {
+        assert_eq!(next_point_target(u32::MAX), u32::MAX);
+    }
 }
@@ -3,10 +3,18 @@ use bevy::prelude::*;
 use crate::{
     audio::BgmConfig,
     game::{
+        config::GameConfig,
         modifiers::Modifier,
-        state::{GamePhase, GameState, NewRoundInfo},
+        rng::GameRng,
+        state::{
+            GamePhase, GameState, NewRoundInfo, RunStatistics,
+            double_or_nothing::DoubleOrNothing,
+            efficiency::{self, EfficiencyRating},
+            milestone::{MilestoneReached, is_milestone},
+        },
     },
     post_processing::DreamCloudVignette,
+    settings::GameplaySettings,
     theme::{
         palette::{CARD_BACKGROUND, CARD_BORDER},
         prelude::*,
@@ -22,26 +30,49 @@ fn on_modifier_choice(
     mut vignette: ResMut<DreamCloudVignette>,
     mut game_state: ResMut<GameState>,
     mut bgm_config: ResMut<BgmConfig>,
+    double_or_nothing: Res<DoubleOrNothing>,
+    settings: Res<GameplaySettings>,
+    config: Res<GameConfig>,
+    mut rng: ResMut<GameRng>,
+    mut milestones: MessageWriter<MilestoneReached>,
 ) {
     bgm_config.base_enabled = true;
     bgm_config.extra_enabled = false;
     bgm_config.percussion_enabled = false;
 
     let NewRoundInfo {
-        removed_modifier,
+        removed_modifiers,
         modifier_choices,
-    } = game_state.new_round();
+    } = game_state.new_round(&settings.banned_modifiers, &mut *rng);
+    if is_milestone(&config.round, game_state.completed_rounds) {
+        milestones.write(MilestoneReached {
+            round: game_state.completed_rounds,
+        });
+    }
+    let drafted_money = game_state.drafted_modifier_money();
+    game_state.money += drafted_money;
 
-    draw_choice_ui(&mut commands, removed_modifier, &modifier_choices);
+    draw_choice_ui(
+        &mut commands,
+        &removed_modifiers,
+        &modifier_choices,
+        &double_or_nothing,
+        drafted_money,
+        game_state.last_round_rating,
+    );
 
     vignette.target_coverage = 1.0;
 }
 
 fn draw_choice_ui(
     commands: &mut Commands,
-    removed_modifier: Option<Modifier>,
+    removed_modifiers: &[Modifier],
     modifier_choices: &[Modifier],
+    double_or_nothing: &DoubleOrNothing,
+    drafted_money: u32,
+    last_round_rating: Option<EfficiencyRating>,
 ) {
+    let money_multiplier = double_or_nothing.money_multiplier();
     commands
         .spawn((
             widget::ui_root("Modifier choice UI"),
@@ -51,12 +82,37 @@ fn draw_choice_ui(
         .with_children(|root| {
             root.spawn(widget::panel()).with_children(|panel| {
                 panel.spawn(widget::header("Choose new dream modifier:"));
-                if let Some(removed_modifier) = removed_modifier {
+                for removed_modifier in removed_modifiers {
                     panel.spawn(widget::label(format!(
                         "Modifier no longer active: {}",
                         removed_modifier.name()
                     )));
                 }
+                match double_or_nothing {
+                    DoubleOrNothing::Won => {
+                        panel.spawn(widget::label("Double or Nothing won: money doubled!"));
+                    }
+                    DoubleOrNothing::Lost => {
+                        panel.spawn(widget::label(
+                            "Double or Nothing lost: no money this round.",
+                        ));
+                    }
+                    _ => {}
+                }
+                if let Some(rating) = last_round_rating {
+                    panel.spawn(efficiency::grade_badge(
+                        rating,
+                        format!(
+                            "Efficiency: {:.2} points/s ({:.1}x the pace needed)",
+                            rating.points_per_second, rating.pace
+                        ),
+                    ));
+                }
+                if drafted_money > 0 {
+                    panel.spawn(widget::label(format!(
+                        "Drafted modifiers: +{drafted_money} money"
+                    )));
+                }
                 panel
                     .spawn((
                         Name::new("Modifiers Row"),
@@ -73,14 +129,46 @@ fn draw_choice_ui(
                     ))
                     .with_children(|row| {
                         for choice in modifier_choices {
-                            row.spawn(modifier_card(*choice));
+                            row.spawn(choice_card(*choice, money_multiplier));
                         }
                     });
+                // Every enabled modifier may already be active, e.g. with many disabled in a draft
+                // run, so there has to be a way on without picking one.
+                if modifier_choices.is_empty() {
+                    panel.spawn(widget::label("No new modifiers to choose from."));
+                    panel.spawn(widget::button("Continue", skip_modifier_choice));
+                }
             });
+            root.spawn(widget::controls_footer(&[("P / Esc", "Pause")]));
         });
 }
 
-fn modifier_card(modifier: Modifier) -> impl Bundle {
+fn choice_card(modifier: Modifier, money_multiplier: u32) -> impl Bundle {
+    let money = modifier.difficulty().coins_given() as u32 * money_multiplier;
+    modifier_card(
+        modifier,
+        format!("+{money} money"),
+        widget::button_medium(
+            "Choose",
+            move |_: On<Pointer<Click>>,
+                  mut next_state: ResMut<NextState<GamePhase>>,
+                  mut state: ResMut<GameState>,
+                  mut run_stats: ResMut<RunStatistics>| {
+                state.add_modifier(modifier);
+                run_stats.record_modifier_chosen(modifier);
+                state.money += money;
+                next_state.set(GamePhase::Shop);
+            },
+        ),
+    )
+}
+
+fn skip_modifier_choice(_: On<Pointer<Click>>, mut next_state: ResMut<NextState<GamePhase>>) {
+    next_state.set(GamePhase::Shop);
+}
+
+/// A card describing `modifier`, with `value` saying what it's worth and `button` at the bottom.
+pub fn modifier_card(modifier: Modifier, value: String, button: impl Bundle) -> impl Bundle {
     (
         Name::new(format!("Modifier Card {}", modifier.name())),
         Node {
@@ -113,23 +201,77 @@ fn modifier_card(modifier: Modifier) -> impl Bundle {
                 TextColor(ui_palette::LABEL_TEXT),
                 TextLayout::new_with_justify(Justify::Center),
             ),
+            (
+                Name::new("Modifier Intensity"),
+                Text(modifier.intensity_text().unwrap_or_default()),
+                TextFont::from_font_size(14.0),
+                TextColor(ui_palette::SUB_HEADER_TEXT),
+                TextLayout::new_with_justify(Justify::Center),
+            ),
             (
                 Name::new("Modifier Value"),
-                Text(format!("+{} money", modifier.difficulty().coins_given())),
+                Text(value),
                 TextFont::from_font_size(14.0),
                 TextColor(ui_palette::LABEL_TEXT),
                 TextLayout::new_with_justify(Justify::Center),
             ),
-            widget::button_medium(
-                "Choose",
-                move |_: On<Pointer<Click>>,
-                      mut next_state: ResMut<NextState<GamePhase>>,
-                      mut state: ResMut<GameState>| {
-                    state.active_modifiers.push(modifier);
-                    state.money += modifier.difficulty().coins_given() as u32;
-                    next_state.set(GamePhase::Shop);
-                }
-            )
+            button,
         ],
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::settings::{MIN_ENABLED_MODIFIERS, MIN_ENABLED_MODIFIERS_FOR_DRAFT};
+
+    fn draft_state() -> GameState {
+        let mut state = GameState::default();
+        state.add_drafted_modifier(Modifier::ALL[0]);
+        state.add_drafted_modifier(Modifier::ALL[1]);
+        state
+    }
+
+    #[test]
+    fn empty_choices_can_be_skipped() {
+        // Two drafted modifiers and two carried over leave nothing new with only the minimum
+        // enabled.
+        let mut state = draft_state();
+        state.add_modifier(Modifier::ALL[2]);
+        state.add_modifier(Modifier::ALL[3]);
+        let banned = &Modifier::ALL[MIN_ENABLED_MODIFIERS..];
+        let info = state.new_round(banned, &mut StdRng::seed_from_u64(0));
+        assert!(info.modifier_choices.is_empty());
+
+        let mut world = World::new();
+        world
+            .run_system_once(move |mut commands: Commands| {
+                draw_choice_ui(
+                    &mut commands,
+                    &[],
+                    &info.modifier_choices,
+                    &DoubleOrNothing::default(),
+                    0,
+                    None,
+                );
+            })
+            .unwrap();
+        let mut texts = world.query::<&Text>();
+        assert!(texts.iter(&world).any(|text| text.0 == "Continue"));
+    }
+
+    #[test]
+    fn draft_always_has_choices_with_enough_modifiers_enabled() {
+        let mut state = draft_state();
+        let banned = &Modifier::ALL[MIN_ENABLED_MODIFIERS_FOR_DRAFT..];
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let info = state.new_round(banned, &mut rng);
+            assert_eq!(info.modifier_choices.len(), 2);
+            state.add_modifier(info.modifier_choices[0]);
+        }
+    }
+}
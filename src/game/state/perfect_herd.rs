@@ -0,0 +1,98 @@
+//! A bonus for keeping the flock bunched up: once a round, keeping most of the flock within reach
+//! of each other (see [`PerfectHerdConfig::neighbor_fraction`]) for
+//! [`PerfectHerdConfig::hold_seconds`] pays out money.
+
+use bevy::prelude::*;
+
+use crate::{
+    AppSystems, PausableSystems,
+    game::{
+        config::{GameConfig, HerdConfig, PerfectHerdConfig},
+        level::GoalTextMessage,
+        sheep::Sheep,
+        state::{GamePhase, GameState},
+    },
+};
+
+const PERFECT_HERD_COLOR: Color = Color::srgb(0.55, 0.95, 0.55);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PerfectHerd>();
+    app.add_systems(OnEnter(GamePhase::Herding), reset_perfect_herd);
+    app.add_systems(
+        Update,
+        track_perfect_herd
+            .run_if(in_state(GamePhase::Herding))
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Progress towards this round's perfect herd.
+#[derive(Resource, Debug, Default)]
+struct PerfectHerd {
+    /// How long the flock has been held together so far.
+    held_secs: f32,
+    awarded: bool,
+}
+
+fn reset_perfect_herd(mut perfect_herd: ResMut<PerfectHerd>) {
+    *perfect_herd = PerfectHerd::default();
+}
+
+/// Whether the flock is bunched up enough to count towards a perfect herd. Small flocks don't
+/// count, since a handful of sheep are easy to keep together. Spooked sheep aren't flocking, so
+/// they count as having no flockmates.
+fn flock_is_tight<'a>(
+    sheep: impl Iterator<Item = &'a Sheep>,
+    herd: &HerdConfig,
+    config: &PerfectHerdConfig,
+) -> bool {
+    let (count, neighbors) = sheep
+        .filter(|sheep| sheep.is_herdable())
+        .fold((0, 0), |(count, neighbors), sheep| {
+            (count + 1, neighbors + sheep.neighbor_count())
+        });
+    if count < config.min_sheep.max(2) {
+        return false;
+    }
+    let possible = (count - 1).min(herd.max_neighbors);
+    neighbors as f32 / (count * possible) as f32 >= config.neighbor_fraction
+}
+
+fn track_perfect_herd(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut perfect_herd: ResMut<PerfectHerd>,
+    mut game_state: ResMut<GameState>,
+    sheep_query: Query<(&GlobalTransform, &Sheep)>,
+    mut writer: MessageWriter<GoalTextMessage>,
+) {
+    let (herd, config) = (&config.herd, &config.perfect_herd);
+    if perfect_herd.awarded {
+        return;
+    }
+    if !flock_is_tight(sheep_query.iter().map(|(_, sheep)| sheep), herd, config) {
+        perfect_herd.held_secs = 0.0;
+        return;
+    }
+    perfect_herd.held_secs += time.delta_secs();
+    if perfect_herd.held_secs < config.hold_seconds {
+        return;
+    }
+
+    perfect_herd.awarded = true;
+    game_state.money += config.reward_money;
+    // Show the toast over the middle of the flock, where the player is looking.
+    let (total, count) = sheep_query
+        .iter()
+        .filter(|(_, sheep)| sheep.is_herdable())
+        .fold((Vec3::ZERO, 0.0), |(total, count), (transform, _)| {
+            (total + transform.translation(), count + 1.0)
+        });
+    writer.write(GoalTextMessage {
+        text: format!("Perfect Herd! +{}", config.reward_money),
+        color: Some(PERFECT_HERD_COLOR),
+        position: Some(total / count),
+    });
+}
@@ -0,0 +1,87 @@
+//! Diminishing returns on rapid counting in [`GameMode::Ranked`], so farming loops with charms like
+//! [`Charm::Cloning`] and [`Charm::Exponential`] can't run the score away.
+//!
+//! The pace `n` is how many sheep were counted in the last [`RankedConfig::window_seconds`],
+//! including the one being scored. The first [`RankedConfig::free_counts`] score in full. Past
+//! that, the points the count gains are multiplied by
+//!
+//! ```text
+//! 1 / (1 + falloff * (n - free_counts))
+//! ```
+//!
+//! and rounded down, where `falloff` is [`RankedConfig::falloff`]. With the defaults (6 free counts
+//! in 2 seconds, falloff 0.5) the 7th sheep in a burst scores x0.67, the 8th x0.5, the 10th x0.33.
+//!
+//! [`GameMode::Ranked`]: crate::game::state::GameMode::Ranked
+//! [`Charm::Cloning`]: crate::game::state::shop::items::Charm::Cloning
+//! [`Charm::Exponential`]: crate::game::state::shop::items::Charm::Exponential
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::game::{config::RankedConfig, state::GamePhase};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<CountPace>();
+    app.add_systems(OnEnter(GamePhase::Herding), reset_count_pace);
+}
+
+/// When recent sheep were counted, in seconds of game time.
+#[derive(Resource, Debug, Default)]
+pub struct CountPace {
+    recent: VecDeque<f32>,
+}
+
+impl CountPace {
+    /// Records a count at `now` and returns the multiplier for the points it gains.
+    pub fn record(&mut self, now: f32, config: &RankedConfig) -> f32 {
+        while self
+            .recent
+            .front()
+            .is_some_and(|counted_at| now - counted_at > config.window_seconds)
+        {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(now);
+        pace_multiplier(self.recent.len(), config)
+    }
+}
+
+/// The multiplier for a count made at a pace of `pace` counts per window.
+pub fn pace_multiplier(pace: usize, config: &RankedConfig) -> f32 {
+    let excess = pace.saturating_sub(config.free_counts) as f32;
+    1.0 / (1.0 + config.falloff * excess)
+}
+
+fn reset_count_pace(mut pace: ResMut<CountPace>) {
+    pace.recent.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pace_multiplier_matches_the_documented_values() {
+        let config = RankedConfig::default();
+        for pace in 0..=config.free_counts {
+            assert_eq!(pace_multiplier(pace, &config), 1.0);
+        }
+        let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+        assert!(close(pace_multiplier(7, &config), 0.67));
+        assert!(close(pace_multiplier(8, &config), 0.5));
+        assert!(close(pace_multiplier(10, &config), 0.33));
+    }
+
+    #[test]
+    fn counts_outside_the_window_are_forgotten() {
+        let config = RankedConfig::default();
+        let mut pace = CountPace::default();
+        for _ in 0..config.free_counts {
+            assert_eq!(pace.record(0.0, &config), 1.0);
+        }
+        assert!(pace.record(0.0, &config) < 1.0);
+        assert_eq!(pace.record(config.window_seconds + 1.0, &config), 1.0);
+    }
+}
@@ -0,0 +1,67 @@
+//! A look back at the round just finished, shown before picking the next modifier.
+
+use bevy::prelude::*;
+
+use crate::{
+    game::state::{GamePhase, GameState, RoundStats},
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(GamePhase::RoundSummary), on_round_summary);
+}
+
+fn on_round_summary(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    round_stats: Res<RoundStats>,
+) {
+    let money_gained = game_state.money.saturating_sub(round_stats.money_at_start);
+    // Only white and black sheep are tallied separately; everything else is lumped together.
+    let other_sheep = round_stats
+        .sheep_counted
+        .saturating_sub(round_stats.white_sheep_counted + round_stats.black_sheep_counted);
+
+    commands
+        .spawn((
+            widget::ui_root("Round summary UI"),
+            GlobalZIndex(2),
+            DespawnOnExit(GamePhase::RoundSummary),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::panel()).with_children(|panel| {
+                panel.spawn(widget::header(format!(
+                    "Round {} complete!",
+                    game_state.completed_rounds + 1
+                )));
+                panel.spawn(widget::label(format!(
+                    "Points: {}/{}",
+                    game_state.points, game_state.point_target
+                )));
+                panel.spawn(widget::label(format!(
+                    "Sheep counted: {}",
+                    round_stats.sheep_counted
+                )));
+                panel.spawn(widget::label(format!(
+                    "White: {}  Black: {}  Other: {other_sheep}",
+                    round_stats.white_sheep_counted, round_stats.black_sheep_counted
+                )));
+                if round_stats.sheep_abducted > 0 {
+                    panel.spawn(widget::label(format!(
+                        "Sheep lost to UFOs: {}",
+                        round_stats.sheep_abducted
+                    )));
+                }
+                panel.spawn(widget::label(format!("Money gained: +{money_gained}")));
+                panel.spawn(widget::button("Continue", continue_to_modifier_choice));
+            });
+            root.spawn(widget::controls_footer(&[("P / Esc", "Pause")]));
+        });
+}
+
+fn continue_to_modifier_choice(
+    _: On<Pointer<Click>>,
+    mut next_state: ResMut<NextState<GamePhase>>,
+) {
+    next_state.set(GamePhase::ModifierChoice);
+}
@@ -1,7 +1,16 @@
+use std::borrow::Cow;
+
 use bevy::prelude::Reflect;
 use rand::Rng;
 
-use crate::game::state::GameState;
+use crate::game::{
+    config::GameConfig,
+    sheep::{BLUE_SHEEP_POINTS, GREEN_SHEEP_BONUS, SheepColor},
+    state::{
+        GameState,
+        herding::{BLACK_SHEEP_CHANCE, INK_BLACK_SHEEP_CHANCE},
+    },
+};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Reflect)]
 pub enum ItemType {
@@ -17,10 +26,10 @@ impl ItemType {
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    pub fn description(&self, config: &GameConfig) -> Cow<'static, str> {
         match self {
-            ItemType::Boost(boost) => boost.description(),
-            ItemType::Charm(charm) => charm.description(),
+            ItemType::Boost(boost) => boost.description(config),
+            ItemType::Charm(charm) => charm.description(config),
         }
     }
 
@@ -31,6 +40,22 @@ impl ItemType {
         }
     }
 
+    /// The exact numbers behind the description, read from the current state, for the shop
+    /// tooltip.
+    pub fn details(&self, state: &GameState, config: &GameConfig) -> Option<String> {
+        match self {
+            ItemType::Boost(boost) => Some(boost.details(state, config)),
+            ItemType::Charm(charm) => charm.details(state, config),
+        }
+    }
+
+    pub fn icon_path(&self) -> &'static str {
+        match self {
+            ItemType::Boost(boost) => boost.icon_path(),
+            ItemType::Charm(charm) => charm.icon_path(),
+        }
+    }
+
     pub fn kind_label(&self) -> &'static str {
         match self {
             ItemType::Boost(_) => "Boost",
@@ -38,45 +63,60 @@ impl ItemType {
         }
     }
 
-    pub fn random_unique(count: usize, owned_charms: &[Charm]) -> Vec<Self> {
-        let mut rng = rand::rng();
+    /// Rolls `count` distinct offers, none of them in `excluded` (e.g. items already locked in the
+    /// shop). Normally the first slot is a boost and the rest are charms, but with
+    /// `guarantee_charm` the boost slot is given over to a charm as long as there is an unowned
+    /// charm to offer.
+    pub fn random_unique(
+        count: usize,
+        game_state: &GameState,
+        config: &GameConfig,
+        guarantee_charm: bool,
+        excluded: &[ItemType],
+        rng: &mut impl Rng,
+    ) -> Vec<Self> {
         let mut items = Vec::with_capacity(count);
+        let excluded_charms: Vec<Charm> = game_state
+            .charms
+            .iter()
+            .copied()
+            .chain(excluded.iter().filter_map(|item| match item {
+                ItemType::Charm(charm) => Some(*charm),
+                ItemType::Boost(_) => None,
+            }))
+            .collect();
 
-        let boosts = [
-            Boost::BlueSheep,
-            Boost::RedSheep,
-            // Boost::BarkPower,
-            Boost::MaxCharms,
-        ];
-        let boost_idx = rng.random_range(0..boosts.len());
-        items.push(ItemType::Boost(boosts[boost_idx]));
-
-        let charm_pool = [
-            Charm::GoldenSheep,
-            Charm::HalfTimeDoubleSheep,
-            Charm::ChanceBlueOnBuy,
-            Charm::ChanceRedOnBuy,
-            Charm::Exponential,
-            // Charm::WellTrained,
-            Charm::Evolution,
-            Charm::Cloning,
-            Charm::ShopCount,
-            Charm::Ink,
-            Charm::RedToGold,
-            Charm::BlackInc,
-        ];
-        let available_charms: Vec<Charm> = charm_pool
+        let any_charm_available = Charm::ALL
             .into_iter()
-            .filter(|charm| !owned_charms.contains(charm))
-            .collect();
+            .any(|charm| charm.in_shop_pool() && !excluded_charms.contains(&charm));
 
-        while items.len() < count && items.len() - 1 < available_charms.len() {
-            let charm_idx = rng.random_range(0..available_charms.len());
-            let next = ItemType::Charm(available_charms[charm_idx]);
-            if !items.contains(&next) {
-                items.push(next);
+        if !guarantee_charm || !any_charm_available {
+            let boosts: Vec<Boost> = [
+                Boost::BlueSheep,
+                Boost::RedSheep,
+                Boost::GreenSheep,
+                // Boost::BarkPower,
+                Boost::MaxCharms,
+                Boost::Insurance,
+                Boost::Recolor,
+            ]
+            .into_iter()
+            .filter(|boost| {
+                boost.in_shop_pool(game_state, config)
+                    && !excluded.contains(&ItemType::Boost(*boost))
+            })
+            .collect();
+            if count > 0 && !boosts.is_empty() {
+                let boost_idx = rng.random_range(0..boosts.len());
+                items.push(ItemType::Boost(boosts[boost_idx]));
             }
         }
+        let charm_count = count.saturating_sub(items.len());
+        items.extend(
+            Charm::random_unique(charm_count, &excluded_charms, rng)
+                .into_iter()
+                .map(ItemType::Charm),
+        );
 
         items
     }
@@ -86,26 +126,130 @@ impl ItemType {
 pub enum Boost {
     BlueSheep,
     RedSheep,
+    GreenSheep,
     BarkPower,
     MaxCharms,
+    Insurance,
+    /// Handled by the recolor picker in the shop UI rather than [`Boost::apply`], since the
+    /// player picks which colors to swap.
+    Recolor,
 }
 
+/// How much [`Charm::Echo`] grows the bark radius for each sheep counted this round.
+pub const ECHO_BARK_RADIUS_PER_SHEEP: f32 = 0.1;
+/// The most [`Charm::Echo`] can grow the bark radius by in one round.
+pub const ECHO_MAX_BARK_RADIUS_BONUS: f32 = 2.5;
+/// Points over the target that [`Charm::Overflow`] turns into one coin.
+pub const OVERFLOW_POINTS_PER_COIN: u32 = 3;
+/// The most [`Charm::Overflow`] pays out in one round, so a late red sheep multiplying a big score
+/// doesn't pay out a fortune.
+pub const OVERFLOW_MAX_COINS: u32 = 5;
+
+/// [`Charm::Evolution`] turns every this many white sheep counted into a blue one.
+pub const EVOLUTION_WHITE_SHEEP_PER_BLUE: u16 = 5;
+
 impl Boost {
+    pub const ALL: [Boost; 7] = [
+        Boost::BlueSheep,
+        Boost::RedSheep,
+        Boost::GreenSheep,
+        Boost::BarkPower,
+        Boost::MaxCharms,
+        Boost::Insurance,
+        Boost::Recolor,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             Boost::BlueSheep => "Blue Sheep",
             Boost::RedSheep => "Red Sheep",
+            Boost::GreenSheep => "Green Sheep",
             Boost::BarkPower => "Bark Power",
             Boost::MaxCharms => "Dream Catcher",
+            Boost::Insurance => "Abduction Insurance",
+            Boost::Recolor => "Sheep Dye",
+        }
+    }
+
+    /// Where the shop icon is, under `assets/`.
+    pub fn icon_path(&self) -> &'static str {
+        match self {
+            Boost::BlueSheep => "images/icons/boosts/blue_sheep.png",
+            Boost::RedSheep => "images/icons/boosts/red_sheep.png",
+            Boost::GreenSheep => "images/icons/boosts/green_sheep.png",
+            Boost::BarkPower => "images/icons/boosts/bark_power.png",
+            Boost::MaxCharms => "images/icons/boosts/max_charms.png",
+            Boost::Insurance => "images/icons/boosts/insurance.png",
+            Boost::Recolor => "images/icons/boosts/recolor.png",
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    pub fn description(&self, config: &GameConfig) -> Cow<'static, str> {
         match self {
-            Boost::BlueSheep => "Apply blue wool to one of your sheep (5 points)",
-            Boost::RedSheep => "Apply red wool to one of your sheep (points x1.5)",
-            Boost::BarkPower => "Your bark affects sheep in a wider area.",
-            Boost::MaxCharms => "1 in 4 chance to increase the maximum number of charms.",
+            Boost::BlueSheep => {
+                format!("Apply blue wool to one of your sheep ({BLUE_SHEEP_POINTS} points)").into()
+            }
+            Boost::RedSheep => "Apply red wool to one of your sheep (points x1.5)".into(),
+            Boost::GreenSheep => format!(
+                "Apply green wool to one of your sheep (+{} seconds)",
+                GREEN_SHEEP_BONUS.as_secs()
+            )
+            .into(),
+            Boost::BarkPower => "Your bark affects sheep in a wider area.".into(),
+            Boost::MaxCharms => format!(
+                "1 in {} chance to increase the maximum number of charms.",
+                config.shop.max_charms_chance_denominator
+            )
+            .into(),
+            Boost::Insurance => format!(
+                "Get 1 money back for each sheep abducted by a UFO. Stacks {} times.",
+                config.shop.max_insurance_level
+            )
+            .into(),
+            Boost::Recolor => "Permanently change the color of one of your sheep.".into(),
+        }
+    }
+
+    /// See [`ItemType::details`].
+    pub fn details(&self, state: &GameState, config: &GameConfig) -> String {
+        match self {
+            Boost::BlueSheep => format!(
+                "+1 blue sheep, worth {BLUE_SHEEP_POINTS} points (owned: {})",
+                state.blue_sheep_count
+            ),
+            Boost::RedSheep => format!(
+                "+1 red sheep, points x1.5 (owned: {})",
+                state.red_sheep_count
+            ),
+            Boost::GreenSheep => format!(
+                "+1 green sheep, adds {} seconds to the clock (owned: {})",
+                GREEN_SHEEP_BONUS.as_secs(),
+                state.green_sheep_count
+            ),
+            Boost::BarkPower => format!(
+                "+{:.1} bark radius (current: {:.1})",
+                config.shop.bark_radius_per_boost, state.player_bark_radius
+            ),
+            Boost::MaxCharms => format!(
+                "1-in-{} chance of +1 charm slot (current: {})",
+                config.shop.max_charms_chance_denominator, state.max_charms
+            ),
+            Boost::Insurance => format!(
+                "+1 money per abducted sheep (level {}/{})",
+                state.insurance_level, config.shop.max_insurance_level
+            ),
+            Boost::Recolor => [
+                SheepColor::White,
+                SheepColor::Blue,
+                SheepColor::Red,
+                SheepColor::Black,
+                SheepColor::Gold,
+                SheepColor::Green,
+            ]
+            .iter()
+            .map(|color| format!("{}: {}", color.name(), state.sheep_of_color(color)))
+            .collect::<Vec<_>>()
+            .join(", "),
         }
     }
 
@@ -113,22 +257,39 @@ impl Boost {
         match self {
             Boost::BlueSheep => 2,
             Boost::RedSheep => 2,
+            Boost::GreenSheep => 2,
             Boost::BarkPower => 2,
             Boost::MaxCharms => 3,
+            Boost::Insurance => 3,
+            Boost::Recolor => 3,
+        }
+    }
+
+    /// Whether the boost can still be offered. Insurance stops showing up once maxed out.
+    pub fn in_shop_pool(&self, state: &GameState, config: &GameConfig) -> bool {
+        match self {
+            Boost::Insurance => state.insurance_level < config.shop.max_insurance_level,
+            _ => true,
         }
     }
 
-    pub fn apply(&self, state: &mut GameState) {
+    pub fn apply(&self, state: &mut GameState, config: &GameConfig) {
         match self {
             Boost::BlueSheep => state.blue_sheep_count += 1,
             Boost::RedSheep => state.red_sheep_count += 1,
-            Boost::BarkPower => state.player_bark_radius += 1.0,
+            Boost::GreenSheep => state.green_sheep_count += 1,
+            Boost::BarkPower => state.player_bark_radius += config.shop.bark_radius_per_boost,
             Boost::MaxCharms => {
                 let rng = &mut rand::rng();
-                if rng.random_ratio(1, 4) {
+                if rng.random_ratio(1, config.shop.max_charms_chance_denominator) {
                     state.max_charms += 1;
                 }
             }
+            Boost::Insurance => {
+                state.insurance_level =
+                    (state.insurance_level + 1).min(config.shop.max_insurance_level);
+            }
+            Boost::Recolor => {}
         }
     }
 }
@@ -148,9 +309,83 @@ pub enum Charm {
     Ink,
     RedToGold,
     BlackInc,
+    Vacuum,
+    Regular,
+    Clairvoyant,
+    TimeBonus,
+    Echo,
+    Overflow,
 }
 
 impl Charm {
+    /// Rolls up to `count` distinct charms from the shop pool, leaving out `excluded`. Returns
+    /// fewer if the pool runs out.
+    pub fn random_unique(count: usize, excluded: &[Charm], rng: &mut impl Rng) -> Vec<Charm> {
+        let available: Vec<Charm> = Charm::ALL
+            .into_iter()
+            .filter(|charm| charm.in_shop_pool() && !excluded.contains(charm))
+            .collect();
+        let mut charms = Vec::with_capacity(count);
+        while charms.len() < count && charms.len() < available.len() {
+            let next = available[rng.random_range(0..available.len())];
+            if !charms.contains(&next) {
+                charms.push(next);
+            }
+        }
+        charms
+    }
+
+    /// Every charm, in declaration order. A test checks this against the enum's variants.
+    pub const ALL: [Charm; 19] = [
+        Charm::GoldenSheep,
+        Charm::HalfTimeDoubleSheep,
+        Charm::ChanceBlueOnBuy,
+        Charm::ChanceRedOnBuy,
+        Charm::Exponential,
+        Charm::WellTrained,
+        Charm::DoubleCountRadius,
+        Charm::Evolution,
+        Charm::Cloning,
+        Charm::ShopCount,
+        Charm::Ink,
+        Charm::RedToGold,
+        Charm::BlackInc,
+        Charm::Vacuum,
+        Charm::Regular,
+        Charm::Clairvoyant,
+        Charm::TimeBonus,
+        Charm::Echo,
+        Charm::Overflow,
+    ];
+
+    /// Whether the shop can offer this charm. Deliberately not a wildcard match, so every new
+    /// charm has to be explicitly added to or left out of the pool.
+    pub fn in_shop_pool(&self) -> bool {
+        match self {
+            Charm::GoldenSheep
+            | Charm::HalfTimeDoubleSheep
+            | Charm::ChanceBlueOnBuy
+            | Charm::ChanceRedOnBuy
+            | Charm::Exponential
+            | Charm::Evolution
+            | Charm::Cloning
+            | Charm::ShopCount
+            | Charm::Ink
+            | Charm::RedToGold
+            | Charm::BlackInc
+            | Charm::Vacuum
+            | Charm::Regular
+            | Charm::Clairvoyant
+            | Charm::TimeBonus
+            | Charm::Echo
+            | Charm::Overflow => true,
+            // Its effect makes herding too easy.
+            Charm::WellTrained => false,
+            // Has no effect yet.
+            Charm::DoubleCountRadius => false,
+        }
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             Charm::GoldenSheep => "Golden Sheep",
@@ -166,19 +401,62 @@ impl Charm {
             Charm::Ink => "Ink",
             Charm::RedToGold => "Rose Gold",
             Charm::BlackInc => "Strength in Numbers",
+            Charm::Vacuum => "Vacuum",
+            Charm::Regular => "Regular",
+            Charm::Clairvoyant => "Clairvoyant",
+            Charm::TimeBonus => "Early Bird",
+            Charm::Echo => "Echo",
+            Charm::Overflow => "Overflow",
         }
     }
 
-    pub fn description(&self) -> &'static str {
+    /// Where the shop icon is, under `assets/`.
+    pub fn icon_path(&self) -> &'static str {
         match self {
+            Charm::GoldenSheep => "images/icons/charms/golden_sheep.png",
+            Charm::HalfTimeDoubleSheep => "images/icons/charms/half_time_double_sheep.png",
+            Charm::ChanceBlueOnBuy => "images/icons/charms/chance_blue_on_buy.png",
+            Charm::ChanceRedOnBuy => "images/icons/charms/chance_red_on_buy.png",
+            Charm::Exponential => "images/icons/charms/exponential.png",
+            Charm::WellTrained => "images/icons/charms/well_trained.png",
+            Charm::DoubleCountRadius => "images/icons/charms/double_count_radius.png",
+            Charm::Evolution => "images/icons/charms/evolution.png",
+            Charm::Cloning => "images/icons/charms/cloning.png",
+            Charm::ShopCount => "images/icons/charms/shop_count.png",
+            Charm::Ink => "images/icons/charms/ink.png",
+            Charm::RedToGold => "images/icons/charms/red_to_gold.png",
+            Charm::BlackInc => "images/icons/charms/black_inc.png",
+            Charm::Vacuum => "images/icons/charms/vacuum.png",
+            Charm::Regular => "images/icons/charms/regular.png",
+            Charm::Clairvoyant => "images/icons/charms/clairvoyant.png",
+            Charm::TimeBonus => "images/icons/charms/time_bonus.png",
+            Charm::Echo => "images/icons/charms/echo.png",
+            Charm::Overflow => "images/icons/charms/overflow.png",
+        }
+    }
+
+    pub fn description(&self, config: &GameConfig) -> Cow<'static, str> {
+        let description = match self {
             Charm::GoldenSheep => "Spawn a golden sheep that gives 1 money when counted.",
             Charm::HalfTimeDoubleSheep => {
-                "Spawn two of every sheep in your flock, but reduces the timer by 20s."
+                return format!(
+                    "Spawn two of every sheep in your flock, but reduces the timer by {}s.",
+                    config.round.half_time_penalty_seconds
+                )
+                .into();
             }
-            Charm::ChanceBlueOnBuy => {
-                "Each time you buy a sheep it has a 1 in 4 chance to be blue."
+            Charm::ChanceBlueOnBuy | Charm::ChanceRedOnBuy => {
+                let color = if *self == Charm::ChanceBlueOnBuy {
+                    "blue"
+                } else {
+                    "red"
+                };
+                return format!(
+                    "Each time you buy a sheep it has a 1 in {} chance to be {color}.",
+                    config.shop.color_on_buy_chance_denominator
+                )
+                .into();
             }
-            Charm::ChanceRedOnBuy => "Each time you buy a sheep it has a 1 in 4 chance to be red.",
             Charm::Exponential => {
                 "When a black sheep is counted, spawn two new black sheep at random locations."
             }
@@ -196,7 +474,75 @@ impl Charm {
             Charm::BlackInc => {
                 "When a black sheep is counted, all future black sheep are worth +1 points this round."
             }
-        }
+            Charm::Vacuum => {
+                "When you reach the points target, any gold sheep left in the field are counted automatically."
+            }
+            Charm::Regular => {
+                "The shop always offers charms you don't own in place of a boost, if there are any left."
+            }
+            Charm::Clairvoyant => "See which dream modifiers will be offered next round.",
+            Charm::TimeBonus => {
+                return format!(
+                    "When you reach the points target early, get 1 money for every {} seconds \
+                     left.",
+                    config.shop.time_bonus_seconds_per_coin
+                )
+                .into();
+            }
+            Charm::Echo => "Your bark reaches further for every sheep counted this round.",
+            Charm::Overflow => "When the round ends, points over the target are turned into money.",
+        };
+        description.into()
+    }
+
+    /// See [`ItemType::details`]. `None` when the description already says it all.
+    pub fn details(&self, state: &GameState, config: &GameConfig) -> Option<String> {
+        let details = match self {
+            Charm::GoldenSheep => format!(
+                "+1 gold sheep, worth 1 money (owned: {})",
+                state.gold_sheep_count
+            ),
+            Charm::HalfTimeDoubleSheep => format!(
+                "Flock each round: {} -> {} sheep",
+                state.sheep_count,
+                state.sheep_count * 2
+            ),
+            Charm::ChanceBlueOnBuy | Charm::ChanceRedOnBuy => format!(
+                "{}% chance per sheep bought",
+                100 / config.shop.color_on_buy_chance_denominator
+            ),
+            Charm::Exponential => "+2 black sheep per black sheep counted".to_string(),
+            Charm::Evolution => format!(
+                "White sheep: 1 -> 0 points. Every {EVOLUTION_WHITE_SHEEP_PER_BLUE}th: +1 blue sheep"
+            ),
+            Charm::Cloning => "+1 permanent sheep per round".to_string(),
+            Charm::Ink => format!(
+                "Black sheep chance: {}% -> {}%",
+                BLACK_SHEEP_CHANCE * 100.0,
+                INK_BLACK_SHEEP_CHANCE * 100.0
+            ),
+            Charm::BlackInc => "Black sheep: +1 point for each one already counted".to_string(),
+            Charm::TimeBonus => format!(
+                "+1 money per {} seconds left when the round ends",
+                config.shop.time_bonus_seconds_per_coin
+            ),
+            Charm::Echo => format!(
+                "+{ECHO_BARK_RADIUS_PER_SHEEP} bark radius per sheep counted, up to \
+                 +{ECHO_MAX_BARK_RADIUS_BONUS}"
+            ),
+            Charm::Overflow => format!(
+                "+1 money per {OVERFLOW_POINTS_PER_COIN} points over the target, up to \
+                 {OVERFLOW_MAX_COINS} a round"
+            ),
+            Charm::WellTrained
+            | Charm::DoubleCountRadius
+            | Charm::ShopCount
+            | Charm::RedToGold
+            | Charm::Vacuum
+            | Charm::Regular
+            | Charm::Clairvoyant => return None,
+        };
+        Some(details)
     }
 
     pub fn price(&self) -> u32 {
@@ -214,6 +560,94 @@ impl Charm {
             Charm::Ink => 3,
             Charm::RedToGold => 4,
             Charm::BlackInc => 4,
+            Charm::Vacuum => 3,
+            Charm::Regular => 3,
+            Charm::Clairvoyant => 3,
+            Charm::TimeBonus => 4,
+            Charm::Echo => 3,
+            Charm::Overflow => 4,
+        }
+    }
+
+    /// What selling the charm refunds: half its price, rounded down.
+    pub fn sell_price(&self) -> u32 {
+        self.price() / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::{Enum, TypeInfo, Typed, VariantInfo};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    /// Charms deliberately kept out of the shop, along with why.
+    const EXCLUDED_FROM_SHOP: [Charm; 2] = [
+        // Its effect makes herding too easy.
+        Charm::WellTrained,
+        // Has no effect yet.
+        Charm::DoubleCountRadius,
+    ];
+
+    /// Every variant of `T`, as listed by its `Reflect` derive rather than by hand.
+    fn variant_names<T: Typed>() -> Vec<&'static str> {
+        let TypeInfo::Enum(info) = T::type_info() else {
+            panic!("not an enum");
+        };
+        info.iter().map(VariantInfo::name).collect()
+    }
+
+    #[test]
+    fn all_lists_every_variant() {
+        let charms: Vec<_> = Charm::ALL.iter().map(Enum::variant_name).collect();
+        assert_eq!(charms, variant_names::<Charm>());
+        let boosts: Vec<_> = Boost::ALL.iter().map(Enum::variant_name).collect();
+        assert_eq!(boosts, variant_names::<Boost>());
+    }
+
+    #[test]
+    fn charm_pool_matches_the_allowlist() {
+        for charm in Charm::ALL {
+            assert_eq!(
+                charm.in_shop_pool(),
+                !EXCLUDED_FROM_SHOP.contains(&charm),
+                "{charm:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn every_icon_file_exists() {
+        let items = Boost::ALL
+            .into_iter()
+            .map(ItemType::Boost)
+            .chain(Charm::ALL.into_iter().map(ItemType::Charm));
+        for item in items {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("assets")
+                .join(item.icon_path());
+            assert!(path.is_file(), "{item:?} has no icon at {}", path.display());
+        }
+    }
+
+    #[test]
+    fn time_bonus_description_uses_the_payout_rate() {
+        let mut config = GameConfig::default();
+        config.shop.time_bonus_seconds_per_coin = 7;
+        let description = Charm::TimeBonus.description(&config);
+        assert!(
+            description.ends_with("1 money for every 7 seconds left."),
+            "{description}"
+        );
+    }
+
+    #[test]
+    fn every_pooled_charm_can_be_offered() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let offered = Charm::random_unique(Charm::ALL.len(), &[], &mut rng);
+        for charm in Charm::ALL {
+            assert_eq!(offered.contains(&charm), charm.in_shop_pool(), "{charm:?}");
         }
     }
 }
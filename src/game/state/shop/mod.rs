@@ -1,14 +1,31 @@
-use bevy::prelude::*;
+use std::collections::HashMap;
+
+use bevy::{
+    asset::RenderAssetUsages,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use rand::Rng;
 
 use crate::{
+    asset_tracking::LoadResource,
     audio::BgmConfig,
-    game::state::{
-        GamePhase, GameState,
-        shop::{
-            items::{Charm, ItemType},
-            ui::redraw_shop_ui,
+    game::{
+        config::GameConfig,
+        modifiers::ModifierDifficulty,
+        rng::GameRng,
+        state::{
+            GamePhase, GameState,
+            shop::{
+                items::{Boost, Charm, ItemType},
+                ui::{
+                    RecolorChoice, redraw_shop_ui, show_placeholder_icons, update_recolor_status,
+                },
+            },
         },
     },
+    settings::GameplaySettings,
+    theme::{palette::CARD_BORDER, widget},
 };
 
 pub mod items;
@@ -17,35 +34,310 @@ mod ui;
 #[derive(Debug, Resource, Default)]
 pub struct ShopOffers {
     pub items: Vec<Option<ItemType>>,
+    /// Which slots are locked, so rerolling leaves their item alone. Lines up with `items`.
+    pub locked: Vec<bool>,
+    /// How many times the charms have been rerolled on their own during this shop visit.
+    pub charm_rerolls: u32,
 }
 
 impl ShopOffers {
-    pub fn reroll(&mut self, owned_charms: &[items::Charm], count: usize) {
-        self.items = ItemType::random_unique(count, owned_charms)
+    /// Fills the shop with `count` new offers, keeping the items in locked slots where they are.
+    /// Locked items aren't offered a second time. If there aren't enough items to go round, the
+    /// shop ends up with fewer slots.
+    pub fn reroll(
+        &mut self,
+        game_state: &GameState,
+        config: &GameConfig,
+        count: usize,
+        rng: &mut impl Rng,
+    ) {
+        let guarantee_charm = game_state.is_charm_active(items::Charm::Regular);
+        let kept = self.locked_items();
+        let excluded: Vec<ItemType> = kept.iter().map(|(_, item)| *item).collect();
+        let mut new_items = ItemType::random_unique(
+            count.saturating_sub(kept.len()),
+            game_state,
+            config,
+            guarantee_charm,
+            &excluded,
+            rng,
+        )
+        .into_iter();
+
+        let mut items = Vec::with_capacity(count);
+        let mut locked = Vec::with_capacity(count);
+        for slot in 0..count {
+            if let Some((_, item)) = kept.iter().find(|(kept_slot, _)| *kept_slot == slot) {
+                items.push(Some(*item));
+                locked.push(true);
+            } else if let Some(item) = new_items.next() {
+                items.push(Some(item));
+                locked.push(false);
+            }
+        }
+        self.items = items;
+        self.locked = locked;
+    }
+
+    /// Replaces the unlocked charms on offer with new ones, leaving boosts, locked and bought
+    /// slots alone. If there aren't enough unowned charms to go round, the remaining slots keep
+    /// their current charm.
+    pub fn reroll_charms_only(&mut self, owned_charms: &[items::Charm], rng: &mut impl Rng) {
+        let charm_slots: Vec<usize> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(slot, item)| {
+                matches!(item, Some(ItemType::Charm(_))) && !self.is_locked(*slot)
+            })
+            .map(|(slot, _)| slot)
+            .collect();
+        let excluded: Vec<Charm> = owned_charms
+            .iter()
+            .copied()
+            .chain(
+                self.locked_items()
+                    .into_iter()
+                    .filter_map(|(_, item)| match item {
+                        ItemType::Charm(charm) => Some(charm),
+                        ItemType::Boost(_) => None,
+                    }),
+            )
+            .collect();
+        let new_charms = Charm::random_unique(charm_slots.len(), &excluded, rng);
+        for (slot, charm) in charm_slots.into_iter().zip(new_charms) {
+            self.items[slot] = Some(ItemType::Charm(charm));
+        }
+        self.charm_rerolls += 1;
+    }
+
+    /// Rerolling just the charms starts at [`CHARM_REROLL_BASE_PRICE`] and costs one more each
+    /// time in the same shop visit.
+    pub fn charm_reroll_price(&self) -> u32 {
+        CHARM_REROLL_BASE_PRICE + self.charm_rerolls
+    }
+
+    /// Whether there are any unlocked charms for [`ShopOffers::reroll_charms_only`] to replace.
+    pub fn has_charm_offers(&self) -> bool {
+        self.items
+            .iter()
+            .enumerate()
+            .any(|(slot, item)| matches!(item, Some(ItemType::Charm(_))) && !self.is_locked(slot))
+    }
+
+    pub fn is_locked(&self, slot: usize) -> bool {
+        self.locked.get(slot).copied().unwrap_or(false)
+    }
+
+    pub fn toggle_lock(&mut self, slot: usize) {
+        if let Some(locked) = self.locked.get_mut(slot) {
+            *locked = !*locked;
+        }
+    }
+
+    /// The items in locked slots, with their slot.
+    fn locked_items(&self) -> Vec<(usize, ItemType)> {
+        self.items
+            .iter()
+            .enumerate()
+            .filter(|(slot, _)| self.is_locked(*slot))
+            .filter_map(|(slot, item)| item.map(|item| (slot, item)))
+            .collect()
+    }
+}
+
+/// Money beyond a cap is turned into extra sheep when entering the shop, so late-game savings
+/// aren't wasted. The cap is high enough that normal play never reaches it.
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct MoneyCapConfig {
+    pub cap: u32,
+    /// Every this much money over the cap becomes one sheep. Any remainder is kept.
+    pub money_per_sheep: u32,
+}
+
+impl Default for MoneyCapConfig {
+    fn default() -> Self {
+        Self {
+            cap: 40,
+            money_per_sheep: 10,
+        }
+    }
+}
+
+/// Icons for the shop's items, loaded from [`ItemType::icon_path`].
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct ShopAssets {
+    // Not dependencies, so a missing icon can't hold up loading. It shows the placeholder instead.
+    icons: HashMap<ItemType, Handle<Image>>,
+    /// Shown in place of an icon that's still loading, or missing or failed to load.
+    pub placeholder_icon: Handle<Image>,
+}
+
+impl ShopAssets {
+    pub fn icon(&self, item: ItemType) -> Handle<Image> {
+        self.icons
+            .get(&item)
+            .cloned()
+            .unwrap_or_else(|| self.placeholder_icon.clone())
+    }
+}
+
+impl FromWorld for ShopAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        let icons = Boost::ALL
             .into_iter()
-            .map(Some)
+            .map(ItemType::Boost)
+            .chain(Charm::ALL.into_iter().map(ItemType::Charm))
+            .map(|item| (item, assets.load(item.icon_path())))
             .collect();
+        let placeholder_icon = world.resource_mut::<Assets<Image>>().add(Image::new_fill(
+            Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &CARD_BORDER.to_srgba().to_u8_array(),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        ));
+        Self {
+            icons,
+            placeholder_icon,
+        }
     }
 }
 
+const CHARM_REROLL_BASE_PRICE: u32 = 2;
+
+/// How many items the shop offers, before [`Charm::ShopCount`] adds one more.
+const BASE_SHOP_OFFERS: usize = 3;
+
+/// Money for each hard modifier active on entering the shop beyond the first, rewarding the risk
+/// of stacking them.
+const HARD_MODIFIER_COMBO_MONEY: u32 = 2;
+
+const SHOP_TOAST_SECS: f32 = 3.0;
+
+/// A short notice at the top of the shop, removed once its timer runs out.
+#[derive(Component, Debug)]
+struct ShopToast(Timer);
+
 pub(super) fn plugin(app: &mut App) {
+    app.load_resource::<ShopAssets>();
     app.insert_resource(ShopOffers::default());
+    app.init_resource::<MoneyCapConfig>();
     app.add_systems(OnEnter(GamePhase::Shop), on_shop);
-    app.add_systems(Update, redraw_shop_ui.run_if(in_state(GamePhase::Shop)));
+    app.add_systems(
+        Update,
+        (
+            redraw_shop_ui,
+            tick_shop_toasts,
+            show_placeholder_icons,
+            update_recolor_status.run_if(resource_exists_and_changed::<RecolorChoice>),
+        )
+            .run_if(in_state(GamePhase::Shop)),
+    );
 }
 
 fn on_shop(
+    mut commands: Commands,
     mut shop_offers: ResMut<ShopOffers>,
     mut bgm_config: ResMut<BgmConfig>,
-    game_state: Res<GameState>,
+    mut game_state: ResMut<GameState>,
+    mut rng: ResMut<GameRng>,
+    money_cap: Res<MoneyCapConfig>,
+    settings: Res<GameplaySettings>,
+    config: Res<GameConfig>,
 ) {
+    let mut toasts = Vec::new();
+    let combo_bonus = hard_modifier_combo_bonus(&game_state);
+    if combo_bonus > 0 {
+        game_state.money += combo_bonus;
+        toasts.push(format!("Nightmare combo: +{combo_bonus} money"));
+    }
+    let extra_sheep = convert_money_overflow(&mut game_state, &money_cap);
+    if extra_sheep > 0 {
+        toasts.push(format!(
+            "Savings over {} traded for {extra_sheep} extra sheep",
+            money_cap.cap
+        ));
+    }
+    for (row, text) in toasts.into_iter().enumerate() {
+        commands.spawn(shop_toast(text, row));
+    }
+
+    // Just the base layer, so the shop is calmer than herding.
     bgm_config.base_enabled = true;
-    bgm_config.extra_enabled = true;
+    bgm_config.extra_enabled = false;
     bgm_config.percussion_enabled = false;
     let count = if game_state.is_charm_active(Charm::ShopCount) {
-        4
+        BASE_SHOP_OFFERS + 1
     } else {
-        3
+        BASE_SHOP_OFFERS
     };
-    shop_offers.reroll(&game_state.charms, count);
+    // Locks only last for one shop visit.
+    shop_offers.locked.clear();
+    shop_offers.reroll(&game_state, &config, count, &mut *rng);
+    shop_offers.charm_rerolls = 0;
+    // Always rolled, even without Clairvoyant, so a seeded run makes the same choices either way.
+    game_state.roll_next_modifier_choices(&settings.banned_modifiers, &mut *rng);
+}
+
+/// Bonus money for having two or more hard modifiers active at once, growing with each extra one.
+fn hard_modifier_combo_bonus(game_state: &GameState) -> u32 {
+    let hard_count = game_state
+        .active_modifiers
+        .iter()
+        .filter(|active| matches!(active.modifier.difficulty(), ModifierDifficulty::Hard))
+        .count() as u32;
+    hard_count.saturating_sub(1) * HARD_MODIFIER_COMBO_MONEY
+}
+
+fn shop_toast(text: String, row: usize) -> impl Bundle {
+    (
+        Name::new("Shop Toast"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            top: px(24.0 + 40.0 * row as f32),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(10),
+        Pickable::IGNORE,
+        DespawnOnExit(GamePhase::Shop),
+        ShopToast(Timer::from_seconds(SHOP_TOAST_SECS, TimerMode::Once)),
+        children![widget::hud_label(text)],
+    )
+}
+
+/// Trades whole multiples of `money_per_sheep` above the cap for sheep, returning how many were
+/// added.
+fn convert_money_overflow(game_state: &mut GameState, config: &MoneyCapConfig) -> u32 {
+    if config.money_per_sheep == 0 {
+        return 0;
+    }
+    let overflow = game_state.money.saturating_sub(config.cap);
+    let extra_sheep = overflow / config.money_per_sheep;
+    game_state.money -= extra_sheep * config.money_per_sheep;
+    game_state.sheep_count = game_state
+        .sheep_count
+        .saturating_add(extra_sheep.try_into().unwrap_or(u16::MAX));
+    extra_sheep
+}
+
+fn tick_shop_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut ShopToast)>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        if toast.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
 }
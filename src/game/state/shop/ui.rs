@@ -1,14 +1,19 @@
-use bevy::{math::ops::floor, prelude::*};
+use std::borrow::Cow;
+
+use bevy::prelude::*;
 use rand::Rng;
 
 use crate::{
     game::{
+        config::GameConfig,
         modifiers::Modifier,
+        rng::GameRng,
+        sheep::SheepColor,
         state::{
-            GamePhase, GameState,
+            ActiveModifier, GamePhase, GameState,
             shop::{
-                ShopOffers,
-                items::{Charm, ItemType},
+                BASE_SHOP_OFFERS, ShopAssets, ShopOffers,
+                items::{Boost, Charm, ItemType},
             },
         },
     },
@@ -18,17 +23,58 @@ use crate::{
     },
 };
 
+/// Size of the icon at the top of item and charm cards.
+const ICON_SIZE: f32 = 48.0;
+
+/// Border of an offer that's locked in place, so rerolling won't replace it.
+const LOCKED_CARD_BORDER: Color = Color::srgb(0.902, 0.627, 0.157);
+
 #[derive(Component)]
 pub struct ShopUiRoot;
 
-pub fn draw_shop_ui(mut commands: Commands, game_state: &GameState, shop_offers: &ShopOffers) {
+pub fn draw_shop_ui(
+    mut commands: Commands,
+    game_state: &GameState,
+    shop_offers: &ShopOffers,
+    shop_assets: &ShopAssets,
+    config: &GameConfig,
+) {
     let active_modifiers = game_state.active_modifiers.clone();
     let charms = game_state.charms.clone();
     let max_charms = game_state.max_charms;
     let money = game_state.money;
     let point_target = game_state.point_target;
-    let offers = shop_offers.items.clone();
+    let offers: Vec<_> = shop_offers
+        .items
+        .iter()
+        .map(|item| {
+            item.map(|item| {
+                (
+                    item,
+                    item.description(config),
+                    item_tooltip(item, game_state, config),
+                    shop_assets.icon(item),
+                )
+            })
+        })
+        .collect();
+    let charm_tooltips: Vec<_> = charms
+        .iter()
+        .map(|charm| {
+            (
+                charm.description(config),
+                charm_tooltip(*charm, game_state, config),
+                shop_assets.icon(ItemType::Charm(*charm)),
+            )
+        })
+        .collect();
+    let locked: Vec<bool> = (0..offers.len())
+        .map(|slot| shop_offers.is_locked(slot))
+        .collect();
+    let charm_reroll_price = shop_offers.charm_reroll_price();
     let charms_full = game_state.charms_full();
+    let charms_refund: u32 = charms.iter().map(Charm::sell_price).sum();
+    let fully_stocked = game_state.is_charm_active(Charm::ShopCount);
     let gold_sheep_count = if game_state.is_charm_active(Charm::GoldenSheep) {
         game_state.gold_sheep_count + 1
     } else {
@@ -38,145 +84,268 @@ pub fn draw_shop_ui(mut commands: Commands, game_state: &GameState, shop_offers:
         ShopUiRoot,
         widget::ui_root("Shop UI"),
         DespawnOnExit(GamePhase::Shop),
-        children![(
-            widget::panel(),
-            children![(
-                widget::columns(),
-                children![
-                    (
-                        Name::new("Left Column"),
-                        Node {
-                            min_width: px(250),
-                            flex_grow: 1.0,
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            row_gap: px(12),
-                            ..default()
-                        },
-                        children![
-                            widget::column_header("Modifiers"),
-                            (
-                                Node {
-                                    flex_direction: FlexDirection::Column,
-                                    row_gap: px(8),
-                                    ..default()
-                                },
-                                Children::spawn(SpawnIter(
-                                    active_modifiers.into_iter().map(modifier_card)
-                                ))
-                            ),
-                        ]
-                    ),
-                    (
-                        Name::new("Center Column"),
-                        Node {
-                            min_width: px(450),
-                            flex_grow: 2.0,
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            row_gap: px(12),
-                            border: UiRect::horizontal(px(1)),
-                            ..default()
-                        },
-                        BorderColor::all(LABEL_TEXT.with_alpha(0.35)),
-                        children![
-                            widget::header("Shop"),
-                            (
-                                widget::row(),
-                                children![
-                                    widget::label(format!("Money: {}", money)),
-                                    widget::button_medium("Reroll (1)", draw_new_items),
-                                ]
-                            ),
-                            (
-                                Node {
-                                    width: percent(100),
-                                    justify_content: JustifyContent::SpaceAround,
-                                    align_items: AlignItems::Center,
-                                    flex_direction: FlexDirection::Row,
-                                    flex_wrap: FlexWrap::Wrap,
-                                    column_gap: px(10),
-                                    row_gap: px(10),
-                                    ..default()
-                                },
-                                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
-                                    for (slot, item) in offers.into_iter().enumerate() {
-                                        match item {
-                                            Some(item) => {
-                                                parent.spawn(item_card(
-                                                    slot,
-                                                    item,
-                                                    money,
-                                                    charms_full,
-                                                ));
-                                            }
-                                            None => {
-                                                parent.spawn(bought_item_card());
+        children![
+            (
+                widget::panel(),
+                children![(
+                    widget::columns(),
+                    children![
+                        (
+                            Name::new("Left Column"),
+                            Node {
+                                min_width: px(250),
+                                flex_grow: 1.0,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: px(12),
+                                ..default()
+                            },
+                            children![
+                                widget::column_header("Modifiers"),
+                                (
+                                    Node {
+                                        flex_direction: FlexDirection::Column,
+                                        row_gap: px(8),
+                                        ..default()
+                                    },
+                                    Children::spawn(SpawnIter(
+                                        active_modifiers.into_iter().map(modifier_card)
+                                    ))
+                                ),
+                            ]
+                        ),
+                        (
+                            Name::new("Center Column"),
+                            Node {
+                                min_width: px(450),
+                                flex_grow: 2.0,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: px(12),
+                                border: UiRect::horizontal(px(1)),
+                                ..default()
+                            },
+                            BorderColor::all(LABEL_TEXT.with_alpha(0.35)),
+                            children![
+                                widget::header("Shop"),
+                                (
+                                    widget::row(),
+                                    children![
+                                        widget::label(format!("Money: {}", money)),
+                                        widget::button_medium("Reroll (1)", draw_new_items),
+                                        widget::button_medium(
+                                            format!("Reroll Charms ({charm_reroll_price})"),
+                                            reroll_charms,
+                                        ),
+                                    ]
+                                ),
+                                (
+                                    Node {
+                                        width: percent(100),
+                                        justify_content: JustifyContent::SpaceAround,
+                                        align_items: AlignItems::Center,
+                                        flex_direction: FlexDirection::Row,
+                                        flex_wrap: FlexWrap::Wrap,
+                                        column_gap: px(10),
+                                        row_gap: px(10),
+                                        ..default()
+                                    },
+                                    Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                                        for (slot, item) in offers.into_iter().enumerate() {
+                                            match item {
+                                                Some((item, description, tooltip, icon)) => {
+                                                    let mut card = parent.spawn((
+                                                        item_card(
+                                                            slot,
+                                                            item,
+                                                            description,
+                                                            icon,
+                                                            money,
+                                                            charms_full,
+                                                            locked[slot],
+                                                        ),
+                                                        tooltip,
+                                                    ));
+                                                    if fully_stocked && slot >= BASE_SHOP_OFFERS {
+                                                        card.with_child(fully_stocked_badge());
+                                                    }
+                                                }
+                                                None => {
+                                                    parent.spawn(bought_item_card());
+                                                }
                                             }
                                         }
-                                    }
-                                })),
-                            ),
-                            widget::divider(),
-                            widget::label("Sheep Count"),
-                            (
-                                widget::row(),
-                                children![
-                                    widget::label(format!("Total: {}", game_state.sheep_count)),
-                                    widget::label(format!("Blue: {}", game_state.blue_sheep_count)),
-                                    widget::label(format!("Red: {}", game_state.red_sheep_count)),
-                                    widget::label(format!("Gold: {}", gold_sheep_count)),
-                                ]
-                            ),
-                            widget::button_medium("Buy Sheep (1)", buy_sheep),
-                            widget::divider(),
-                            widget::label(format!("Points target: {}", point_target)),
-                            widget::button("Start", start_next_round)
-                        ]
-                    ),
-                    (
-                        Name::new("Right Column"),
-                        Node {
-                            min_width: px(250),
-                            flex_grow: 1.0,
-                            flex_direction: FlexDirection::Column,
-                            align_items: AlignItems::Center,
-                            row_gap: px(12),
-                            ..default()
-                        },
-                        children![
-                            widget::column_header(format!(
-                                "Charms ({}/{})",
-                                charms.len(),
-                                max_charms
-                            )),
-                            (
-                                Node {
-                                    flex_direction: FlexDirection::Column,
-                                    row_gap: px(8),
-                                    ..default()
-                                },
-                                Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
-                                    if charms.is_empty() {
-                                        parent.spawn(widget::label("No charms equipped"));
-                                        return;
-                                    }
-
-                                    for (slot, charm) in charms.into_iter().enumerate() {
-                                        parent.spawn(charm_card(slot, charm));
-                                    }
-                                })),
-                            ),
-                        ]
-                    ),
-                ]
-            )]
-        )],
+                                    })),
+                                ),
+                                widget::divider(),
+                                widget::label("Sheep Count"),
+                                (
+                                    widget::row(),
+                                    children![
+                                        widget::label(format!("Total: {}", game_state.sheep_count)),
+                                        widget::label(format!(
+                                            "Blue: {}",
+                                            game_state.blue_sheep_count
+                                        )),
+                                        widget::label(format!(
+                                            "Red: {}",
+                                            game_state.red_sheep_count
+                                        )),
+                                        widget::label(format!("Gold: {}", gold_sheep_count)),
+                                        widget::label(format!(
+                                            "Green: {}",
+                                            game_state.green_sheep_count
+                                        )),
+                                    ]
+                                ),
+                                widget::button_medium("Buy Sheep (1)", buy_sheep),
+                                widget::divider(),
+                                widget::label(format!("Points target: {}", point_target)),
+                                widget::label(format!(
+                                    "Flock value: ~{}",
+                                    game_state.flock_value()
+                                )),
+                                next_modifiers_preview(game_state),
+                                widget::button("Start", start_next_round)
+                            ]
+                        ),
+                        (
+                            Name::new("Right Column"),
+                            Node {
+                                min_width: px(250),
+                                flex_grow: 1.0,
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: px(12),
+                                ..default()
+                            },
+                            children![
+                                widget::column_header(format!(
+                                    "Charms ({}/{})",
+                                    charms.len(),
+                                    max_charms
+                                )),
+                                (
+                                    Node {
+                                        flex_direction: FlexDirection::Column,
+                                        row_gap: px(8),
+                                        ..default()
+                                    },
+                                    Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                                        if charms.is_empty() {
+                                            parent.spawn(widget::label("No charms equipped"));
+                                            return;
+                                        }
+
+                                        for (slot, (charm, (description, tooltip, icon))) in
+                                            charms.into_iter().zip(charm_tooltips).enumerate()
+                                        {
+                                            parent.spawn((
+                                                charm_card(slot, charm, description, icon),
+                                                tooltip,
+                                            ));
+                                        }
+                                        parent.spawn(widget::button_medium(
+                                            format!("Sell All (+{charms_refund})"),
+                                            open_sell_all_confirm,
+                                        ));
+                                    })),
+                                ),
+                            ]
+                        ),
+                    ]
+                )]
+            ),
+            widget::controls_footer(&[("P / Esc", "Pause")])
+        ],
     ));
 }
 
-fn charm_card(slot: usize, charm: Charm) -> impl Bundle {
-    let sell_price = floor(charm.price() as f32 / 2.0);
+/// With [`Charm::Clairvoyant`], a line naming the modifiers the next round will offer.
+fn next_modifiers_preview(game_state: &GameState) -> impl Bundle {
+    let preview = game_state
+        .next_modifier_choices
+        .as_ref()
+        .filter(|_| game_state.is_charm_active(Charm::Clairvoyant))
+        .map(|choices| {
+            let names: Vec<&str> = choices.iter().map(Modifier::name).collect();
+            format!("Next dream: {}", names.join(" or "))
+        });
+    let display = if preview.is_some() {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    (
+        widget::label(preview.unwrap_or_default()),
+        TextColor(CHARM_TEXT),
+        Node {
+            display,
+            ..default()
+        },
+    )
+}
+
+/// The exact effect of a shop offer, and whether it's affordable.
+fn item_tooltip(item: ItemType, game_state: &GameState, config: &GameConfig) -> Tooltip {
+    let price = format!("Price: {} (you have {})", item.price(), game_state.money);
+    Tooltip(match item.details(game_state, config) {
+        Some(details) => format!("{details}\n{price}"),
+        None => price,
+    })
+}
+
+fn charm_tooltip(charm: Charm, game_state: &GameState, config: &GameConfig) -> Tooltip {
+    let sell = format!("Sells for {}", charm.sell_price());
+    Tooltip(match charm.details(game_state, config) {
+        Some(details) => format!("{details}\n{sell}"),
+        None => sell,
+    })
+}
+
+/// The icon on an item or charm card, and the image it shows once that has loaded. Until then,
+/// or if it fails to load, the card shows [`ShopAssets::placeholder_icon`] instead.
+#[derive(Component)]
+pub struct ShopIcon(Handle<Image>);
+
+fn item_icon(icon: Handle<Image>) -> impl Bundle {
+    (
+        Name::new("Item Icon"),
+        ShopIcon(icon.clone()),
+        ImageNode::new(icon),
+        Node {
+            width: px(ICON_SIZE),
+            height: px(ICON_SIZE),
+            ..default()
+        },
+    )
+}
+
+/// Shows the placeholder on any card whose icon hasn't loaded, e.g. because its file is missing.
+pub fn show_placeholder_icons(
+    asset_server: Res<AssetServer>,
+    shop_assets: Res<ShopAssets>,
+    mut icons: Query<(&ShopIcon, &mut ImageNode)>,
+) {
+    for (icon, mut image) in &mut icons {
+        let shown = if asset_server.is_loaded(&icon.0) {
+            &icon.0
+        } else {
+            &shop_assets.placeholder_icon
+        };
+        if image.image != *shown {
+            image.image = shown.clone();
+        }
+    }
+}
+
+fn charm_card(
+    slot: usize,
+    charm: Charm,
+    description: Cow<'static, str>,
+    icon: Handle<Image>,
+) -> impl Bundle {
+    let sell_price = charm.sell_price();
 
     (
         Name::new(format!("Charm Card {}", charm.name())),
@@ -195,6 +364,7 @@ fn charm_card(slot: usize, charm: Charm) -> impl Bundle {
         BackgroundColor(CARD_BACKGROUND),
         BorderColor::all(CARD_BORDER),
         children![
+            item_icon(icon),
             (
                 Name::new("Charm Name"),
                 Text(charm.name().to_string()),
@@ -204,7 +374,7 @@ fn charm_card(slot: usize, charm: Charm) -> impl Bundle {
             ),
             (
                 Name::new("Charm Description"),
-                Text(charm.description().to_string()),
+                Text(description.into_owned()),
                 TextFont::from_font_size(14.0),
                 TextColor(ui_palette::LABEL_TEXT),
                 TextLayout::new_with_justify(Justify::Center),
@@ -219,7 +389,19 @@ fn charm_card(slot: usize, charm: Charm) -> impl Bundle {
     )
 }
 
-fn modifier_card(modifier: Modifier) -> impl Bundle {
+fn modifier_card(active: ActiveModifier) -> impl Bundle {
+    let ActiveModifier {
+        modifier,
+        rounds_left,
+        drafted,
+    } = active;
+    let rounds_left_text = if drafted {
+        "Drafted for the whole run".to_string()
+    } else if rounds_left == 1 {
+        "Last round".to_string()
+    } else {
+        format!("{rounds_left} rounds left")
+    };
     (
         Name::new(format!("Modifier Card {}", modifier.name())),
         Node {
@@ -250,6 +432,20 @@ fn modifier_card(modifier: Modifier) -> impl Bundle {
                 TextColor(ui_palette::LABEL_TEXT),
                 TextLayout::new_with_justify(Justify::Center),
             ),
+            (
+                Name::new("Modifier Intensity"),
+                Text(modifier.intensity_text().unwrap_or_default()),
+                TextFont::from_font_size(14.0),
+                TextColor(ui_palette::SUB_HEADER_TEXT),
+                TextLayout::new_with_justify(Justify::Center),
+            ),
+            (
+                Name::new("Modifier Rounds Left"),
+                Text(rounds_left_text),
+                TextFont::from_font_size(14.0),
+                TextColor(ui_palette::LABEL_TEXT),
+                TextLayout::new_with_justify(Justify::Center),
+            ),
         ],
     )
 }
@@ -262,20 +458,54 @@ fn draw_new_items(
     _: On<Pointer<Click>>,
     mut game_state: ResMut<GameState>,
     mut shop_offers: ResMut<ShopOffers>,
+    mut rng: ResMut<GameRng>,
+    config: Res<GameConfig>,
 ) {
     if game_state.money == 0 {
         return;
     }
     game_state.money -= 1;
     let count = if game_state.is_charm_active(Charm::ShopCount) {
-        4
+        BASE_SHOP_OFFERS + 1
     } else {
-        3
+        BASE_SHOP_OFFERS
     };
-    shop_offers.reroll(&game_state.charms, count);
+    shop_offers.reroll(&game_state, &config, count, &mut *rng);
+}
+
+fn reroll_charms(
+    _: On<Pointer<Click>>,
+    mut game_state: ResMut<GameState>,
+    mut shop_offers: ResMut<ShopOffers>,
+    mut rng: ResMut<GameRng>,
+) {
+    let price = shop_offers.charm_reroll_price();
+    if game_state.money < price || !shop_offers.has_charm_offers() {
+        return;
+    }
+    game_state.money -= price;
+    shop_offers.reroll_charms_only(&game_state.charms, &mut *rng);
 }
 
-fn item_card(slot: usize, item: ItemType, money: u32, charms_full: bool) -> impl Bundle {
+/// Marks the offer that's only there thanks to [`Charm::ShopCount`].
+fn fully_stocked_badge() -> impl Bundle {
+    (
+        Name::new("Fully Stocked Badge"),
+        Text(format!("Extra offer from {}", Charm::ShopCount.name())),
+        TextFont::from_font_size(12.0),
+        TextColor(CHARM_TEXT),
+    )
+}
+
+fn item_card(
+    slot: usize,
+    item: ItemType,
+    description: Cow<'static, str>,
+    icon: Handle<Image>,
+    money: u32,
+    charms_full: bool,
+    locked: bool,
+) -> impl Bundle {
     let price = item.price();
     let buy_text = format!("Buy ({})", price);
     let color = match item {
@@ -298,7 +528,11 @@ fn item_card(slot: usize, item: ItemType, money: u32, charms_full: bool) -> impl
             ..default()
         },
         BackgroundColor(CARD_BACKGROUND),
-        BorderColor::all(CARD_BORDER),
+        BorderColor::all(if locked {
+            LOCKED_CARD_BORDER
+        } else {
+            CARD_BORDER
+        }),
         Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
             parent.spawn((
                 Name::new("Item Type"),
@@ -306,6 +540,7 @@ fn item_card(slot: usize, item: ItemType, money: u32, charms_full: bool) -> impl
                 TextFont::from_font_size(14.0),
                 TextColor(color),
             ));
+            parent.spawn(item_icon(icon));
             parent.spawn((
                 Name::new("Item Name"),
                 Text(item.name().to_string()),
@@ -315,24 +550,46 @@ fn item_card(slot: usize, item: ItemType, money: u32, charms_full: bool) -> impl
             ));
             parent.spawn((
                 Name::new("Item Description"),
-                Text(item.description().to_string()),
+                Text(description.into_owned()),
                 TextFont::from_font_size(14.0),
                 TextColor(ui_palette::LABEL_TEXT),
                 TextLayout::new_with_justify(Justify::Center),
             ));
 
-            if money >= price && !(matches!(item, ItemType::Charm(_)) && charms_full) {
+            let can_buy = money >= price && !(matches!(item, ItemType::Charm(_)) && charms_full);
+            if can_buy && item == ItemType::Boost(Boost::Recolor) {
+                parent.spawn(widget::button_medium(
+                    buy_text.clone(),
+                    move |_: On<Pointer<Click>>,
+                          mut commands: Commands,
+                          game_state: Res<GameState>| {
+                        commands.insert_resource(RecolorChoice {
+                            slot,
+                            from: None,
+                            to: None,
+                        });
+                        commands.spawn(recolor_picker(&game_state));
+                    },
+                ));
+            } else if can_buy {
                 parent.spawn(widget::button_medium(
                     buy_text.clone(),
                     move |_: On<Pointer<Click>>,
                           mut game_state: ResMut<GameState>,
-                          mut shop_offers: ResMut<ShopOffers>| {
-                        buy_shop_item(slot, &mut game_state, &mut shop_offers);
+                          mut shop_offers: ResMut<ShopOffers>,
+                          config: Res<GameConfig>| {
+                        buy_shop_item(slot, &mut game_state, &mut shop_offers, &config);
                     },
                 ));
             } else {
                 parent.spawn(widget::button_medium_disabled(buy_text.clone()));
             }
+            parent.spawn(widget::button_medium(
+                if locked { "Unlock" } else { "Lock" },
+                move |_: On<Pointer<Click>>, mut shop_offers: ResMut<ShopOffers>| {
+                    shop_offers.toggle_lock(slot);
+                },
+            ));
         })),
     )
 }
@@ -361,41 +618,72 @@ fn bought_item_card() -> impl Bundle {
     )
 }
 
-fn buy_sheep(_: On<Pointer<Click>>, mut game_state: ResMut<GameState>) {
+fn buy_sheep(_: On<Pointer<Click>>, mut game_state: ResMut<GameState>, config: Res<GameConfig>) {
     if game_state.money < 1 {
         return;
     }
     game_state.sheep_count += 1;
     let mut rng = rand::rng();
-    if game_state.is_charm_active(Charm::ChanceBlueOnBuy) && rng.random_ratio(1, 4) {
+    if game_state.is_charm_active(Charm::ChanceBlueOnBuy)
+        && rng.random_ratio(1, config.shop.color_on_buy_chance_denominator)
+    {
         game_state.blue_sheep_count += 1;
     }
-    if game_state.is_charm_active(Charm::ChanceRedOnBuy) && rng.random_ratio(1, 4) {
+    if game_state.is_charm_active(Charm::ChanceRedOnBuy)
+        && rng.random_ratio(1, config.shop.color_on_buy_chance_denominator)
+    {
         game_state.red_sheep_count += 1;
     }
     game_state.money -= 1;
 }
 
-fn buy_shop_item(slot: usize, game_state: &mut GameState, shop_offers: &mut ShopOffers) {
+fn buy_shop_item(
+    slot: usize,
+    game_state: &mut GameState,
+    shop_offers: &mut ShopOffers,
+    config: &GameConfig,
+) {
+    buy_shop_item_with(
+        slot,
+        game_state,
+        shop_offers,
+        |game_state, item| match item {
+            ItemType::Boost(boost) => {
+                boost.apply(game_state, config);
+                true
+            }
+            ItemType::Charm(charm) => {
+                if game_state.charms_full() {
+                    return false;
+                }
+                game_state.charms.push(charm);
+                true
+            }
+        },
+    );
+}
+
+/// Buys the item in `slot` if it's affordable and `apply` succeeds, then charges for it and clears
+/// the slot. Returns whether it was bought.
+fn buy_shop_item_with(
+    slot: usize,
+    game_state: &mut GameState,
+    shop_offers: &mut ShopOffers,
+    apply: impl FnOnce(&mut GameState, ItemType) -> bool,
+) -> bool {
     let Some(Some(item)) = shop_offers.items.get(slot).copied() else {
-        return;
+        return false;
     };
-    if game_state.money < item.price() {
-        return;
-    }
-
-    match item {
-        ItemType::Boost(boost) => boost.apply(game_state),
-        ItemType::Charm(charm) => {
-            if game_state.charms_full() {
-                return;
-            }
-            game_state.charms.push(charm);
-        }
+    if game_state.money < item.price() || !apply(game_state, item) {
+        return false;
     }
 
     game_state.money -= item.price();
     shop_offers.items[slot] = None;
+    if shop_offers.is_locked(slot) {
+        shop_offers.toggle_lock(slot);
+    }
+    true
 }
 
 fn sell_charm(slot: usize, game_state: &mut GameState) {
@@ -404,21 +692,284 @@ fn sell_charm(slot: usize, game_state: &mut GameState) {
     };
 
     game_state.charms.remove(slot);
-    game_state.money += floor(charm.price() as f32 / 2.0) as u32;
+    game_state.money += charm.sell_price();
+}
+
+/// Asks before selling every charm at once, since it can't be undone.
+#[derive(Component)]
+struct SellAllConfirm;
+
+fn open_sell_all_confirm(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    existing: Query<(), With<SellAllConfirm>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+    let refund: u32 = game_state.charms.iter().map(Charm::sell_price).sum();
+    commands.spawn((
+        SellAllConfirm,
+        widget::ui_root("Sell All Charms Confirm"),
+        GlobalZIndex(5),
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        DespawnOnExit(GamePhase::Shop),
+        children![(
+            widget::panel(),
+            children![
+                widget::label(format!(
+                    "Sell all {} charms for +{refund}?",
+                    game_state.charms.len()
+                )),
+                (
+                    widget::row(),
+                    children![
+                        widget::button_medium("Sell All", sell_all_charms),
+                        widget::button_medium("Cancel", close_sell_all_confirm),
+                    ]
+                ),
+            ]
+        )],
+    ));
+}
+
+fn sell_all_charms(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    mut game_state: ResMut<GameState>,
+    confirm: Query<Entity, With<SellAllConfirm>>,
+) {
+    let refund: u32 = game_state
+        .charms
+        .drain(..)
+        .map(|charm| charm.sell_price())
+        .sum();
+    game_state.money += refund;
+    for entity in &confirm {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn close_sell_all_confirm(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    confirm: Query<Entity, With<SellAllConfirm>>,
+) {
+    for entity in &confirm {
+        commands.entity(entity).despawn();
+    }
 }
 
 pub fn redraw_shop_ui(
     mut commands: Commands,
     game_state: Res<GameState>,
     shop_offers: Res<ShopOffers>,
+    shop_assets: Res<ShopAssets>,
+    config: Res<GameConfig>,
     roots: Query<Entity, With<ShopUiRoot>>,
 ) {
-    if !game_state.is_changed() && !shop_offers.is_changed() {
+    if !game_state.is_changed() && !shop_offers.is_changed() && !config.is_changed() {
         return;
     }
 
     for root in &roots {
         commands.entity(root).despawn();
     }
-    draw_shop_ui(commands, &game_state, &shop_offers);
+    draw_shop_ui(commands, &game_state, &shop_offers, &shop_assets, &config);
+}
+
+/// Colors a sheep can be dyed from with [`Boost::Recolor`], and the colors it can be dyed to.
+/// Gold only comes from charms, so sheep can't be dyed gold.
+const RECOLOR_SOURCES: [SheepColor; 6] = [
+    SheepColor::White,
+    SheepColor::Blue,
+    SheepColor::Red,
+    SheepColor::Black,
+    SheepColor::Gold,
+    SheepColor::Green,
+];
+const RECOLOR_TARGETS: [SheepColor; 5] = [
+    SheepColor::White,
+    SheepColor::Blue,
+    SheepColor::Red,
+    SheepColor::Black,
+    SheepColor::Green,
+];
+
+/// The [`Boost::Recolor`] offer being bought, and the colors picked so far.
+#[derive(Resource, Debug)]
+pub struct RecolorChoice {
+    slot: usize,
+    from: Option<SheepColor>,
+    to: Option<SheepColor>,
+}
+
+#[derive(Component)]
+struct RecolorPicker;
+
+#[derive(Component)]
+pub struct RecolorStatus;
+
+fn recolor_picker(game_state: &GameState) -> impl Bundle {
+    let sources: Vec<(SheepColor, u16)> = RECOLOR_SOURCES
+        .into_iter()
+        .map(|color| {
+            let count = game_state.sheep_of_color(&color);
+            (color, count)
+        })
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    (
+        RecolorPicker,
+        widget::ui_root("Recolor Picker"),
+        GlobalZIndex(5),
+        BackgroundColor(Color::BLACK.with_alpha(0.6)),
+        DespawnOnExit(GamePhase::Shop),
+        children![(
+            widget::panel(),
+            children![
+                widget::header(Boost::Recolor.name()),
+                widget::label("Dye one of your"),
+                (
+                    widget::row(),
+                    Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+                        for (color, count) in sources {
+                            parent.spawn(widget::button_small(
+                                format!("{} ({count})", color.name()),
+                                move |_: On<Pointer<Click>>, mut choice: ResMut<RecolorChoice>| {
+                                    choice.from = Some(color.clone());
+                                },
+                            ));
+                        }
+                    })),
+                ),
+                widget::label("sheep"),
+                (
+                    widget::row(),
+                    Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+                        for color in RECOLOR_TARGETS {
+                            parent.spawn(widget::button_small(
+                                color.name(),
+                                move |_: On<Pointer<Click>>, mut choice: ResMut<RecolorChoice>| {
+                                    choice.to = Some(color.clone());
+                                },
+                            ));
+                        }
+                    })),
+                ),
+                (widget::label(""), RecolorStatus),
+                (
+                    widget::row(),
+                    children![
+                        widget::button_medium(
+                            format!("Dye ({})", Boost::Recolor.price()),
+                            confirm_recolor
+                        ),
+                        widget::button_medium("Cancel", close_recolor_picker),
+                    ]
+                ),
+            ]
+        )],
+    )
+}
+
+pub fn update_recolor_status(
+    choice: Res<RecolorChoice>,
+    mut status: Single<&mut Text, With<RecolorStatus>>,
+) {
+    let name = |color: &Option<SheepColor>| color.as_ref().map_or("...", SheepColor::name);
+    status.0 = match (&choice.from, &choice.to) {
+        (Some(from), Some(to)) if from == to => "Pick two different colors".to_string(),
+        (None, None) => "Pick a sheep to dye, then its new color".to_string(),
+        (from, to) => format!("{} sheep to {}", name(from), name(to)),
+    };
+}
+
+fn confirm_recolor(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    choice: Res<RecolorChoice>,
+    mut game_state: ResMut<GameState>,
+    mut shop_offers: ResMut<ShopOffers>,
+    picker: Query<Entity, With<RecolorPicker>>,
+) {
+    let (Some(from), Some(to)) = (&choice.from, &choice.to) else {
+        return;
+    };
+    let bought = buy_shop_item_with(
+        choice.slot,
+        &mut game_state,
+        &mut shop_offers,
+        |game_state, _| game_state.recolor_sheep(from, to),
+    );
+    if !bought {
+        return;
+    }
+    for entity in &picker {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn close_recolor_picker(
+    _: On<Pointer<Click>>,
+    mut commands: Commands,
+    picker: Query<Entity, With<RecolorPicker>>,
+) {
+    for entity in &picker {
+        commands.entity(entity).despawn();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dyeing_a_sheep_pays_and_unlocks_its_slot() {
+        let item = ItemType::Boost(Boost::Recolor);
+        let mut game_state = GameState {
+            money: item.price(),
+            ..default()
+        };
+        let mut shop_offers = ShopOffers {
+            items: vec![Some(item)],
+            locked: vec![true],
+            ..default()
+        };
+        assert!(!buy_shop_item_with(
+            0,
+            &mut game_state,
+            &mut shop_offers,
+            |game_state, _| game_state.recolor_sheep(&SheepColor::White, &SheepColor::Gold),
+        ));
+        assert_eq!(game_state.money, item.price());
+        assert!(buy_shop_item_with(
+            0,
+            &mut game_state,
+            &mut shop_offers,
+            |game_state, _| game_state.recolor_sheep(&SheepColor::White, &SheepColor::Blue),
+        ));
+        assert_eq!(game_state.money, 0);
+        assert_eq!(shop_offers.items[0], None);
+        assert!(!shop_offers.is_locked(0));
+    }
+
+    #[test]
+    fn selling_a_charm_grants_its_sell_price() {
+        for charm in Charm::ALL {
+            let mut game_state = GameState {
+                charms: vec![charm],
+                ..default()
+            };
+            let money_before = game_state.money;
+            sell_charm(0, &mut game_state);
+            assert_eq!(
+                game_state.money - money_before,
+                charm.sell_price(),
+                "{charm:?}"
+            );
+            assert!(game_state.charms.is_empty());
+        }
+    }
 }
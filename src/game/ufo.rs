@@ -1,23 +1,38 @@
-use bevy::prelude::*;
-use rand::seq::IteratorRandom;
+use std::time::Duration;
+
+use bevy::{
+    audio::Volume,
+    color::{Mix, palettes::css},
+    prelude::*,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::IndexedRandom};
+use serde::{Deserialize, Serialize};
 
 use crate::{
     AppSystems, PausableSystems,
     asset_tracking::LoadResource,
+    audio::SoundEffect,
     game::{
+        level::GoalTextMessage,
         modifiers::Modifier,
         movement::{HopMovementController, MovementController, SphereMovementController},
+        rng::GameRng,
         sheep::Sheep,
-        state::{GamePhase, GameState},
+        state::{GamePhase, GameState, zen_mode},
     },
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.load_resource::<UfoAssets>();
-    app.add_systems(OnEnter(GamePhase::Herding), spawn_ufo);
+    app.init_resource::<UfoConfig>();
+    app.insert_resource(UfoRng(StdRng::seed_from_u64(0)));
+    app.add_systems(
+        OnEnter(GamePhase::Herding),
+        (reseed_ufo_rng, spawn_ufo.run_if(not(zen_mode))),
+    );
     app.add_systems(
         Update,
-        tick_abduction_timers
+        (tick_abduction_timers, play_delayed_sirens)
             .in_set(AppSystems::TickTimers)
             .in_set(PausableSystems)
             .run_if(in_state(GamePhase::Herding)),
@@ -30,31 +45,163 @@ pub(super) fn plugin(app: &mut App) {
             .in_set(PausableSystems)
             .run_if(in_state(GamePhase::Herding)),
     );
+    app.add_systems(
+        Update,
+        draw_target_warning
+            .in_set(AppSystems::Update)
+            .run_if(in_state(GamePhase::Herding)),
+    );
 }
 
 pub const UFO_HEIGHT: f32 = 15.0;
-const UFO_ABDUCTION_SECONDS: f32 = 8.0;
-const UFO_POST_ABDUCTION_PAUSE_SECONDS: f32 = 3.0;
-const UFO_SPEED: f32 = 7.0;
 const UFO_TARGET_REACHED_DISTANCE: f32 = 0.5;
+/// The warning ring around a targeted sheep shrinks from the max to the min radius as the
+/// abduction cooldown runs out.
+const TARGET_WARNING_MAX_RADIUS: f32 = 2.5;
+const TARGET_WARNING_MIN_RADIUS: f32 = 0.8;
+const TARGET_WARNING_HEIGHT: f32 = 0.1;
+
+/// Every this many completed rounds a mega UFO shows up, whether or not the `Ufo` modifier is active.
+const MEGA_UFO_ROUND_INTERVAL: u32 = 5;
+const MEGA_UFO_CONFIG: UfoConfig = UfoConfig {
+    abduction_seconds: 4.0,
+    pause_seconds: 1.5,
+    speed: 11.0,
+    speed_per_round: 0.3,
+    max_speed: 16.0,
+    abduction_seconds_per_round: 0.1,
+    min_abduction_seconds: 2.0,
+};
+const MEGA_UFO_SCALE: f32 = 2.0;
+/// Frequencies (Hz) of the descending two-tone siren played when a mega UFO arrives.
+const MEGA_UFO_SIREN_TONES: [f32; 2] = [880.0, 440.0];
+const MEGA_UFO_SIREN_TONE_SECONDS: f32 = 0.35;
+/// With [`Modifier::FeverDream`] active, UFOs escalate this much faster each round.
+const FEVER_DREAM_ESCALATION: f32 = 1.5;
+
+/// How aggressive UFOs are. Changes take effect on UFOs that are already flying around.
+///
+/// Set from [`GameConfig`](crate::game::config::GameConfig) when the config file loads.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct UfoConfig {
+    /// Cooldown between abductions.
+    pub abduction_seconds: f32,
+    /// How long a UFO hovers in place after abducting a sheep.
+    pub pause_seconds: f32,
+    pub speed: f32,
+    /// Added to the speed for every completed round, up to `max_speed`.
+    pub speed_per_round: f32,
+    pub max_speed: f32,
+    /// Taken off the abduction cooldown for every completed round, down to
+    /// `min_abduction_seconds`.
+    pub abduction_seconds_per_round: f32,
+    pub min_abduction_seconds: f32,
+}
+
+impl UfoConfig {
+    /// This config after `escalation` rounds' worth of getting faster and abducting more often.
+    fn escalated(&self, escalation: f32) -> Self {
+        Self {
+            speed: (self.speed + self.speed_per_round * escalation)
+                .min(self.max_speed.max(self.speed)),
+            abduction_seconds: (self.abduction_seconds
+                - self.abduction_seconds_per_round * escalation)
+                .max(self.min_abduction_seconds.min(self.abduction_seconds)),
+            ..self.clone()
+        }
+    }
+}
+
+impl Default for UfoConfig {
+    fn default() -> Self {
+        Self {
+            abduction_seconds: 8.0,
+            pause_seconds: 3.0,
+            speed: 7.0,
+            speed_per_round: 0.3,
+            max_speed: 12.0,
+            abduction_seconds_per_round: 0.25,
+            min_abduction_seconds: 3.0,
+        }
+    }
+}
 
 #[derive(Debug, Component)]
-struct Ufo {
+pub struct Ufo {
     abduction_timer: Timer,
     post_abduction_pause_timer: Timer,
     target: Option<Entity>,
+    /// The sheep this UFO last started abducting, which may already be gone.
+    abducting: Option<Entity>,
+    /// Used instead of the global [`UfoConfig`] for special UFOs.
+    config_override: Option<UfoConfig>,
+    /// How many rounds' worth of escalation apply to this UFO, set when it spawns.
+    escalation: f32,
 }
 impl Ufo {
-    pub fn new() -> Self {
+    /// A UFO escalated by `escalation` rounds, see [`ufo_escalation`].
+    pub fn new(config: &UfoConfig, escalation: f32) -> Self {
+        let config = config.escalated(escalation);
         let mut post_abduction_pause_timer =
-            Timer::from_seconds(UFO_POST_ABDUCTION_PAUSE_SECONDS, TimerMode::Once);
+            Timer::from_seconds(config.pause_seconds, TimerMode::Once);
         post_abduction_pause_timer.set_elapsed(post_abduction_pause_timer.duration());
         Self {
-            abduction_timer: Timer::from_seconds(UFO_ABDUCTION_SECONDS, TimerMode::Once),
+            abduction_timer: Timer::from_seconds(config.abduction_seconds, TimerMode::Once),
             post_abduction_pause_timer,
             target: None,
+            abducting: None,
+            config_override: None,
+            escalation,
         }
     }
+
+    /// A bigger, faster UFO that abducts more often.
+    pub fn mega(escalation: f32) -> Self {
+        Self {
+            config_override: Some(MEGA_UFO_CONFIG),
+            ..Self::new(&MEGA_UFO_CONFIG, escalation)
+        }
+    }
+
+    /// The sheep this UFO is currently heading for.
+    pub fn target(&self) -> Option<Entity> {
+        self.target
+    }
+
+    /// The sheep this UFO last started abducting. Check the sheep is still being abducted before
+    /// acting on it.
+    pub fn abducting(&self) -> Option<Entity> {
+        self.abducting
+    }
+
+    /// Lets go of the sheep being abducted, and hovers in place again as if it had just finished an
+    /// abduction.
+    pub fn drop_sheep(&mut self) {
+        self.abducting = None;
+        self.target = None;
+        self.post_abduction_pause_timer.reset();
+    }
+
+    /// The config this UFO flies by, with its escalation applied.
+    fn config(&self, global: &UfoConfig) -> UfoConfig {
+        self.config_override
+            .as_ref()
+            .unwrap_or(global)
+            .escalated(self.escalation)
+    }
+}
+
+/// How many rounds' worth of escalation UFOs spawned this round get. UFOs get faster and abduct
+/// more often as the run goes on, and faster still with [`Modifier::FeverDream`].
+fn ufo_escalation(game_state: &GameState) -> f32 {
+    let escalation = game_state.completed_rounds as f32;
+    if game_state.is_modifier_active(Modifier::FeverDream) {
+        escalation * FEVER_DREAM_ESCALATION
+    } else {
+        escalation
+    }
 }
 
 #[derive(Resource, Asset, Clone, Reflect)]
@@ -73,13 +220,56 @@ impl FromWorld for UfoAssets {
     }
 }
 
-fn spawn_ufo(mut commands: Commands, assets: Res<UfoAssets>, game_state: Res<GameState>) {
+fn spawn_ufo(
+    mut commands: Commands,
+    assets: Res<UfoAssets>,
+    game_state: Res<GameState>,
+    ufo_config: Res<UfoConfig>,
+    mut pitch_assets: ResMut<Assets<Pitch>>,
+    mut writer: MessageWriter<GoalTextMessage>,
+) {
+    let escalation = ufo_escalation(&game_state);
+    if is_mega_ufo_round(game_state.completed_rounds) {
+        commands.spawn((
+            Name::new("Mega UFO"),
+            Transform::from_xyz(0.0, UFO_HEIGHT, -20.0).with_scale(Vec3::splat(MEGA_UFO_SCALE)),
+            SceneRoot(assets.ufo.clone()),
+            Ufo::mega(escalation),
+            DespawnOnExit(GamePhase::Herding),
+        ));
+        for (i, frequency) in MEGA_UFO_SIREN_TONES.into_iter().enumerate() {
+            let tone = pitch_assets.add(Pitch::new(
+                frequency,
+                Duration::from_secs_f32(MEGA_UFO_SIREN_TONE_SECONDS),
+            ));
+            commands.spawn((
+                Name::new("Mega UFO Siren"),
+                AudioPlayer(tone),
+                PlaybackSettings {
+                    volume: Volume::Linear(0.3),
+                    ..PlaybackSettings::DESPAWN.paused()
+                },
+                SoundEffect,
+                SirenDelay(Timer::from_seconds(
+                    i as f32 * MEGA_UFO_SIREN_TONE_SECONDS,
+                    TimerMode::Once,
+                )),
+                DespawnOnExit(GamePhase::Herding),
+            ));
+        }
+        writer.write(GoalTextMessage {
+            text: "A mega UFO approaches!".to_string(),
+            color: Some(Color::srgb(0.6, 1.0, 0.4)),
+            position: None,
+        });
+    }
+
     if game_state.is_modifier_active(Modifier::Ufo) {
         commands.spawn((
             Name::new("UFO"),
             Transform::from_xyz(0.0, UFO_HEIGHT, -20.0),
             SceneRoot(assets.ufo.clone()),
-            Ufo::new(),
+            Ufo::new(&ufo_config, escalation),
             DespawnOnExit(GamePhase::Herding),
         ));
         if game_state.is_modifier_active(Modifier::FeverDream) {
@@ -87,37 +277,142 @@ fn spawn_ufo(mut commands: Commands, assets: Res<UfoAssets>, game_state: Res<Gam
                 Name::new("UFO"),
                 Transform::from_xyz(0.0, UFO_HEIGHT, -20.0),
                 SceneRoot(assets.ufo.clone()),
-                Ufo::new(),
+                Ufo::new(&ufo_config, escalation),
                 DespawnOnExit(GamePhase::Herding),
             ));
         }
     }
 }
 
-fn tick_abduction_timers(time: Res<Time>, mut ufo_query: Query<&mut Ufo>) {
+fn is_mega_ufo_round(completed_rounds: u32) -> bool {
+    completed_rounds > 0 && completed_rounds.is_multiple_of(MEGA_UFO_ROUND_INTERVAL)
+}
+
+/// Delays a siren tone so the tones play one after another.
+#[derive(Debug, Component)]
+struct SirenDelay(Timer);
+
+fn play_delayed_sirens(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut siren_query: Query<(Entity, &mut SirenDelay, &AudioSink)>,
+) {
+    for (entity, mut delay, sink) in &mut siren_query {
+        if delay.0.tick(time.delta()).is_finished() {
+            sink.play();
+            commands.entity(entity).remove::<SirenDelay>();
+        }
+    }
+}
+
+fn tick_abduction_timers(
+    time: Res<Time>,
+    ufo_config: Res<UfoConfig>,
+    mut ufo_query: Query<&mut Ufo>,
+) {
     for mut ufo in &mut ufo_query {
+        let config = ufo.config(&ufo_config);
+        let abduction = Duration::from_secs_f32(config.abduction_seconds);
+        if ufo.abduction_timer.duration() != abduction {
+            ufo.abduction_timer.set_duration(abduction);
+        }
+        let pause = Duration::from_secs_f32(config.pause_seconds);
+        if ufo.post_abduction_pause_timer.duration() != pause {
+            ufo.post_abduction_pause_timer.set_duration(pause);
+        }
         ufo.abduction_timer.tick(time.delta());
         ufo.post_abduction_pause_timer.tick(time.delta());
     }
 }
 
-fn pick_targets(mut ufo_query: Query<&mut Ufo>, sheep_query: Query<Entity, With<Sheep>>) {
-    for mut ufo in &mut ufo_query {
+/// Randomness for UFO targeting. It's reseeded from the run's seed every round, so a seeded run's
+/// UFOs choose the same way given the same flock, without drawing from [`GameRng`] and changing
+/// which modifiers and shop items come up later.
+#[derive(Resource, Debug, Deref, DerefMut)]
+struct UfoRng(StdRng);
+
+fn reseed_ufo_rng(mut ufo_rng: ResMut<UfoRng>, game_rng: Res<GameRng>, state: Res<GameState>) {
+    // Mixed with the round so each round gets its own sequence.
+    let round = u64::from(state.completed_rounds).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    ufo_rng.0 = StdRng::seed_from_u64(game_rng.seed() ^ round);
+}
+
+/// Gives a target to every UFO that's ready for one. Targets are spread out so two UFOs don't go
+/// after the same sheep while there are others to pick from.
+fn pick_targets(
+    mut ufo_query: Query<(&Transform, &mut Ufo)>,
+    sheep_query: Query<(Entity, &Transform), With<Sheep>>,
+    mut rng: ResMut<UfoRng>,
+) {
+    for (_, mut ufo) in &mut ufo_query {
         if !ufo.post_abduction_pause_timer.is_finished() {
             ufo.target = None;
-            continue;
-        }
-        if ufo.target.is_some() {
-            continue;
         }
+    }
+    let mut claimed: Vec<Entity> = ufo_query.iter().filter_map(|(_, ufo)| ufo.target).collect();
+
+    // Queries don't iterate in a fixed order, and entity ids depend on everything spawned before,
+    // so sort by position before drawing from the seeded RNG.
+    let mut sheep: Vec<(Entity, &Transform)> = sheep_query.iter().collect();
+    sheep.sort_unstable_by(|(_, a), (_, b)| position_order(a, b));
+    let sheep: Vec<Entity> = sheep.into_iter().map(|(entity, _)| entity).collect();
+    let mut ufos: Vec<_> = ufo_query
+        .iter_mut()
+        .filter(|(_, ufo)| ufo.target.is_none() && ufo.post_abduction_pause_timer.is_finished())
+        .collect();
+    ufos.sort_unstable_by(|(a, _), (b, _)| position_order(a, b));
+
+    for (_, ufo) in &mut ufos {
+        ufo.target = pick_target(&sheep, &claimed, &mut rng.0);
+        claimed.extend(ufo.target);
+    }
+}
+
+/// Orders transforms along x, then z.
+fn position_order(a: &Transform, b: &Transform) -> std::cmp::Ordering {
+    a.translation
+        .x
+        .total_cmp(&b.translation.x)
+        .then(a.translation.z.total_cmp(&b.translation.z))
+}
 
-        let rng = &mut rand::rng();
-        ufo.target = sheep_query.iter().choose(rng);
+/// Picks a random sheep from `sheep`, preferring ones not in `claimed`. Falls back to a claimed
+/// sheep rather than leaving the UFO idle when every sheep is taken.
+fn pick_target(sheep: &[Entity], claimed: &[Entity], rng: &mut impl Rng) -> Option<Entity> {
+    let unclaimed: Vec<Entity> = sheep
+        .iter()
+        .copied()
+        .filter(|sheep| !claimed.contains(sheep))
+        .collect();
+    unclaimed.choose(rng).or_else(|| sheep.choose(rng)).copied()
+}
+
+/// Rings the sheep a UFO is heading for, shrinking and turning red as the UFO gets ready to abduct.
+fn draw_target_warning(
+    ufo_query: Query<&Ufo>,
+    sheep_query: Query<&GlobalTransform, With<Sheep>>,
+    mut gizmos: Gizmos,
+) {
+    for ufo in &ufo_query {
+        let Some(transform) = ufo.target.and_then(|target| sheep_query.get(target).ok()) else {
+            continue;
+        };
+        let progress = ufo.abduction_timer.fraction();
+        let radius = TARGET_WARNING_MAX_RADIUS.lerp(TARGET_WARNING_MIN_RADIUS, progress);
+        let color = css::YELLOW.mix(&css::RED, progress);
+        let mut position = transform.translation();
+        position.y = TARGET_WARNING_HEIGHT;
+        gizmos.circle(
+            Isometry3d::new(position, Quat::from_rotation_x(std::f32::consts::FRAC_PI_2)),
+            radius,
+            color,
+        );
     }
 }
 
 fn update_ufo(
     time: Res<Time>,
+    ufo_config: Res<UfoConfig>,
     mut commands: Commands,
     mut ufo_query: Query<(&mut Transform, &mut Ufo), Without<Sheep>>,
     mut sheep_query: Query<(&Transform, &mut Sheep), Without<Ufo>>,
@@ -144,7 +439,8 @@ fn update_ufo(
         let distance = to_target.length();
 
         if distance > f32::EPSILON {
-            let step = (UFO_SPEED * time.delta_secs()).min(distance);
+            let speed = ufo.config(&ufo_config).speed;
+            let step = (speed * time.delta_secs()).min(distance);
             let dir = to_target / distance;
             ufo_transform.translation.x += dir.x * step;
             ufo_transform.translation.z += dir.y * step;
@@ -160,6 +456,7 @@ fn update_ufo(
                     )>();
                     ufo.abduction_timer.reset();
                     ufo.post_abduction_pause_timer.reset();
+                    ufo.abducting = Some(target);
                 }
                 ufo.target = None;
             } else {
@@ -168,3 +465,38 @@ fn update_ufo(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escalation_is_capped() {
+        for config in [UfoConfig::default(), MEGA_UFO_CONFIG] {
+            let escalated = config.escalated(1_000.0);
+            assert_eq!(escalated.speed, config.max_speed);
+            assert_eq!(escalated.abduction_seconds, config.min_abduction_seconds);
+        }
+    }
+
+    #[test]
+    fn two_ufos_pick_different_targets() {
+        let mut world = World::new();
+        let sheep: Vec<Entity> = (0..5).map(|_| world.spawn_empty().id()).collect();
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let first = pick_target(&sheep, &[], &mut rng).unwrap();
+            let second = pick_target(&sheep, &[first], &mut rng).unwrap();
+            assert_ne!(first, second, "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn ufos_share_a_target_when_only_one_sheep_is_left() {
+        let mut world = World::new();
+        let sheep = [world.spawn_empty().id()];
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(pick_target(&sheep, &sheep, &mut rng), Some(sheep[0]));
+        assert_eq!(pick_target(&[], &[], &mut rng), None);
+    }
+}
@@ -5,12 +5,17 @@
 
 mod asset_tracking;
 mod audio;
+mod clipboard;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod game;
 mod menus;
+mod performance;
+mod persistence;
 mod post_processing;
 mod screens;
+mod screenshot;
+mod settings;
 mod theme;
 
 use bevy::{
@@ -58,8 +63,11 @@ impl Plugin for AppPlugin {
             #[cfg(feature = "dev")]
             dev_tools::plugin,
             menus::plugin,
+            performance::plugin,
             post_processing::plugin,
             screens::plugin,
+            screenshot::plugin,
+            settings::plugin,
             theme::plugin,
         ));
 
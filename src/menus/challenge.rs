@@ -0,0 +1,165 @@
+//! The menu for entering another player's share code, reached from the main menu.
+
+use bevy::{
+    input::{
+        ButtonState,
+        common_conditions::input_just_pressed,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    clipboard,
+    game::{
+        challenge::{PendingChallenge, ShareCode},
+        state::GameMode,
+    },
+    menus::{Menu, main::start_run},
+    screens::Screen,
+    theme::{palette::CARD_BACKGROUND, prelude::*},
+};
+
+/// Generous upper bound on how long a typed code can get.
+const MAX_CODE_LENGTH: usize = 32;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Challenge), spawn_challenge_menu);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            (type_code, update_code_text).chain(),
+        )
+            .run_if(in_state(Menu::Challenge)),
+    );
+}
+
+/// The code typed so far.
+#[derive(Resource, Debug, Default)]
+struct ChallengeCodeInput(String);
+
+#[derive(Component)]
+struct ChallengeCodeText;
+
+#[derive(Component)]
+struct ChallengeCodeStatus;
+
+fn spawn_challenge_menu(mut commands: Commands) {
+    commands.insert_resource(ChallengeCodeInput::default());
+    commands.spawn((
+        widget::ui_root("Challenge Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Challenge),
+        children![
+            (
+                widget::panel(),
+                children![
+                    widget::header("Play a Challenge"),
+                    widget::label("Type or paste (Ctrl+V) a share code to replay someone's run."),
+                    (
+                        Name::new("Code Field"),
+                        Node {
+                            min_width: px(400),
+                            padding: UiRect::all(px(10)),
+                            justify_content: JustifyContent::Center,
+                            border: UiRect::all(px(2)),
+                            border_radius: BorderRadius::all(px(8)),
+                            ..default()
+                        },
+                        BackgroundColor(CARD_BACKGROUND),
+                        BorderColor::all(ui_palette::LABEL_TEXT),
+                        children![(widget::label("_"), ChallengeCodeText)],
+                    ),
+                    (widget::label(""), ChallengeCodeStatus),
+                    widget::button("Play Challenge", play_challenge),
+                    widget::button("Back", go_back_on_click),
+                ],
+            ),
+            widget::controls_footer(&[("Esc", "Back")])
+        ],
+    ));
+}
+
+fn type_code(
+    mut keyboard: MessageReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut input: ResMut<ChallengeCodeInput>,
+) {
+    let ctrl = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Backspace => {
+                input.0.pop();
+            }
+            Key::Character(_) if ctrl && event.key_code == KeyCode::KeyV => {
+                if let Some(pasted) = clipboard::paste() {
+                    input.0 = pasted.trim().to_string();
+                }
+            }
+            Key::Character(text) if !ctrl => {
+                input.0.extend(
+                    text.chars()
+                        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_'),
+                );
+            }
+            _ => {}
+        }
+    }
+    if input.0.len() > MAX_CODE_LENGTH {
+        input.0.truncate(MAX_CODE_LENGTH);
+    }
+}
+
+fn update_code_text(
+    input: Res<ChallengeCodeInput>,
+    mut text: Single<&mut Text, With<ChallengeCodeText>>,
+) {
+    if !input.is_changed() {
+        return;
+    }
+    text.0 = format!("{}_", input.0);
+}
+
+fn play_challenge(
+    _: On<Pointer<Click>>,
+    input: Res<ChallengeCodeInput>,
+    resource_handles: Res<ResourceHandles>,
+    mut pending: ResMut<PendingChallenge>,
+    mut game_mode: ResMut<GameMode>,
+    mut status: Single<&mut Text, With<ChallengeCodeStatus>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    match ShareCode::decode(&input.0) {
+        Ok(challenge) => {
+            pending.0 = Some(challenge);
+            *game_mode = challenge.mode;
+            if challenge.mode == GameMode::Draft {
+                // The modifiers still have to be drafted. The challenge is picked up once the run
+                // starts from there.
+                next_menu.set(Menu::Draft);
+            } else {
+                start_run(&resource_handles, &mut next_screen);
+            }
+        }
+        Err(err) => status.0 = err.to_string(),
+    }
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
@@ -0,0 +1,220 @@
+//! The controls menu, reached from the settings menu. Click an action, then press the key to bind
+//! it to.
+
+use bevy::{ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    game::player::KeyBindings,
+    menus::{Menu, settings::set_button_text},
+    theme::{focus::FocusSystems, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Rebinding>();
+    // Otherwise the key being bound would also move the keyboard focus.
+    app.configure_sets(
+        PostUpdate,
+        FocusSystems.run_if(|rebinding: Res<Rebinding>| rebinding.0.is_none()),
+    );
+    app.add_systems(OnEnter(Menu::Controls), spawn_controls_menu);
+    app.add_systems(OnExit(Menu::Controls), stop_rebinding);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(
+                input_just_pressed(KeyCode::Escape)
+                    .and(|rebinding: Res<Rebinding>| rebinding.0.is_none()),
+            ),
+            capture_rebind_key,
+            update_binding_labels,
+        )
+            .chain()
+            .run_if(in_state(Menu::Controls)),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Bark,
+}
+
+impl Action {
+    const ALL: [Action; 5] = [
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::Bark,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Action::Up => "Move Up",
+            Action::Down => "Move Down",
+            Action::Left => "Move Left",
+            Action::Right => "Move Right",
+            Action::Bark => "Bark",
+        }
+    }
+
+    fn key(&self, bindings: &KeyBindings) -> KeyCode {
+        match self {
+            Action::Up => bindings.up,
+            Action::Down => bindings.down,
+            Action::Left => bindings.left,
+            Action::Right => bindings.right,
+            Action::Bark => bindings.bark,
+        }
+    }
+
+    fn key_mut<'a>(&self, bindings: &'a mut KeyBindings) -> &'a mut KeyCode {
+        match self {
+            Action::Up => &mut bindings.up,
+            Action::Down => &mut bindings.down,
+            Action::Left => &mut bindings.left,
+            Action::Right => &mut bindings.right,
+            Action::Bark => &mut bindings.bark,
+        }
+    }
+}
+
+/// The action waiting for a key press, if any.
+#[derive(Resource, Debug, Default)]
+struct Rebinding(Option<Action>);
+
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+struct BindingLabel(Action);
+
+fn spawn_controls_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Controls Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::Controls),
+        children![
+            widget::header("Controls"),
+            (
+                Name::new("Bindings Grid"),
+                Node {
+                    display: Display::Grid,
+                    row_gap: px(10),
+                    column_gap: px(30),
+                    grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+                    ..default()
+                },
+                Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+                    for action in Action::ALL {
+                        parent.spawn(binding_row_label(action));
+                        parent.spawn(binding_button(action));
+                    }
+                })),
+            ),
+            widget::label("The arrow keys and Space always work too."),
+            widget::button("Reset to Defaults", reset_bindings),
+            widget::button("Back", go_back_on_click),
+            widget::controls_footer(&[("Esc", "Back")]),
+        ],
+    ));
+}
+
+fn binding_row_label(action: Action) -> impl Bundle {
+    (
+        widget::label(action.name()),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+fn binding_button(action: Action) -> impl Bundle {
+    (
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![(
+            widget::button_medium(
+                "",
+                move |_: On<Pointer<Click>>, mut rebinding: ResMut<Rebinding>| {
+                    rebinding.0 = Some(action);
+                }
+            ),
+            BindingLabel(action),
+        )],
+    )
+}
+
+/// Binds the action waiting for a key to the next key pressed. A key already bound to another
+/// action swaps over, so no two actions share a key. Escape cancels.
+fn capture_rebind_key(
+    input: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<KeyBindings>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+    let Some(&key) = input.get_just_pressed().next() else {
+        return;
+    };
+    rebinding.0 = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+
+    let previous = action.key(&bindings);
+    if let Some(other) = Action::ALL
+        .into_iter()
+        .find(|other| *other != action && other.key(&bindings) == key)
+    {
+        *other.key_mut(&mut bindings) = previous;
+    }
+    *action.key_mut(&mut bindings) = key;
+}
+
+fn update_binding_labels(
+    bindings: Res<KeyBindings>,
+    rebinding: Res<Rebinding>,
+    labels: Query<(Entity, &BindingLabel)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, label) in &labels {
+        let text = if rebinding.0 == Some(label.0) {
+            "Press a key...".to_string()
+        } else {
+            key_name(label.0.key(&bindings))
+        };
+        set_button_text(entity, &text, &children, &mut texts);
+    }
+}
+
+/// A short name for a key, e.g. `W` rather than `KeyW`.
+fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    ["Key", "Digit"]
+        .into_iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .map_or_else(|| name.clone(), str::to_string)
+}
+
+fn reset_bindings(_: On<Pointer<Click>>, mut bindings: ResMut<KeyBindings>) {
+    *bindings = KeyBindings::default();
+}
+
+fn stop_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
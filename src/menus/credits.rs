@@ -23,6 +23,7 @@ fn spawn_credits_menu(mut commands: Commands) {
             widget::header("Assets"),
             assets(),
             widget::button("Back", go_back_on_click),
+            widget::controls_footer(&[("Esc", "Back")]),
         ],
     ));
 }
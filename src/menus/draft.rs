@@ -0,0 +1,245 @@
+//! The draft menu, reached from the main menu. The player picks modifiers from a pool before the
+//! run starts, and they stay active for the whole run in exchange for extra money each round.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use rand::seq::IteratorRandom;
+
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{
+        challenge::{PendingChallenge, PendingSeed},
+        modifiers::Modifier,
+        rng::GameRng,
+        state::{
+            DRAFT_PICKS, DRAFTED_MODIFIER_MONEY, GameMode, GameState,
+            modifier_choice::modifier_card,
+        },
+    },
+    menus::{Menu, main::start_run},
+    screens::Screen,
+    settings::{GameplaySettings, MIN_ENABLED_MODIFIERS_FOR_DRAFT},
+    theme::{palette::CARD_BORDER, prelude::*},
+};
+
+/// How many modifiers are offered in the draft.
+const DRAFT_POOL_SIZE: usize = 4;
+const PICKED_CARD_BORDER: Color = Color::srgb(0.902, 0.627, 0.157);
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Draft), spawn_draft_menu);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            update_draft_cards.run_if(resource_changed::<DraftSelection>),
+        )
+            .run_if(in_state(Menu::Draft)),
+    );
+}
+
+/// The modifiers picked so far. Fewer than [`DRAFT_PICKS`] are required if so many modifiers are
+/// banned that the pool is smaller than that.
+#[derive(Resource, Debug, Default)]
+struct DraftSelection {
+    picked: Vec<Modifier>,
+    required: usize,
+}
+
+impl DraftSelection {
+    fn toggle(&mut self, modifier: Modifier) {
+        if let Some(index) = self.picked.iter().position(|m| *m == modifier) {
+            self.picked.remove(index);
+        } else if self.picked.len() < self.required {
+            self.picked.push(modifier);
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+struct DraftCard(Modifier);
+
+#[derive(Component, Debug)]
+struct DraftStatus;
+
+/// The modifiers offered in the draft for a run with `seed`, so a seeded draft challenge offers the
+/// same ones.
+fn draft_pool(seed: u64, banned: &[Modifier]) -> Vec<Modifier> {
+    Modifier::ALL
+        .into_iter()
+        .filter(|modifier| !banned.contains(modifier))
+        .choose_multiple(&mut GameRng::pre_run(seed), DRAFT_POOL_SIZE)
+}
+
+fn spawn_draft_menu(
+    mut commands: Commands,
+    settings: Res<GameplaySettings>,
+    pending: Res<PendingChallenge>,
+    mut pending_seed: ResMut<PendingSeed>,
+) {
+    if settings.enabled_modifier_count() < MIN_ENABLED_MODIFIERS_FOR_DRAFT {
+        spawn_too_few_modifiers_menu(&mut commands);
+        return;
+    }
+    let seed = pending_seed.get_or_pick(&pending);
+    let pool = draft_pool(seed, &settings.banned_modifiers);
+    commands.insert_resource(DraftSelection {
+        picked: Vec::new(),
+        required: DRAFT_PICKS.min(pool.len()),
+    });
+
+    commands
+        .spawn((
+            widget::ui_root("Draft Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Draft),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::panel()).with_children(|panel| {
+                panel.spawn(widget::header("Draft Your Modifiers"));
+                panel.spawn(widget::label(format!(
+                    "Pick {DRAFT_PICKS}. They stay active from the first round to the end of the \
+                     run, and each pays +{DRAFTED_MODIFIER_MONEY} money every round."
+                )));
+                panel
+                    .spawn((
+                        Name::new("Draft Row"),
+                        Node {
+                            width: percent(100),
+                            justify_content: JustifyContent::SpaceAround,
+                            align_items: AlignItems::FlexStart,
+                            flex_direction: FlexDirection::Row,
+                            flex_wrap: FlexWrap::Wrap,
+                            column_gap: px(24),
+                            row_gap: px(24),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|row| {
+                        for modifier in pool {
+                            row.spawn((draft_card(modifier), DraftCard(modifier)));
+                        }
+                    });
+                panel.spawn((widget::label(""), DraftStatus));
+                panel.spawn(widget::row()).with_children(|row| {
+                    row.spawn(widget::button("Start Run", start_draft_run));
+                    row.spawn(widget::button("Back", go_back_on_click));
+                });
+            });
+            root.spawn(widget::controls_footer(&[("Esc", "Back")]));
+        });
+}
+
+/// Shown instead of the draft when so many modifiers are disabled that a drafted run could run out
+/// of new ones to offer.
+fn spawn_too_few_modifiers_menu(commands: &mut Commands) {
+    commands.insert_resource(DraftSelection::default());
+    commands
+        .spawn((
+            widget::ui_root("Draft Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Draft),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::panel()).with_children(|panel| {
+                panel.spawn(widget::header("Draft Your Modifiers"));
+                panel.spawn(widget::label(format!(
+                    "Draft needs at least {MIN_ENABLED_MODIFIERS_FOR_DRAFT} modifiers enabled. \
+                     Turn more on under Settings > Modifiers."
+                )));
+                panel.spawn(widget::button("Back", go_back_on_click));
+            });
+            root.spawn(widget::controls_footer(&[("Esc", "Back")]));
+        });
+}
+
+fn draft_card(modifier: Modifier) -> impl Bundle {
+    modifier_card(
+        modifier,
+        format!("+{DRAFTED_MODIFIER_MONEY} money every round"),
+        widget::button_medium(
+            "Pick",
+            move |_: On<Pointer<Click>>, mut selection: ResMut<DraftSelection>| {
+                selection.toggle(modifier);
+            },
+        ),
+    )
+}
+
+/// Highlights the picked cards and says how many picks are left.
+fn update_draft_cards(
+    selection: Res<DraftSelection>,
+    mut cards: Query<(&DraftCard, &mut BorderColor)>,
+    mut status: Single<&mut Text, With<DraftStatus>>,
+) {
+    for (card, mut border) in &mut cards {
+        *border = if selection.picked.contains(&card.0) {
+            BorderColor::all(PICKED_CARD_BORDER)
+        } else {
+            BorderColor::all(CARD_BORDER)
+        };
+    }
+    let remaining = selection.required - selection.picked.len();
+    status.0 = match remaining {
+        0 => "Ready to start!".to_string(),
+        1 => "Pick 1 more".to_string(),
+        n => format!("Pick {n} more"),
+    };
+}
+
+fn start_draft_run(
+    _: On<Pointer<Click>>,
+    selection: Res<DraftSelection>,
+    resource_handles: Res<ResourceHandles>,
+    mut game_state: ResMut<GameState>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    if selection.picked.len() < selection.required {
+        return;
+    }
+    for modifier in &selection.picked {
+        game_state.add_drafted_modifier(*modifier);
+    }
+    *game_mode = GameMode::Draft;
+    start_run(&resource_handles, &mut next_screen);
+}
+
+fn go_back_on_click(
+    _: On<Pointer<Click>>,
+    pending: ResMut<PendingChallenge>,
+    pending_seed: ResMut<PendingSeed>,
+    game_mode: ResMut<GameMode>,
+    next_menu: ResMut<NextState<Menu>>,
+) {
+    go_back(pending, pending_seed, game_mode, next_menu);
+}
+
+/// Leaving the draft also drops a draft challenge that led here, and the seed picked for the draft,
+/// so they don't carry over to a run started some other way.
+fn go_back(
+    mut pending: ResMut<PendingChallenge>,
+    mut pending_seed: ResMut<PendingSeed>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    pending.0 = None;
+    pending_seed.0 = None;
+    *game_mode = GameMode::default();
+    next_menu.set(Menu::Main);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_draft_pool() {
+        let banned = [Modifier::ALL[0]];
+        let pool = draft_pool(42, &banned);
+        assert_eq!(pool.len(), DRAFT_POOL_SIZE);
+        assert!(!pool.contains(&Modifier::ALL[0]));
+        assert_eq!(draft_pool(42, &banned), pool);
+        // Other seeds shouldn't all land on the same pool.
+        assert!((0..10).any(|seed| draft_pool(seed, &banned) != pool));
+    }
+}
@@ -2,13 +2,30 @@
 
 use bevy::prelude::*;
 
-use crate::{asset_tracking::ResourceHandles, menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    asset_tracking::ResourceHandles,
+    game::{
+        playtime::{LifetimePlaytime, Playtime, format_hms},
+        state::GameMode,
+    },
+    menus::Menu,
+    screens::Screen,
+    theme::widget,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
 }
 
-fn spawn_main_menu(mut commands: Commands) {
+fn spawn_main_menu(
+    mut commands: Commands,
+    playtime: Res<Playtime>,
+    lifetime_playtime: Res<LifetimePlaytime>,
+) {
+    let total_playtime = format!(
+        "Total playtime: {}",
+        format_hms(playtime.lifetime(&lifetime_playtime))
+    );
     commands.spawn((
         widget::ui_root("Main Menu"),
         GlobalZIndex(2),
@@ -16,17 +33,31 @@ fn spawn_main_menu(mut commands: Commands) {
         #[cfg(not(target_family = "wasm"))]
         children![
             widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Zen Mode", start_zen_mode),
+            widget::button("Gentle Herding", start_gentle_mode),
+            widget::button("Draft", open_draft_menu),
+            widget::button("Ranked", start_ranked_mode),
+            widget::button("Challenge", open_challenge_menu),
             widget::button("How to Play", open_how_to_play),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
+            widget::label(total_playtime),
+            widget::controls_footer(&[("F11", "Fullscreen"), ("F12", "Screenshot")]),
         ],
         #[cfg(target_family = "wasm")]
         children![
             widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Zen Mode", start_zen_mode),
+            widget::button("Gentle Herding", start_gentle_mode),
+            widget::button("Draft", open_draft_menu),
+            widget::button("Ranked", start_ranked_mode),
+            widget::button("Challenge", open_challenge_menu),
             widget::button("How to Play", open_how_to_play),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
+            widget::label(total_playtime),
+            widget::controls_footer(&[("F11", "Fullscreen"), ("F12", "Screenshot")]),
         ],
     ));
 }
@@ -36,6 +67,41 @@ fn enter_loading_or_gameplay_screen(
     resource_handles: Res<ResourceHandles>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
+    start_run(&resource_handles, &mut next_screen);
+}
+
+fn start_zen_mode(
+    _: On<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    *game_mode = GameMode::Zen;
+    start_run(&resource_handles, &mut next_screen);
+}
+
+fn start_gentle_mode(
+    _: On<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    *game_mode = GameMode::Gentle;
+    start_run(&resource_handles, &mut next_screen);
+}
+
+fn start_ranked_mode(
+    _: On<Pointer<Click>>,
+    resource_handles: Res<ResourceHandles>,
+    mut game_mode: ResMut<GameMode>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    *game_mode = GameMode::Ranked;
+    start_run(&resource_handles, &mut next_screen);
+}
+
+/// Goes straight to gameplay if everything has loaded, or to the loading screen otherwise.
+pub(super) fn start_run(resource_handles: &ResourceHandles, next_screen: &mut NextState<Screen>) {
     if resource_handles.is_all_done() {
         next_screen.set(Screen::Gameplay);
     } else {
@@ -43,6 +109,14 @@ fn enter_loading_or_gameplay_screen(
     }
 }
 
+fn open_challenge_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Challenge);
+}
+
+fn open_draft_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Draft);
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
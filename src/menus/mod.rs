@@ -1,20 +1,40 @@
 //! The game's menus and transitions between them.
 
+mod challenge;
+mod controls;
 mod credits;
+mod draft;
 mod main;
+mod modifier_settings;
 mod pause;
+mod photo_mode;
 mod settings;
 
 use bevy::prelude::*;
 
+use crate::{game::state::GamePhase, theme::focus::FocusSystems};
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
+    // The arrow keys and Enter are gameplay controls while herding and in photo mode.
+    app.configure_sets(
+        PostUpdate,
+        FocusSystems.run_if(
+            not(in_state(GamePhase::Herding).and(in_state(Menu::None)))
+                .and(not(in_state(Menu::PhotoMode))),
+        ),
+    );
 
     app.add_plugins((
+        challenge::plugin,
+        controls::plugin,
         credits::plugin,
+        draft::plugin,
         main::plugin,
+        modifier_settings::plugin,
         settings::plugin,
         pause::plugin,
+        photo_mode::plugin,
     ));
 }
 
@@ -23,7 +43,12 @@ pub enum Menu {
     #[default]
     None,
     Main,
+    Challenge,
+    Controls,
     Credits,
+    Draft,
     Settings,
+    ModifierSettings,
     Pause,
+    PhotoMode,
 }
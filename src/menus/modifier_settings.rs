@@ -0,0 +1,112 @@
+//! A checklist for turning individual dream modifiers off, reached from the settings menu.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    game::modifiers::Modifier,
+    menus::{
+        Menu,
+        settings::{on_off, set_button_text},
+    },
+    settings::{GameplaySettings, MIN_ENABLED_MODIFIERS, MIN_ENABLED_MODIFIERS_FOR_DRAFT},
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(Menu::ModifierSettings),
+        spawn_modifier_settings_menu,
+    );
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            update_modifier_toggle_labels,
+        )
+            .run_if(in_state(Menu::ModifierSettings)),
+    );
+}
+
+fn spawn_modifier_settings_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            widget::ui_root("Modifier Settings Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::ModifierSettings),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::panel()).with_children(|panel| {
+                panel.spawn(widget::header("Modifiers"));
+                panel.spawn(widget::label(format!(
+                    "Disabled modifiers are never offered. At least {MIN_ENABLED_MODIFIERS} must stay \
+                     on, or {MIN_ENABLED_MODIFIERS_FOR_DRAFT} to play Draft."
+                )));
+                panel
+                    .spawn((
+                        Name::new("Modifier Grid"),
+                        Node {
+                            display: Display::Grid,
+                            row_gap: px(6),
+                            column_gap: px(30),
+                            grid_template_columns: RepeatedGridTrack::auto(4),
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                    ))
+                    .with_children(|grid| {
+                        for modifier in Modifier::ALL {
+                            grid.spawn((
+                                widget::label(modifier.name()),
+                                Node {
+                                    justify_self: JustifySelf::End,
+                                    ..default()
+                                },
+                            ));
+                            grid.spawn((
+                                Name::new("Modifier Toggle"),
+                                Node {
+                                    justify_self: JustifySelf::Start,
+                                    ..default()
+                                },
+                                children![(
+                                    widget::button_medium(
+                                        "",
+                                        move |_: On<Pointer<Click>>,
+                                              mut settings: ResMut<GameplaySettings>| {
+                                            settings.toggle_modifier_ban(modifier);
+                                        }
+                                    ),
+                                    ModifierToggle(modifier),
+                                )],
+                            ));
+                        }
+                    });
+                panel.spawn(widget::button("Back", go_back_on_click));
+            });
+            root.spawn(widget::controls_footer(&[("Esc", "Back")]));
+        });
+}
+
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+struct ModifierToggle(Modifier);
+
+fn update_modifier_toggle_labels(
+    settings: Res<GameplaySettings>,
+    toggles: Query<(Entity, &ModifierToggle)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, toggle) in &toggles {
+        let enabled = !settings.banned_modifiers.contains(&toggle.0);
+        set_button_text(entity, on_off(enabled), &children, &mut texts);
+    }
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Settings);
+}
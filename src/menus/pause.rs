@@ -2,7 +2,7 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{menus::Menu, screens::Screen, settings::GameplaySettings, theme::widget};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
@@ -12,21 +12,29 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn spawn_pause_menu(mut commands: Commands) {
-    commands.spawn((
-        widget::ui_root("Pause Menu"),
-        GlobalZIndex(2),
-        DespawnOnExit(Menu::Pause),
-        children![(
-            widget::panel(),
-            children![
-                widget::header("Game paused"),
-                widget::button("Continue", close_menu),
-                widget::button("Settings", open_settings_menu),
-                widget::button("Quit to title", quit_to_title),
-            ]
-        )],
-    ));
+fn spawn_pause_menu(mut commands: Commands, settings: Res<GameplaySettings>) {
+    commands
+        .spawn((
+            widget::ui_root("Pause Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::Pause),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::panel()).with_children(|panel| {
+                panel.spawn(widget::header("Game paused"));
+                panel.spawn(widget::button("Continue", close_menu));
+                if settings.photo_mode {
+                    panel.spawn(widget::button("Photo Mode", open_photo_mode));
+                }
+                panel.spawn(widget::button("Settings", open_settings_menu));
+                panel.spawn(widget::button("Quit to title", quit_to_title));
+            });
+            root.spawn(widget::controls_footer(&[("Esc", "Continue")]));
+        });
+}
+
+fn open_photo_mode(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::PhotoMode);
 }
 
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
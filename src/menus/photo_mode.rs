@@ -0,0 +1,206 @@
+//! Photo mode: a free camera for framing screenshots, reached from the pause menu when enabled in
+//! the settings. The game stays paused the whole time.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    game::{camera::MainCamera, level::LevelBounds},
+    menus::Menu,
+    post_processing::DreamCloudVignette,
+};
+
+const PHOTO_MODE_PAN_SPEED: f32 = 12.0;
+const PHOTO_MODE_ORBIT_SPEED: f32 = 1.5;
+const PHOTO_MODE_ZOOM_SPEED: f32 = 0.8;
+const PHOTO_MODE_MIN_ZOOM: f32 = 0.3;
+const PHOTO_MODE_MAX_ZOOM: f32 = 1.5;
+/// How far past the level bounds the camera's focus point may go.
+const PHOTO_MODE_BOUNDS_PADDING: f32 = 6.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::PhotoMode), enter_photo_mode);
+    app.add_systems(OnExit(Menu::PhotoMode), exit_photo_mode);
+    app.add_systems(
+        Update,
+        (
+            go_back.run_if(input_just_pressed(KeyCode::Escape)),
+            toggle_vignette.run_if(input_just_pressed(KeyCode::KeyV)),
+            toggle_hint.run_if(input_just_pressed(KeyCode::KeyH)),
+            move_photo_camera,
+        )
+            .run_if(in_state(Menu::PhotoMode)),
+    );
+}
+
+/// The camera and vignette as they were before photo mode, restored on exit.
+#[derive(Resource, Debug)]
+struct PhotoModeRestore {
+    camera_transform: Transform,
+    camera_projection: Projection,
+    vignette_coverage: f32,
+    vignette_target_coverage: f32,
+}
+
+/// UI that was visible before photo mode and has been hidden for a clean shot.
+#[derive(Component, Debug)]
+struct HiddenForPhotoMode;
+
+#[derive(Component, Debug)]
+struct PhotoModeHint;
+
+fn enter_photo_mode(
+    mut commands: Commands,
+    camera: Single<(&Transform, &Projection), With<MainCamera>>,
+    vignette: Res<DreamCloudVignette>,
+    ui_roots: Query<(Entity, &Visibility), (With<Node>, Without<ChildOf>)>,
+) {
+    let (transform, projection) = *camera;
+    commands.insert_resource(PhotoModeRestore {
+        camera_transform: *transform,
+        camera_projection: projection.clone(),
+        vignette_coverage: vignette.coverage,
+        vignette_target_coverage: vignette.target_coverage,
+    });
+
+    for (entity, visibility) in &ui_roots {
+        if *visibility != Visibility::Hidden {
+            commands
+                .entity(entity)
+                .insert((Visibility::Hidden, HiddenForPhotoMode));
+        }
+    }
+
+    commands.spawn((
+        Name::new("Photo Mode Hint"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            bottom: px(24),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(10),
+        Pickable::IGNORE,
+        PhotoModeHint,
+        DespawnOnExit(Menu::PhotoMode),
+        children![(
+            Name::new("Label"),
+            Text::new("WASD: move  Q/E: rotate  Z/X: zoom  V: vignette  H: hide help  Esc: back"),
+            TextFont::from_font_size(20.0),
+            TextColor(Color::WHITE),
+            BackgroundColor(Color::BLACK.with_alpha(0.5)),
+        )],
+    ));
+}
+
+fn exit_photo_mode(
+    mut commands: Commands,
+    restore: Option<Res<PhotoModeRestore>>,
+    mut camera: Single<(&mut Transform, &mut Projection), With<MainCamera>>,
+    mut vignette: ResMut<DreamCloudVignette>,
+    hidden: Query<Entity, With<HiddenForPhotoMode>>,
+) {
+    for entity in &hidden {
+        commands
+            .entity(entity)
+            .insert(Visibility::Inherited)
+            .remove::<HiddenForPhotoMode>();
+    }
+
+    let Some(restore) = restore else {
+        return;
+    };
+    let (transform, projection) = &mut *camera;
+    **transform = restore.camera_transform;
+    **projection = restore.camera_projection.clone();
+    vignette.coverage = restore.vignette_coverage;
+    vignette.target_coverage = restore.vignette_target_coverage;
+    commands.remove_resource::<PhotoModeRestore>();
+}
+
+/// Pans, orbits and zooms the camera around the point on the ground it's looking at, keeping that
+/// point near the level.
+fn move_photo_camera(
+    time: Res<Time<Real>>,
+    input: Res<ButtonInput<KeyCode>>,
+    bounds: Res<LevelBounds>,
+    mut camera: Single<(&mut Transform, &mut Projection), With<MainCamera>>,
+) {
+    let (transform, projection) = &mut *camera;
+    let dt = time.delta_secs();
+
+    let forward = transform.forward();
+    if forward.y.abs() < f32::EPSILON {
+        return;
+    }
+    let focus = transform.translation - forward * (transform.translation.y / forward.y);
+
+    let mut pan = Vec2::ZERO;
+    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+        pan.y += 1.0;
+    }
+    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+        pan.y -= 1.0;
+    }
+    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+        pan.x += 1.0;
+    }
+    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+        pan.x -= 1.0;
+    }
+    let ground_forward = Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero();
+    let ground_right = Vec3::new(-ground_forward.z, 0.0, ground_forward.x);
+    let step = (ground_right * pan.x + ground_forward * pan.y).normalize_or_zero()
+        * PHOTO_MODE_PAN_SPEED
+        * dt;
+    let min = bounds.min - PHOTO_MODE_BOUNDS_PADDING;
+    let max = bounds.max + PHOTO_MODE_BOUNDS_PADDING;
+    let new_focus = (focus + step).xz().clamp(min, max);
+    let new_focus = Vec3::new(new_focus.x, 0.0, new_focus.y);
+    transform.translation += new_focus - focus;
+
+    let mut orbit = 0.0;
+    if input.pressed(KeyCode::KeyQ) {
+        orbit += 1.0;
+    }
+    if input.pressed(KeyCode::KeyE) {
+        orbit -= 1.0;
+    }
+    if orbit != 0.0 {
+        let rotation = Quat::from_rotation_y(orbit * PHOTO_MODE_ORBIT_SPEED * dt);
+        transform.rotate_around(new_focus, rotation);
+    }
+
+    if let Projection::Orthographic(ortho) = &mut **projection {
+        let mut zoom = 0.0;
+        if input.pressed(KeyCode::KeyZ) {
+            zoom -= 1.0;
+        }
+        if input.pressed(KeyCode::KeyX) {
+            zoom += 1.0;
+        }
+        ortho.scale = (ortho.scale + zoom * PHOTO_MODE_ZOOM_SPEED * dt)
+            .clamp(PHOTO_MODE_MIN_ZOOM, PHOTO_MODE_MAX_ZOOM);
+    }
+}
+
+fn toggle_vignette(restore: Res<PhotoModeRestore>, mut vignette: ResMut<DreamCloudVignette>) {
+    let target = if vignette.target_coverage > 0.0 {
+        0.0
+    } else {
+        restore.vignette_target_coverage
+    };
+    vignette.coverage = target;
+    vignette.target_coverage = target;
+}
+
+fn toggle_hint(mut hint: Single<&mut Visibility, With<PhotoModeHint>>) {
+    **hint = match **hint {
+        Visibility::Hidden => Visibility::Inherited,
+        _ => Visibility::Hidden,
+    };
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Pause);
+}
@@ -2,9 +2,22 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{
+    audio::Volume, ecs::system::IntoObserverSystem, input::common_conditions::input_just_pressed,
+    prelude::*,
+};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    audio::RestartAudio,
+    menus::Menu,
+    screens::Screen,
+    settings::{
+        AccessibilityConfig, AudioSettings, CAMERA_PITCH_DEGREES_MAX, CAMERA_PITCH_DEGREES_MIN,
+        CAMERA_PITCH_DEGREES_STEP, CAMERA_ZOOM_MAX, CAMERA_ZOOM_MIN, CAMERA_ZOOM_STEP,
+        DisplaySettings, GameplaySettings,
+    },
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -15,7 +28,13 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (
+            update_global_volume_label,
+            update_category_volume_labels,
+            update_camera_labels,
+            update_toggle_labels,
+        )
+            .run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -24,14 +43,24 @@ fn spawn_settings_menu(mut commands: Commands) {
         widget::ui_root("Settings Menu"),
         GlobalZIndex(2),
         DespawnOnExit(Menu::Settings),
-        children![(
-            widget::panel(),
-            children![
-                widget::header("Settings"),
-                settings_grid(),
-                widget::button("Back", go_back_on_click),
-            ]
-        )],
+        children![
+            (
+                widget::panel(),
+                children![
+                    widget::header("Settings"),
+                    settings_grid(),
+                    (
+                        widget::row(),
+                        children![
+                            widget::button("Modifiers", open_modifier_settings),
+                            widget::button("Controls", open_controls_menu),
+                        ]
+                    ),
+                    widget::button("Back", go_back_on_click),
+                ]
+            ),
+            widget::controls_footer(&[("Esc", "Back"), ("F11", "Fullscreen")])
+        ],
     ));
 }
 
@@ -42,55 +71,137 @@ fn settings_grid() -> impl Bundle {
             display: Display::Grid,
             row_gap: px(10),
             column_gap: px(30),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            // Two label/value pairs per row, so the list stays on screen as settings are added.
+            grid_template_columns: RepeatedGridTrack::auto(4),
+            align_items: AlignItems::Center,
             ..default()
         },
         children![
+            setting_label("Master Volume"),
+            stepper_widget(lower_global_volume, raise_global_volume, GlobalVolumeLabel),
+            setting_label("Music Volume"),
+            stepper_widget(
+                lower_music_volume,
+                raise_music_volume,
+                CategoryVolumeLabel::Music
+            ),
+            setting_label("Sound Effects Volume"),
+            stepper_widget(
+                lower_sfx_volume,
+                raise_sfx_volume,
+                CategoryVolumeLabel::SoundEffects
+            ),
+            setting_label("Camera Angle"),
+            stepper_widget(lower_camera_pitch, raise_camera_pitch, CameraPitchLabel),
+            setting_label("Camera Zoom"),
+            stepper_widget(zoom_camera_out, zoom_camera_in, CameraZoomLabel),
+            setting_label("Music Stopped?"),
             (
-                widget::label("Master Volume"),
                 Node {
-                    justify_self: JustifySelf::End,
+                    justify_self: JustifySelf::Start,
                     ..default()
-                }
+                },
+                children![widget::button_medium("Restart Audio", restart_audio)],
             ),
-            global_volume_widget(),
+            setting_label("Reduced Motion"),
+            toggle_widget(SettingToggle::ReducedMotion, toggle_reduced_motion),
+            setting_label("Hold to Bark"),
+            toggle_widget(SettingToggle::AutoBark, toggle_auto_bark),
+            setting_label("Colorblind Symbols"),
+            toggle_widget(SettingToggle::ColorblindMode, toggle_colorblind_mode),
+            setting_label("Fullscreen"),
+            toggle_widget(SettingToggle::Fullscreen, toggle_fullscreen),
+            setting_label("Pause When Unfocused"),
+            toggle_widget(SettingToggle::PauseOnFocusLoss, toggle_pause_on_focus_loss),
+            setting_label("Double or Nothing"),
+            toggle_widget(SettingToggle::DoubleOrNothing, toggle_double_or_nothing),
+            setting_label("Auto End Round"),
+            toggle_widget(SettingToggle::AutoEndRound, toggle_auto_end_round),
+            setting_label("Herding Hints"),
+            toggle_widget(SettingToggle::HerdingHints, toggle_herding_hints),
+            setting_label("Goal Indicator"),
+            toggle_widget(SettingToggle::GoalIndicator, toggle_goal_indicator),
+            setting_label("Photo Mode"),
+            toggle_widget(SettingToggle::PhotoMode, toggle_photo_mode),
+            setting_label("Sheep Names"),
+            toggle_widget(SettingToggle::SheepNames, toggle_sheep_names),
+            setting_label("Drag to Herd"),
+            toggle_widget(SettingToggle::HerdCommand, toggle_herd_command),
+            setting_label("Dynamic Modifiers"),
+            toggle_widget(SettingToggle::DynamicModifiers, toggle_dynamic_modifiers),
+            setting_label("Flock Aura"),
+            toggle_widget(SettingToggle::HerdAura, toggle_herd_aura),
+            setting_label("Performance"),
+            toggle_widget(SettingToggle::PerformanceTier, cycle_performance_tier),
         ],
     )
 }
 
-fn global_volume_widget() -> impl Bundle {
+fn setting_label(text: &'static str) -> impl Bundle {
     (
-        Name::new("Global Volume Widget"),
+        widget::label(text),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
+/// "-" and "+" buttons around a label showing the current value. `label` marks the text, for a
+/// system to keep up to date.
+fn stepper_widget<E1, B1, M1, I1, E2, B2, M2, I2>(
+    lower: I1,
+    raise: I2,
+    label: impl Component,
+) -> impl Bundle
+where
+    E1: EntityEvent,
+    B1: Bundle,
+    I1: IntoObserverSystem<E1, B1, M1>,
+    E2: EntityEvent,
+    B2: Bundle,
+    I2: IntoObserverSystem<E2, B2, M2>,
+{
+    (
+        Name::new("Stepper Widget"),
         Node {
             justify_self: JustifySelf::Start,
             ..default()
         },
         children![
-            widget::button_small("-", lower_global_volume),
+            widget::button_small("-", lower),
             (
-                Name::new("Current Volume"),
+                Name::new("Current Value"),
                 Node {
                     padding: UiRect::horizontal(px(10)),
                     justify_content: JustifyContent::Center,
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
+                children![(widget::label(""), label)],
             ),
-            widget::button_small("+", raise_global_volume),
+            widget::button_small("+", raise),
         ],
     )
 }
 
+fn restart_audio(_: On<Pointer<Click>>, mut restart: MessageWriter<RestartAudio>) {
+    restart.write(RestartAudio);
+}
+
 const MIN_VOLUME: f32 = 0.0;
 const MAX_VOLUME: f32 = 3.0;
 
+fn step_volume(linear: f32, step: f32) -> f32 {
+    (linear + step).clamp(MIN_VOLUME, MAX_VOLUME)
+}
+
 fn lower_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() - 0.1).max(MIN_VOLUME);
+    let linear = step_volume(global_volume.volume.to_linear(), -0.1);
     global_volume.volume = Volume::Linear(linear);
 }
 
 fn raise_global_volume(_: On<Pointer<Click>>, mut global_volume: ResMut<GlobalVolume>) {
-    let linear = (global_volume.volume.to_linear() + 0.1).min(MAX_VOLUME);
+    let linear = step_volume(global_volume.volume.to_linear(), 0.1);
     global_volume.volume = Volume::Linear(linear);
 }
 
@@ -106,6 +217,232 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn lower_music_volume(_: On<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.music_volume = step_volume(audio.music_volume, -0.1);
+}
+
+fn raise_music_volume(_: On<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.music_volume = step_volume(audio.music_volume, 0.1);
+}
+
+fn lower_sfx_volume(_: On<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.sfx_volume = step_volume(audio.sfx_volume, -0.1);
+}
+
+fn raise_sfx_volume(_: On<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.sfx_volume = step_volume(audio.sfx_volume, 0.1);
+}
+
+/// Marks the label of one of the [`AudioSettings`] volumes.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+enum CategoryVolumeLabel {
+    Music,
+    SoundEffects,
+}
+
+fn update_category_volume_labels(
+    audio: Res<AudioSettings>,
+    mut labels: Query<(&mut Text, &CategoryVolumeLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        let linear = match label {
+            CategoryVolumeLabel::Music => audio.music_volume,
+            CategoryVolumeLabel::SoundEffects => audio.sfx_volume,
+        };
+        text.0 = format!("{:3.0}%", 100.0 * linear);
+    }
+}
+
+fn lower_camera_pitch(_: On<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.camera_pitch_degrees =
+        (display.camera_pitch_degrees() - CAMERA_PITCH_DEGREES_STEP).max(CAMERA_PITCH_DEGREES_MIN);
+}
+
+fn raise_camera_pitch(_: On<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.camera_pitch_degrees =
+        (display.camera_pitch_degrees() + CAMERA_PITCH_DEGREES_STEP).min(CAMERA_PITCH_DEGREES_MAX);
+}
+
+fn zoom_camera_out(_: On<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.camera_zoom = (display.camera_zoom() - CAMERA_ZOOM_STEP).max(CAMERA_ZOOM_MIN);
+}
+
+fn zoom_camera_in(_: On<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.camera_zoom = (display.camera_zoom() + CAMERA_ZOOM_STEP).min(CAMERA_ZOOM_MAX);
+}
+
+#[derive(Component)]
+struct CameraPitchLabel;
+
+#[derive(Component)]
+struct CameraZoomLabel;
+
+fn update_camera_labels(
+    display: Res<DisplaySettings>,
+    mut pitch_label: Single<&mut Text, (With<CameraPitchLabel>, Without<CameraZoomLabel>)>,
+    mut zoom_label: Single<&mut Text, With<CameraZoomLabel>>,
+) {
+    pitch_label.0 = format!("{:.0} deg", display.camera_pitch_degrees());
+    zoom_label.0 = format!("{:3.0}%", 100.0 * display.camera_zoom());
+}
+
+/// A button whose text reflects the current value of `toggle`, usually On or Off.
+fn toggle_widget<E, B, M, I>(toggle: SettingToggle, action: I) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    (
+        Name::new("Toggle Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![(widget::button_medium("", action), toggle)],
+    )
+}
+
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+enum SettingToggle {
+    ReducedMotion,
+    AutoBark,
+    ColorblindMode,
+    Fullscreen,
+    PauseOnFocusLoss,
+    DoubleOrNothing,
+    HerdAura,
+    AutoEndRound,
+    HerdingHints,
+    GoalIndicator,
+    PhotoMode,
+    SheepNames,
+    HerdCommand,
+    DynamicModifiers,
+    PerformanceTier,
+}
+
+fn toggle_reduced_motion(_: On<Pointer<Click>>, mut accessibility: ResMut<AccessibilityConfig>) {
+    accessibility.reduced_motion = !accessibility.reduced_motion;
+}
+
+fn toggle_auto_bark(_: On<Pointer<Click>>, mut accessibility: ResMut<AccessibilityConfig>) {
+    accessibility.auto_bark = !accessibility.auto_bark;
+}
+
+fn toggle_colorblind_mode(_: On<Pointer<Click>>, mut accessibility: ResMut<AccessibilityConfig>) {
+    accessibility.colorblind_mode = !accessibility.colorblind_mode;
+}
+
+fn toggle_fullscreen(_: On<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.fullscreen = !display.fullscreen;
+}
+
+fn toggle_pause_on_focus_loss(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.pause_on_focus_loss = !gameplay.pause_on_focus_loss;
+}
+
+fn toggle_double_or_nothing(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.double_or_nothing = !gameplay.double_or_nothing;
+}
+
+fn toggle_herd_aura(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.show_herd_aura = !gameplay.show_herd_aura;
+}
+
+fn toggle_auto_end_round(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.auto_end_round = !gameplay.auto_end_round;
+}
+
+fn toggle_herding_hints(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.show_herding_hints = !gameplay.show_herding_hints;
+}
+
+fn toggle_goal_indicator(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.show_goal_indicator = !gameplay.show_goal_indicator;
+}
+
+fn toggle_photo_mode(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.photo_mode = !gameplay.photo_mode;
+}
+
+fn toggle_sheep_names(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.show_sheep_names = !gameplay.show_sheep_names;
+}
+
+fn toggle_herd_command(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.herd_command = !gameplay.herd_command;
+}
+
+fn toggle_dynamic_modifiers(_: On<Pointer<Click>>, mut gameplay: ResMut<GameplaySettings>) {
+    gameplay.dynamic_modifiers = !gameplay.dynamic_modifiers;
+}
+
+fn cycle_performance_tier(_: On<Pointer<Click>>, mut display: ResMut<DisplaySettings>) {
+    display.performance_tier = display.performance_tier.next();
+}
+
+fn update_toggle_labels(
+    accessibility: Res<AccessibilityConfig>,
+    display: Res<DisplaySettings>,
+    gameplay: Res<GameplaySettings>,
+    toggles: Query<(Entity, &SettingToggle)>,
+    children: Query<&Children>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, toggle) in &toggles {
+        let text = match toggle {
+            SettingToggle::ReducedMotion => on_off(accessibility.reduced_motion),
+            SettingToggle::AutoBark => on_off(accessibility.auto_bark),
+            SettingToggle::ColorblindMode => on_off(accessibility.colorblind_mode),
+            SettingToggle::Fullscreen => on_off(display.fullscreen),
+            SettingToggle::PauseOnFocusLoss => on_off(gameplay.pause_on_focus_loss),
+            SettingToggle::DoubleOrNothing => on_off(gameplay.double_or_nothing),
+            SettingToggle::HerdAura => on_off(gameplay.show_herd_aura),
+            SettingToggle::AutoEndRound => on_off(gameplay.auto_end_round),
+            SettingToggle::HerdingHints => on_off(gameplay.show_herding_hints),
+            SettingToggle::GoalIndicator => on_off(gameplay.show_goal_indicator),
+            SettingToggle::PhotoMode => on_off(gameplay.photo_mode),
+            SettingToggle::SheepNames => on_off(gameplay.show_sheep_names),
+            SettingToggle::HerdCommand => on_off(gameplay.herd_command),
+            SettingToggle::DynamicModifiers => on_off(gameplay.dynamic_modifiers),
+            SettingToggle::PerformanceTier => display.performance_tier.name(),
+        };
+        set_button_text(entity, text, &children, &mut texts);
+    }
+}
+
+pub(super) fn on_off(enabled: bool) -> &'static str {
+    if enabled { "On" } else { "Off" }
+}
+
+/// Replace the text of a button spawned by one of the [`widget`] button helpers.
+pub(super) fn set_button_text(
+    button: Entity,
+    text: &str,
+    children: &Query<&Children>,
+    texts: &mut Query<&mut Text>,
+) {
+    for descendant in children.iter_descendants(button) {
+        if let Ok(mut button_text) = texts.get_mut(descendant) {
+            if button_text.0 != text {
+                button_text.0 = text.to_string();
+            }
+            return;
+        }
+    }
+}
+
+fn open_modifier_settings(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::ModifierSettings);
+}
+
+fn open_controls_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Controls);
+}
+
 fn go_back_on_click(
     _: On<Pointer<Click>>,
     screen: Res<State<Screen>>,
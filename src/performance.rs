@@ -0,0 +1,120 @@
+//! How much work the game does per frame, picked from the performance tier in
+//! [`DisplaySettings`]. Systems with a cost that scales read their limits from
+//! [`PerformanceBudget`] rather than hardcoding them.
+
+use bevy::prelude::*;
+
+use crate::settings::{DisplaySettings, PerformanceTier};
+
+/// Frames are ignored for this long after startup, while assets load and shaders compile.
+const DETECTION_WARMUP_SECS: f32 = 2.0;
+/// How long the frame rate is averaged over when detecting a tier.
+const DETECTION_SECS: f32 = 3.0;
+/// Average frame rates needed for [`PerformanceTier::High`] and [`PerformanceTier::Medium`].
+const HIGH_TIER_FPS: f32 = 50.0;
+const MEDIUM_TIER_FPS: f32 = 30.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<PerformanceBudget>();
+    app.init_resource::<DetectedPerformanceTier>();
+    app.add_systems(
+        Update,
+        (
+            detect_performance_tier
+                .run_if(|detected: Res<DetectedPerformanceTier>| detected.0.is_none()),
+            apply_performance_tier.run_if(
+                resource_changed::<DisplaySettings>.or(resource_changed::<DetectedPerformanceTier>),
+            ),
+        )
+            .chain(),
+    );
+}
+
+/// Limits derived from the current [`PerformanceTier`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct PerformanceBudget {
+    /// The most sheep on the field at once. The rest of the flock, picked at random, waits in
+    /// reserve and joins as other sheep are counted.
+    pub max_sheep: Option<usize>,
+    /// How often sheep re-evaluate their flockmates.
+    pub herd_update_interval_secs: f32,
+    /// Sheep are split into this many groups, and only one group updates its flocking at a time.
+    pub herd_update_buckets: u64,
+    /// Whether purely cosmetic effects, like wool puffs and bark rings, are spawned.
+    pub visual_effects: bool,
+}
+
+impl Default for PerformanceBudget {
+    fn default() -> Self {
+        Self::for_tier(PerformanceTier::High)
+    }
+}
+
+impl PerformanceBudget {
+    /// [`PerformanceTier::Auto`] is treated as [`PerformanceTier::High`]; resolve it first.
+    pub fn for_tier(tier: PerformanceTier) -> Self {
+        match tier {
+            PerformanceTier::Low => Self {
+                max_sheep: Some(60),
+                herd_update_interval_secs: 0.2,
+                herd_update_buckets: 6,
+                visual_effects: false,
+            },
+            PerformanceTier::Medium => Self {
+                max_sheep: Some(150),
+                herd_update_interval_secs: 0.15,
+                herd_update_buckets: 4,
+                visual_effects: true,
+            },
+            PerformanceTier::Auto | PerformanceTier::High => Self {
+                max_sheep: None,
+                herd_update_interval_secs: 0.1,
+                herd_update_buckets: 4,
+                visual_effects: true,
+            },
+        }
+    }
+}
+
+/// The tier picked from the frame rate shortly after startup, used for
+/// [`PerformanceTier::Auto`]. `None` until detection finishes.
+#[derive(Resource, Debug, Default)]
+pub struct DetectedPerformanceTier(pub Option<PerformanceTier>);
+
+fn detect_performance_tier(
+    time: Res<Time<Real>>,
+    mut elapsed: Local<f32>,
+    mut frames: Local<u32>,
+    mut detected: ResMut<DetectedPerformanceTier>,
+) {
+    *elapsed += time.delta_secs();
+    if *elapsed < DETECTION_WARMUP_SECS {
+        return;
+    }
+    *frames += 1;
+    let measured = *elapsed - DETECTION_WARMUP_SECS;
+    if measured < DETECTION_SECS {
+        return;
+    }
+    let fps = *frames as f32 / measured;
+    detected.0 = Some(if fps >= HIGH_TIER_FPS {
+        PerformanceTier::High
+    } else if fps >= MEDIUM_TIER_FPS {
+        PerformanceTier::Medium
+    } else {
+        PerformanceTier::Low
+    });
+}
+
+fn apply_performance_tier(
+    display: Res<DisplaySettings>,
+    detected: Res<DetectedPerformanceTier>,
+    mut budget: ResMut<PerformanceBudget>,
+) {
+    let tier = match display.performance_tier {
+        PerformanceTier::Auto => detected.0.unwrap_or(PerformanceTier::High),
+        tier => tier,
+    };
+    budget.set_if_neq(PerformanceBudget::for_tier(tier));
+}
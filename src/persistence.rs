@@ -0,0 +1,88 @@
+//! Saving resources to disk so they survive between sessions.
+
+use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+
+/// A [`Resource`] that can be saved to and loaded from disk.
+pub trait Persistent: Resource + Default + Serialize + DeserializeOwned {
+    /// Name of the file (inside the save directory) that this resource is stored in.
+    const FILE_NAME: &'static str;
+}
+
+pub trait PersistResource {
+    /// This will insert the [`Resource`] loaded from disk, falling back to its default if the
+    /// file is missing or can't be parsed. Whenever the resource changes it is written back to disk.
+    ///
+    /// On web there is no file system, so the resource only lives in memory.
+    fn persist_resource<T: Persistent>(&mut self) -> &mut Self;
+}
+
+impl PersistResource for App {
+    fn persist_resource<T: Persistent>(&mut self) -> &mut Self {
+        self.insert_resource(load::<T>());
+        self.add_systems(
+            Last,
+            save::<T>.run_if(resource_changed::<T>.and(not(resource_added::<T>))),
+        );
+        self
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn load<T: Persistent>() -> T {
+    let Some(path) = save_path(T::FILE_NAME) else {
+        return T::default();
+    };
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return T::default();
+    };
+    ron::from_str(&contents).unwrap_or_else(|err| {
+        warn!("Ignoring invalid save file {}: {err}", path.display());
+        T::default()
+    })
+}
+
+#[cfg(target_family = "wasm")]
+fn load<T: Persistent>() -> T {
+    T::default()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn save<T: Persistent>(value: Res<T>) {
+    let Some(path) = save_path(T::FILE_NAME) else {
+        return;
+    };
+    let contents = match ron::ser::to_string_pretty(&*value, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(err) => {
+            warn!("Failed to serialize {}: {err}", T::FILE_NAME);
+            return;
+        }
+    };
+    if let Some(dir) = path.parent()
+        && let Err(err) = std::fs::create_dir_all(dir)
+    {
+        warn!("Failed to create save directory {}: {err}", dir.display());
+        return;
+    }
+    if let Err(err) = std::fs::write(&path, contents) {
+        warn!("Failed to write save file {}: {err}", path.display());
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn save<T: Persistent>(_: Res<T>) {}
+
+/// The per-user data directory for the game, e.g. `~/.local/share/bevy-jam-7/<file_name>`.
+#[cfg(not(target_family = "wasm"))]
+fn save_path(file_name: &str) -> Option<std::path::PathBuf> {
+    use std::{env, path::PathBuf};
+
+    const SAVE_DIR_NAME: &str = "bevy-jam-7";
+
+    let base = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(base.join(SAVE_DIR_NAME).join(file_name))
+}
@@ -23,9 +23,20 @@ use bevy::{
     },
 };
 
-use crate::game::camera::MainCamera;
+use crate::{
+    audio::BgmConfig,
+    game::{camera::MainCamera, state::GameState},
+    screens::Screen,
+    settings::AccessibilityConfig,
+};
 
 const SHADER_ASSET_PATH: &str = "shaders/cloud_vignette.wgsl";
+/// With the percussion playing, the wobble starts pulsing once this little time is left in a round.
+const TENSE_SECONDS: f32 = 15.0;
+/// How much stronger the wobble gets at the peak of a pulse, with no time left.
+const TENSE_WOBBLE_BOOST: f32 = 0.6;
+/// Roughly in time with the music.
+const TENSE_PULSE_HZ: f32 = 2.0;
 
 pub(super) fn plugin(app: &mut App) {
     app.init_resource::<DreamCloudVignette>();
@@ -132,15 +143,43 @@ fn animate_cloud_coverage(time: Res<Time>, mut vignette: ResMut<DreamCloudVignet
     }
 }
 
+/// How tense the round is from 0 to 1, going by how little time is left while the percussion
+/// layer plays. Always 0 with reduced motion on.
+fn tension(
+    screen: &State<Screen>,
+    bgm_config: &BgmConfig,
+    game_state: &GameState,
+    accessibility: &AccessibilityConfig,
+) -> f32 {
+    if accessibility.reduced_motion
+        || !bgm_config.percussion_enabled
+        || *screen.get() != Screen::Gameplay
+    {
+        return 0.0;
+    }
+    let remaining = game_state.countdown.remaining_secs();
+    (1.0 - remaining / TENSE_SECONDS).clamp(0.0, 1.0)
+}
+
 fn sync_settings_from_resource(
     time: Res<Time>,
     vignette: Res<DreamCloudVignette>,
+    screen: Res<State<Screen>>,
+    bgm_config: Res<BgmConfig>,
+    game_state: Res<GameState>,
+    accessibility: Res<AccessibilityConfig>,
     mut settings: Query<&mut DreamCloudPostProcessSettings, With<MainCamera>>,
 ) {
     let coverage = vignette.coverage.clamp(0.0, 1.0);
     let edge_softness = vignette.edge_softness.max(0.001);
     let boundary_thickness = vignette.boundary_thickness.max(0.0);
-    let wobble_strength = vignette.wobble_strength.max(0.0);
+    let tension = tension(&screen, &bgm_config, &game_state, &accessibility);
+    let pulse = (time.elapsed_secs() * TENSE_PULSE_HZ * std::f32::consts::TAU)
+        .sin()
+        .max(0.0)
+        .powi(2);
+    let wobble_strength =
+        vignette.wobble_strength.max(0.0) * (1.0 + TENSE_WOBBLE_BOOST * tension * pulse);
     let wobble_frequency = vignette.wobble_frequency.max(0.0);
     let wobble_speed = vignette.wobble_speed;
 
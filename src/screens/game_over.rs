@@ -1,27 +1,292 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::{game::state::GameState, screens::Screen, theme::prelude::*};
+use crate::{
+    clipboard,
+    game::{
+        challenge::{ActiveChallenge, ShareCode},
+        playtime::{LifetimePlaytime, Playtime, format_hms},
+        rng::GameRng,
+        state::{GameMode, GameState, NewGamePlus, RunStatistics, efficiency},
+    },
+    persistence::Persistent,
+    screens::Screen,
+    theme::prelude::*,
+};
+
+/// How many runs are kept in [`HighScores`]. The best run of each mode is never the one dropped.
+const MAX_RUN_HISTORY: usize = 50;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Screen::GameOver), spawn_game_over_screen);
+    app.add_systems(
+        OnEnter(Screen::GameOver),
+        // The screen compares against the best run before this one is added.
+        (spawn_game_over_screen, record_run).chain(),
+    );
+}
+
+/// Finished runs, oldest first, kept between sessions. Challenge runs aren't recorded, since they
+/// replay someone else's seed. New Game+ runs are, but never count as the best.
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HighScores {
+    pub runs: Vec<RunRecord>,
+}
+
+impl Persistent for HighScores {
+    const FILE_NAME: &'static str = "high_scores.ron";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub completed_rounds: u32,
+    pub sheep_count: u16,
+    /// When the run ended, in seconds since the Unix epoch. Zero on web.
+    pub timestamp: u64,
+    /// Runs are only compared against others played in the same mode.
+    #[serde(default)]
+    pub mode: GameMode,
+    /// Started with part of the previous run carried over, so it isn't compared with fresh runs.
+    #[serde(default)]
+    pub new_game_plus: bool,
+}
+
+impl RunRecord {
+    /// Runs are ranked by rounds completed, then by the size of the flock.
+    fn score(&self) -> (u32, u16) {
+        (self.completed_rounds, self.sheep_count)
+    }
+}
+
+impl HighScores {
+    /// The best run played in `mode`, leaving out New Game+ runs.
+    pub fn best(&self, mode: GameMode) -> Option<&RunRecord> {
+        self.runs
+            .iter()
+            .filter(|run| run.mode == mode && !run.new_game_plus)
+            .max_by_key(|run| run.score())
+    }
+
+    fn record(&mut self, run: RunRecord) {
+        self.runs.push(run);
+        if self.runs.len() > MAX_RUN_HISTORY {
+            let oldest = self
+                .runs
+                .iter()
+                .position(|run| self.best(run.mode) != Some(run));
+            if let Some(oldest) = oldest {
+                self.runs.remove(oldest);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn unix_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
 }
 
-fn spawn_game_over_screen(mut commands: Commands, game_state: Res<GameState>) {
-    commands.spawn((
-        widget::ui_root("Game Over Screen"),
-        DespawnOnExit(Screen::GameOver),
-        children![(
-            widget::panel(),
-            children![
-                widget::header("Game Over"),
-                widget::label(format!("Completed rounds: {}", game_state.completed_rounds)),
-                widget::label(format!("Sheep in flock: {}", game_state.sheep_count)),
-                widget::button("Main Menu", return_to_main_menu),
-            ],
-        )],
-    ));
+/// `SystemTime` isn't available on web, and the history isn't saved there anyway.
+#[cfg(target_family = "wasm")]
+fn unix_timestamp() -> u64 {
+    0
+}
+
+fn record_run(
+    game_state: Res<GameState>,
+    game_mode: Res<GameMode>,
+    challenge: Res<ActiveChallenge>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    if challenge.0.is_some() {
+        return;
+    }
+    high_scores.record(RunRecord {
+        completed_rounds: game_state.completed_rounds,
+        sheep_count: game_state.sheep_count,
+        timestamp: unix_timestamp(),
+        mode: *game_mode,
+        new_game_plus: game_state.new_game_plus,
+    });
+}
+
+fn spawn_game_over_screen(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    playtime: Res<Playtime>,
+    lifetime_playtime: Res<LifetimePlaytime>,
+    rng: Res<GameRng>,
+    challenge: Res<ActiveChallenge>,
+    high_scores: Res<HighScores>,
+    run_stats: Res<RunStatistics>,
+    game_mode: Res<GameMode>,
+) {
+    let score = (game_state.completed_rounds, game_state.sheep_count);
+    let best = high_scores.best(*game_mode).copied();
+    let new_best = challenge.0.is_none()
+        && !game_state.new_game_plus
+        && match best {
+            Some(best) => score > best.score(),
+            None => game_state.completed_rounds > 0,
+        };
+    let share_code = ShareCode {
+        seed: rng.seed(),
+        completed_rounds: game_state.completed_rounds,
+        sheep_count: game_state.sheep_count,
+        mode: *game_mode,
+    }
+    .encode();
+    commands
+        .spawn((
+            widget::ui_root("Game Over Screen"),
+            DespawnOnExit(Screen::GameOver),
+        ))
+        .with_children(|root| {
+            root.spawn(widget::panel()).with_children(|panel| {
+                panel.spawn(widget::header("Game Over"));
+                if game_state.new_game_plus {
+                    panel.spawn(widget::label(
+                        "New Game+ runs don't count towards your best.",
+                    ));
+                }
+                if new_best {
+                    panel.spawn(widget::label("New best!"));
+                } else if let Some(best) = best {
+                    panel.spawn(widget::label(format!(
+                        "Best: {} rounds, {} sheep",
+                        best.completed_rounds, best.sheep_count
+                    )));
+                }
+                panel.spawn(widget::label(format!(
+                    "Completed rounds: {}",
+                    game_state.completed_rounds
+                )));
+                if let Some(rating) = game_state.best_round_rating {
+                    panel.spawn(efficiency::grade_badge(
+                        rating,
+                        format!(
+                            "Best round: {:.2} points/s ({:.1}x the pace needed)",
+                            rating.points_per_second, rating.pace
+                        ),
+                    ));
+                }
+                panel.spawn(widget::label(format!(
+                    "Sheep in flock: {}",
+                    game_state.sheep_count
+                )));
+                panel.spawn(widget::label(sheep_counted_text(&run_stats)));
+                panel.spawn(widget::label(format!(
+                    "Barks: {}  Money earned: {}",
+                    run_stats.barks, run_stats.money_earned
+                )));
+                if let Some((modifier, count)) = run_stats.most_chosen_modifier() {
+                    let times = if count == 1 { "time" } else { "times" };
+                    panel.spawn(widget::label(format!(
+                        "Most chosen modifier: {} ({count} {times})",
+                        modifier.name()
+                    )));
+                }
+                panel.spawn(widget::label(format!(
+                    "Run time: {}",
+                    format_hms(playtime.run)
+                )));
+                panel.spawn(widget::label(format!(
+                    "Total playtime: {}",
+                    format_hms(playtime.lifetime(&lifetime_playtime))
+                )));
+                if game_state.double_or_nothing_won + game_state.double_or_nothing_lost > 0 {
+                    panel.spawn(widget::label(format!(
+                        "Double or Nothing: {} won, {} lost",
+                        game_state.double_or_nothing_won, game_state.double_or_nothing_lost
+                    )));
+                }
+                if let Some(challenge) = challenge.0 {
+                    let result = if game_state.completed_rounds > challenge.completed_rounds {
+                        "beaten!"
+                    } else {
+                        "not beaten"
+                    };
+                    panel.spawn(widget::label(format!(
+                        "Challenge of {} rounds {result}",
+                        challenge.completed_rounds
+                    )));
+                }
+                panel.spawn(widget::label(format!("Share code: {share_code}")));
+                panel.spawn(widget::button(
+                    "Copy Code",
+                    move |_: On<Pointer<Click>>,
+                          mut status: Single<&mut Text, With<CopyStatus>>| {
+                        status.0 = if clipboard::copy(&share_code) {
+                            "Copied!".to_string()
+                        } else {
+                            "Couldn't copy the code".to_string()
+                        };
+                    },
+                ));
+                panel.spawn((widget::label(""), CopyStatus));
+                panel.spawn(widget::button("New Game+", start_new_game_plus));
+                panel.spawn(widget::button("Main Menu", return_to_main_menu));
+            });
+            root.spawn(widget::controls_footer(&[
+                ("F11", "Fullscreen"),
+                ("F12", "Screenshot"),
+            ]));
+        });
+}
+
+/// e.g. "Sheep counted: 50 (42 white, 6 blue, 2 gold)", most counted color first.
+fn sheep_counted_text(run_stats: &RunStatistics) -> String {
+    let total = run_stats.total_sheep_counted();
+    if total == 0 {
+        return "Sheep counted: 0".to_string();
+    }
+    let mut by_color = run_stats.sheep_counted.clone();
+    by_color.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let colors: Vec<String> = by_color
+        .iter()
+        .map(|(color, count)| format!("{count} {}", color.name().to_lowercase()))
+        .collect();
+    format!("Sheep counted: {total} ({})", colors.join(", "))
+}
+
+#[derive(Component)]
+struct CopyStatus;
+
+fn start_new_game_plus(
+    _: On<Pointer<Click>>,
+    mut new_game_plus: ResMut<NewGamePlus>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    new_game_plus.0 = true;
+    next_screen.set(Screen::Gameplay);
 }
 
 fn return_to_main_menu(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Title);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(completed_rounds: u32, new_game_plus: bool) -> RunRecord {
+        RunRecord {
+            completed_rounds,
+            sheep_count: 10,
+            timestamp: 0,
+            mode: GameMode::Normal,
+            new_game_plus,
+        }
+    }
+
+    #[test]
+    fn new_game_plus_runs_are_never_the_best() {
+        let mut high_scores = HighScores::default();
+        high_scores.record(run(3, false));
+        high_scores.record(run(20, true));
+        assert_eq!(high_scores.best(GameMode::Normal), Some(&run(3, false)));
+        assert_eq!(high_scores.runs.len(), 2);
+    }
+}
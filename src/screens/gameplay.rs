@@ -1,8 +1,10 @@
 //! The screen state for the main gameplay.
 
-use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{input::common_conditions::input_just_pressed, prelude::*, window::WindowFocused};
 
-use crate::{Pause, menus::Menu, screens::Screen};
+use crate::{
+    Pause, game::state::GamePhase, menus::Menu, screens::Screen, settings::GameplaySettings,
+};
 
 pub(super) fn plugin(app: &mut App) {
     // Toggle pause on key press.
@@ -21,6 +23,16 @@ pub(super) fn plugin(app: &mut App) {
             ),
         ),
     );
+    // Pause when the window loses focus mid-round, so stepping away doesn't cost the run.
+    app.add_systems(
+        Update,
+        (pause, spawn_pause_overlay, open_pause_menu).run_if(
+            // Check focus first so focus events are always consumed.
+            window_lost_focus
+                .and(in_state(GamePhase::Herding))
+                .and(in_state(Menu::None)),
+        ),
+    );
     app.add_systems(OnExit(Screen::Gameplay), (close_menu, unpause));
     app.add_systems(
         OnEnter(Menu::None),
@@ -28,6 +40,17 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+fn window_lost_focus(
+    settings: Res<GameplaySettings>,
+    mut focus_events: MessageReader<WindowFocused>,
+) -> bool {
+    let lost_focus = focus_events
+        .read()
+        .last()
+        .is_some_and(|event| !event.focused);
+    lost_focus && settings.pause_on_focus_loss
+}
+
 fn unpause(mut next_pause: ResMut<NextState<Pause>>) {
     next_pause.set(Pause(false));
 }
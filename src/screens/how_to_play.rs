@@ -21,7 +21,7 @@ You score points by herding the sheep towards the opening, where they will be co
 - White sheep score 1 point
 - Blue sheep score 5 points
 - Red sheep multiply your current point count by 1.5
-- Black sheep are the same as white by default (but certain charms give them special effects)
+- Black sheep score 1 point and aren't scared by barking, so they stay with the flock (certain charms give them special effects)
 - Gold sheep give money
 
 Sheep run away from you when you get too close. You can also press E or SPACE to bark, which will cause sheep to flee. Sheep also gravitate towards other sheep (you can use this flocking behavior to your advantage).
@@ -32,7 +32,7 @@ You'll then visit a shop where you can spend money on upgrades. 'Boosts' are per
 "#),
                 widget::button("Main Menu", return_to_main_menu),
             ],
-        )],
+        ),widget::controls_footer(&[("F11", "Fullscreen"), ("F12", "Screenshot")])],
     ));
 }
 
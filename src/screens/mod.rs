@@ -9,8 +9,11 @@ mod title;
 
 use bevy::prelude::*;
 
+use crate::persistence::PersistResource;
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
+    app.persist_resource::<game_over::HighScores>();
 
     app.add_plugins((
         gameplay::plugin,
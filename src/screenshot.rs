@@ -0,0 +1,107 @@
+//! Press F12 to save a screenshot of the primary window. On web this triggers a download instead.
+
+use bevy::{
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    render::view::screenshot::{Screenshot, ScreenshotCaptured, save_to_disk},
+};
+
+use crate::theme::prelude::*;
+
+const SCREENSHOT_KEY: KeyCode = KeyCode::F12;
+const FLASH_SECS: f32 = 0.25;
+const TOAST_SECS: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        take_screenshot.run_if(input_just_pressed(SCREENSHOT_KEY)),
+    );
+    app.add_systems(Update, (fade_screenshot_flash, tick_screenshot_toast));
+}
+
+fn take_screenshot(mut commands: Commands, mut counter: Local<u32>) {
+    let path = screenshot_file_name(*counter);
+    *counter += 1;
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(save_to_disk(path))
+        .observe(show_screenshot_feedback);
+}
+
+/// Screenshots are named by capture time so they don't overwrite ones from earlier sessions.
+fn screenshot_file_name(counter: u32) -> String {
+    #[cfg(not(target_family = "wasm"))]
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    // `SystemTime` isn't available on web, but downloads get deduplicated by the browser anyway.
+    #[cfg(target_family = "wasm")]
+    let timestamp = 0;
+
+    format!("screenshot-{timestamp}-{counter}.png")
+}
+
+#[derive(Component, Debug)]
+struct ScreenshotFlash(Timer);
+
+#[derive(Component, Debug)]
+struct ScreenshotToast(Timer);
+
+/// Spawned after the capture so the flash and toast don't end up in the screenshot itself.
+fn show_screenshot_feedback(_: On<ScreenshotCaptured>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Screenshot Flash"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            height: percent(100),
+            ..default()
+        },
+        BackgroundColor(Color::WHITE),
+        GlobalZIndex(10),
+        Pickable::IGNORE,
+        ScreenshotFlash(Timer::from_seconds(FLASH_SECS, TimerMode::Once)),
+    ));
+    commands.spawn((
+        Name::new("Screenshot Toast"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: percent(100),
+            bottom: px(24),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GlobalZIndex(10),
+        Pickable::IGNORE,
+        ScreenshotToast(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+        children![widget::hud_label("Screenshot saved")],
+    ));
+}
+
+fn fade_screenshot_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flash_query: Query<(Entity, &mut ScreenshotFlash, &mut BackgroundColor)>,
+) {
+    for (entity, mut flash, mut background) in &mut flash_query {
+        flash.0.tick(time.delta());
+        background.0.set_alpha(0.8 * flash.0.fraction_remaining());
+        if flash.0.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn tick_screenshot_toast(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut ScreenshotToast)>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        if toast.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Player-configurable options. The menu for changing these lives in `menus::settings`.
+//!
+//! Settings are persisted to disk, see [`PersistResource`].
+
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{modifiers::Modifier, state::DRAFT_PICKS},
+    persistence::{PersistResource, Persistent},
+};
+
+/// Players can't ban so many modifiers that fewer than this are left. With up to two modifiers
+/// carried over between rounds, this guarantees there are always two new ones to choose from.
+pub const MIN_ENABLED_MODIFIERS: usize = 4;
+/// [`MIN_ENABLED_MODIFIERS`] for a draft run, where the drafted modifiers never expire and so are
+/// never offered again either.
+pub const MIN_ENABLED_MODIFIERS_FOR_DRAFT: usize = MIN_ENABLED_MODIFIERS + DRAFT_PICKS;
+
+/// Allowed camera pitch, in degrees above the horizon, and how far one step in the menu moves it.
+pub const CAMERA_PITCH_DEGREES_MIN: f32 = 30.0;
+pub const CAMERA_PITCH_DEGREES_MAX: f32 = 75.0;
+pub const CAMERA_PITCH_DEGREES_STEP: f32 = 5.0;
+/// Allowed camera zoom, and how far one step in the menu moves it.
+pub const CAMERA_ZOOM_MIN: f32 = 0.75;
+pub const CAMERA_ZOOM_MAX: f32 = 1.5;
+pub const CAMERA_ZOOM_STEP: f32 = 0.125;
+
+pub(super) fn plugin(app: &mut App) {
+    app.persist_resource::<AccessibilityConfig>();
+    app.persist_resource::<AudioSettings>();
+    app.persist_resource::<DisplaySettings>();
+    app.persist_resource::<GameplaySettings>();
+
+    app.add_systems(Update, toggle_fullscreen.run_if(fullscreen_hotkey_pressed));
+    app.add_systems(
+        Update,
+        apply_window_mode.run_if(resource_changed::<DisplaySettings>),
+    );
+}
+
+/// Accessibility options for players sensitive to motion or visual effects.
+#[derive(Resource, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct AccessibilityConfig {
+    /// Snap the camera to its target instead of smoothly following it.
+    pub reduced_motion: bool,
+    /// Keep barking whenever the cooldown is up while the bark key is held, instead of needing a
+    /// press per bark.
+    pub auto_bark: bool,
+    /// Float a shape above sheep whose colors are easy to mix up, so they can be told apart
+    /// without relying on color.
+    pub colorblind_mode: bool,
+}
+
+impl Persistent for AccessibilityConfig {
+    const FILE_NAME: &'static str = "accessibility.ron";
+}
+
+/// Volume of each category of sound, as linear multipliers on top of [`GlobalVolume`].
+#[derive(Resource, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct AudioSettings {
+    /// Applies to everything marked [`Music`](crate::audio::Music).
+    pub music_volume: f32,
+    /// Applies to everything marked [`SoundEffect`](crate::audio::SoundEffect).
+    pub sfx_volume: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+        }
+    }
+}
+
+impl Persistent for AudioSettings {
+    const FILE_NAME: &'static str = "audio.ron";
+}
+
+/// Window and display options.
+#[derive(Resource, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct DisplaySettings {
+    /// Borderless fullscreen when true, windowed otherwise.
+    pub fullscreen: bool,
+    /// How much work to do per frame. See [`crate::performance::PerformanceBudget`].
+    pub performance_tier: PerformanceTier,
+    /// How steeply the camera looks down at the player, in degrees above the horizon. Read it
+    /// through [`DisplaySettings::camera_pitch_degrees`], which clamps it.
+    pub camera_pitch_degrees: f32,
+    /// How far the camera is zoomed in, 1 being the default view. Read it through
+    /// [`DisplaySettings::camera_zoom`], which clamps it.
+    pub camera_zoom: f32,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            performance_tier: PerformanceTier::default(),
+            camera_pitch_degrees: 45.0,
+            camera_zoom: 1.0,
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// The camera pitch, kept in range even if the settings file says otherwise.
+    pub fn camera_pitch_degrees(&self) -> f32 {
+        self.camera_pitch_degrees
+            .clamp(CAMERA_PITCH_DEGREES_MIN, CAMERA_PITCH_DEGREES_MAX)
+    }
+
+    /// The camera zoom, kept in range even if the settings file says otherwise.
+    pub fn camera_zoom(&self) -> f32 {
+        self.camera_zoom.clamp(CAMERA_ZOOM_MIN, CAMERA_ZOOM_MAX)
+    }
+}
+
+/// A preset trading visual effects and flock size for frame rate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum PerformanceTier {
+    /// Picked from the frame rate shortly after startup.
+    #[default]
+    Auto,
+    Low,
+    Medium,
+    High,
+}
+
+impl PerformanceTier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PerformanceTier::Auto => "Auto",
+            PerformanceTier::Low => "Low",
+            PerformanceTier::Medium => "Medium",
+            PerformanceTier::High => "High",
+        }
+    }
+
+    /// The tier after this one, for cycling through them in the settings menu.
+    pub fn next(&self) -> Self {
+        match self {
+            PerformanceTier::Auto => PerformanceTier::Low,
+            PerformanceTier::Low => PerformanceTier::Medium,
+            PerformanceTier::Medium => PerformanceTier::High,
+            PerformanceTier::High => PerformanceTier::Auto,
+        }
+    }
+}
+
+impl Persistent for DisplaySettings {
+    const FILE_NAME: &'static str = "display.ron";
+}
+
+/// Options that change how the game plays.
+#[derive(Resource, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct GameplaySettings {
+    /// Open the pause menu when the window loses focus during a round.
+    pub pause_on_focus_loss: bool,
+    /// Offer a double or nothing gamble each time a round's points target is met.
+    pub double_or_nothing: bool,
+    /// Draw a glow under tightly grouped sheep.
+    pub show_herd_aura: bool,
+    /// End the round as soon as the points target is met. When off, the player can keep herding
+    /// until they end the round themselves or time runs out.
+    pub auto_end_round: bool,
+    /// Point out which sheep is worth herding next.
+    pub show_herding_hints: bool,
+    /// Point towards the goal from the edge of the screen while it's off screen.
+    pub show_goal_indicator: bool,
+    /// Offer a free camera for screenshots in the pause menu.
+    pub photo_mode: bool,
+    /// Float a name above each sheep.
+    pub show_sheep_names: bool,
+    /// Drag a box around sheep with the mouse to send them towards the goal.
+    pub herd_command: bool,
+    /// Ease some modifiers in or ramp them up over a round, rather than applying them fully from
+    /// the start. See [`Modifier::intensity`].
+    pub dynamic_modifiers: bool,
+    /// Modifiers that will never be offered.
+    pub banned_modifiers: Vec<Modifier>,
+}
+
+impl Default for GameplaySettings {
+    fn default() -> Self {
+        Self {
+            pause_on_focus_loss: true,
+            double_or_nothing: false,
+            show_herd_aura: true,
+            auto_end_round: true,
+            show_herding_hints: false,
+            show_goal_indicator: true,
+            photo_mode: false,
+            show_sheep_names: false,
+            herd_command: false,
+            dynamic_modifiers: false,
+            banned_modifiers: Vec::new(),
+        }
+    }
+}
+
+impl GameplaySettings {
+    pub fn enabled_modifier_count(&self) -> usize {
+        Modifier::ALL.len() - self.banned_modifiers.len()
+    }
+
+    /// Bans or unbans `modifier`. Does nothing if banning it would leave too few modifiers enabled.
+    pub fn toggle_modifier_ban(&mut self, modifier: Modifier) {
+        if let Some(index) = self.banned_modifiers.iter().position(|m| *m == modifier) {
+            self.banned_modifiers.remove(index);
+        } else if self.enabled_modifier_count() > MIN_ENABLED_MODIFIERS {
+            self.banned_modifiers.push(modifier);
+        }
+    }
+}
+
+impl Persistent for GameplaySettings {
+    const FILE_NAME: &'static str = "gameplay.ron";
+}
+
+/// F11 or Alt+Enter.
+fn fullscreen_hotkey_pressed(input: Res<ButtonInput<KeyCode>>) -> bool {
+    input.just_pressed(KeyCode::F11)
+        || (input.just_pressed(KeyCode::Enter)
+            && input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]))
+}
+
+fn toggle_fullscreen(mut display: ResMut<DisplaySettings>) {
+    display.fullscreen = !display.fullscreen;
+}
+
+/// On web this goes through the browser's fullscreen API, which may refuse the request (e.g. if
+/// it wasn't triggered by user input). In that case the window just stays as it is.
+fn apply_window_mode(
+    display: Res<DisplaySettings>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    window.mode = if display.fullscreen {
+        WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+    } else {
+        WindowMode::Windowed
+    };
+}
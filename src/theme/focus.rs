@@ -0,0 +1,183 @@
+//! Keyboard navigation for buttons. Tab and the arrow keys move focus between [`Focusable`]
+//! buttons, and Enter clicks the focused one.
+
+use std::time::Duration;
+
+use bevy::{
+    camera::NormalizedRenderTarget,
+    picking::{
+        backend::HitData,
+        pointer::{Location, PointerButton, PointerId},
+    },
+    prelude::*,
+};
+
+use crate::theme::interaction::InteractionPalette;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<FocusState>();
+    app.add_systems(
+        PostUpdate,
+        (move_focus, activate_focused, show_focus)
+            .chain()
+            .in_set(FocusSystems),
+    );
+}
+
+/// Keyboard focus handling. Other modules can add run conditions to this set to turn keyboard
+/// navigation off while the keys mean something else.
+#[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct FocusSystems;
+
+/// A button that can be reached with the keyboard. Added to every button made by
+/// [`widget`](super::widget).
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Focusable;
+
+/// The button with keyboard focus, if any.
+#[derive(Resource, Debug, Default)]
+pub struct FocusState {
+    pub focused: Option<Entity>,
+}
+
+/// Moves focus to the next or previous button in reading order: Tab, Down and Right go forward,
+/// Shift+Tab, Up and Left go back. Only the buttons in the top-most layer of UI are reachable, so
+/// focus doesn't wander off behind an open menu.
+fn move_focus(
+    input: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<FocusState>,
+    focusables: Query<
+        (
+            Entity,
+            &UiGlobalTransform,
+            &ComputedNode,
+            &InheritedVisibility,
+        ),
+        With<Focusable>,
+    >,
+    parents: Query<&ChildOf>,
+    z_indices: Query<&GlobalZIndex>,
+) {
+    let shift = input.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let step: isize = if input.just_pressed(KeyCode::Tab) {
+        if shift { -1 } else { 1 }
+    } else if input.any_just_pressed([KeyCode::ArrowDown, KeyCode::ArrowRight]) {
+        1
+    } else if input.any_just_pressed([KeyCode::ArrowUp, KeyCode::ArrowLeft]) {
+        -1
+    } else {
+        return;
+    };
+
+    let layer = |entity: Entity| {
+        std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .filter_map(|entity| z_indices.get(entity).ok())
+            .map(|z_index| z_index.0)
+            .max()
+            .unwrap_or(0)
+    };
+    let mut candidates: Vec<(Entity, i32, Vec2)> = focusables
+        .iter()
+        .filter(|(_, _, node, visibility)| visibility.get() && !node.is_empty())
+        .map(|(entity, transform, _, _)| (entity, layer(entity), transform.translation))
+        .collect();
+    let Some(top) = candidates.iter().map(|(_, layer, _)| *layer).max() else {
+        focus.focused = None;
+        return;
+    };
+    candidates.retain(|(_, layer, _)| *layer == top);
+    // Top to bottom, then left to right. Rounded so buttons in the same row sort by x even if
+    // their centers are a pixel or two apart.
+    candidates.sort_by(|(_, _, a), (_, _, b)| {
+        (a.y / 8.0)
+            .round()
+            .total_cmp(&(b.y / 8.0).round())
+            .then(a.x.total_cmp(&b.x))
+    });
+
+    let current = focus.focused.and_then(|focused| {
+        candidates
+            .iter()
+            .position(|(entity, ..)| *entity == focused)
+    });
+    let next = match current {
+        Some(index) => (index as isize + step).rem_euclid(candidates.len() as isize) as usize,
+        None if step > 0 => 0,
+        None => candidates.len() - 1,
+    };
+    focus.focused = Some(candidates[next].0);
+}
+
+/// Clicks the focused button on Enter, running the same observers a mouse click would.
+fn activate_focused(
+    input: Res<ButtonInput<KeyCode>>,
+    focus: Res<FocusState>,
+    focusables: Query<&UiGlobalTransform, With<Focusable>>,
+    mut commands: Commands,
+) {
+    // Alt+Enter toggles fullscreen instead.
+    if !input.any_just_pressed([KeyCode::Enter, KeyCode::NumpadEnter])
+        || input.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+    {
+        return;
+    }
+    let Some((entity, transform)) = focus
+        .focused
+        .and_then(|entity| Some((entity, focusables.get(entity).ok()?)))
+    else {
+        return;
+    };
+
+    let location = Location {
+        target: NormalizedRenderTarget::None {
+            width: 0,
+            height: 0,
+        },
+        position: transform.translation,
+    };
+    let hit = HitData::new(Entity::PLACEHOLDER, 0.0, None, None);
+    commands.trigger(Pointer::new(
+        PointerId::Mouse,
+        location.clone(),
+        Click {
+            button: PointerButton::Primary,
+            hit: hit.clone(),
+            duration: Duration::ZERO,
+        },
+        entity,
+    ));
+    // Lets go of the button again, so it doesn't stay drawn as pressed.
+    commands.trigger(Pointer::new(
+        PointerId::Mouse,
+        location,
+        Release {
+            button: PointerButton::Primary,
+            hit,
+        },
+        entity,
+    ));
+}
+
+/// Draws the focused button as hovered, and puts back the one that lost focus.
+fn show_focus(
+    focus: Res<FocusState>,
+    mut previous: Local<Option<Entity>>,
+    mut palette_query: Query<(&InteractionPalette, &mut BackgroundColor), With<Focusable>>,
+) {
+    if !focus.is_changed() || *previous == focus.focused {
+        return;
+    }
+    if let Some((palette, mut bg)) = previous.and_then(|entity| palette_query.get_mut(entity).ok())
+    {
+        *bg = palette.none.into();
+    }
+    if let Some((palette, mut bg)) = focus
+        .focused
+        .and_then(|entity| palette_query.get_mut(entity).ok())
+    {
+        *bg = palette.hovered.into();
+    }
+    *previous = focus.focused;
+}
@@ -0,0 +1,135 @@
+//! Hover tooltips. Add [`Tooltip`] to any UI node and its text is shown next to the cursor while
+//! the node (or one of its children) is hovered.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::theme::palette::{CARD_BACKGROUND, CARD_BORDER, LABEL_TEXT};
+
+/// How far the tooltip sits from the cursor, in logical pixels.
+const TOOLTIP_OFFSET: f32 = 16.0;
+const TOOLTIP_MAX_WIDTH: f32 = 280.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<HoveredTooltip>();
+    app.add_systems(Startup, spawn_tooltip_overlay);
+    app.add_observer(show_tooltip_on_over);
+    app.add_observer(hide_tooltip_on_out);
+    app.add_systems(Update, update_tooltip_overlay);
+}
+
+/// Text shown next to the cursor while this node is hovered. UI that redraws when the game state
+/// changes gets live values for free, since the tooltip is read again every frame.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Tooltip(pub String);
+
+/// The node with a [`Tooltip`] currently under the cursor.
+#[derive(Resource, Debug, Default)]
+struct HoveredTooltip(Option<Entity>);
+
+#[derive(Component)]
+struct TooltipOverlay;
+
+#[derive(Component)]
+struct TooltipText;
+
+fn spawn_tooltip_overlay(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Tooltip"),
+        TooltipOverlay,
+        Node {
+            display: Display::None,
+            position_type: PositionType::Absolute,
+            max_width: px(TOOLTIP_MAX_WIDTH),
+            padding: UiRect::all(px(8)),
+            border: UiRect::all(px(1)),
+            border_radius: BorderRadius::all(px(6)),
+            ..default()
+        },
+        BackgroundColor(CARD_BACKGROUND),
+        BorderColor::all(CARD_BORDER),
+        GlobalZIndex(20),
+        Pickable::IGNORE,
+        children![(
+            TooltipText,
+            Text::default(),
+            TextFont::from_font_size(14.0),
+            TextColor(LABEL_TEXT),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+/// The closest node with a [`Tooltip`] at or above `entity`, so hovering a button inside a card
+/// still shows the card's tooltip.
+fn tooltip_owner(
+    entity: Entity,
+    tooltip_query: &Query<(), With<Tooltip>>,
+    parent_query: &Query<&ChildOf>,
+) -> Option<Entity> {
+    std::iter::once(entity)
+        .chain(parent_query.iter_ancestors(entity))
+        .find(|entity| tooltip_query.contains(*entity))
+}
+
+fn show_tooltip_on_over(
+    over: On<Pointer<Over>>,
+    tooltip_query: Query<(), With<Tooltip>>,
+    parent_query: Query<&ChildOf>,
+    mut hovered: ResMut<HoveredTooltip>,
+) {
+    if let Some(owner) = tooltip_owner(over.original_event_target(), &tooltip_query, &parent_query)
+    {
+        hovered.0 = Some(owner);
+    }
+}
+
+fn hide_tooltip_on_out(
+    out: On<Pointer<Out>>,
+    tooltip_query: Query<(), With<Tooltip>>,
+    parent_query: Query<&ChildOf>,
+    mut hovered: ResMut<HoveredTooltip>,
+) {
+    let owner = tooltip_owner(out.original_event_target(), &tooltip_query, &parent_query);
+    if owner.is_some() && hovered.0 == owner {
+        hovered.0 = None;
+    }
+}
+
+fn update_tooltip_overlay(
+    window: Single<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    tooltip_query: Query<&Tooltip>,
+    mut hovered: ResMut<HoveredTooltip>,
+    overlay: Single<(&mut Node, &ComputedNode), With<TooltipOverlay>>,
+    mut text: Single<&mut Text, With<TooltipText>>,
+) {
+    let (mut node, computed) = overlay.into_inner();
+    // The hovered node may have been despawned, e.g. by a redraw, without an `Out` event.
+    let tooltip = hovered.0.and_then(|entity| tooltip_query.get(entity).ok());
+    let (Some(tooltip), Some(cursor)) = (tooltip, window.cursor_position()) else {
+        if tooltip.is_none() {
+            hovered.0 = None;
+        }
+        node.display = Display::None;
+        return;
+    };
+
+    if text.0 != tooltip.0 {
+        text.0.clone_from(&tooltip.0);
+    }
+
+    // Flip to the other side of the cursor rather than running off the edge of the window.
+    let size = computed.size() * computed.inverse_scale_factor();
+    let mut position = cursor + Vec2::splat(TOOLTIP_OFFSET);
+    if position.x + size.x > window.width() {
+        position.x = cursor.x - TOOLTIP_OFFSET - size.x;
+    }
+    if position.y + size.y > window.height() {
+        position.y = cursor.y - TOOLTIP_OFFSET - size.y;
+    }
+    let position = position.max(Vec2::ZERO) / ui_scale.0;
+    node.display = Display::Flex;
+    node.left = px(position.x);
+    node.top = px(position.y);
+}
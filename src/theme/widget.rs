@@ -7,7 +7,7 @@ use bevy::{
     prelude::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::theme::{focus::Focusable, interaction::InteractionPalette, palette::*};
 
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
@@ -67,6 +67,33 @@ pub fn hud_label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// A horizontal meter for the HUD. `fill` goes on the inner node, whose width should be set as a
+/// percentage to show how full the meter is.
+pub fn hud_bar(fill: impl Bundle, color: Color) -> impl Bundle {
+    (
+        Name::new("Bar"),
+        Node {
+            width: px(200),
+            height: px(18),
+            padding: UiRect::all(px(3)),
+            border_radius: BorderRadius::all(px(9)),
+            ..default()
+        },
+        BackgroundColor(Color::WHITE.with_alpha(0.5)),
+        children![(
+            Name::new("Bar Fill"),
+            Node {
+                width: percent(100),
+                height: percent(100),
+                border_radius: BorderRadius::all(px(6)),
+                ..default()
+            },
+            BackgroundColor(color),
+            fill,
+        )],
+    )
+}
+
 pub fn panel() -> impl Bundle {
     (
         Name::new("Panel"),
@@ -129,6 +156,53 @@ pub fn divider() -> impl Bundle {
     )
 }
 
+/// A bar along the bottom of the screen listing keyboard shortcuts, given as `(key, action)` pairs.
+/// Meant as a direct child of a [`ui_root`].
+pub fn controls_footer(hints: &[(&'static str, &'static str)]) -> impl Bundle {
+    let hints = hints.to_vec();
+    (
+        Name::new("Controls Footer"),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(0),
+            width: percent(100),
+            padding: UiRect::axes(px(16), px(6)),
+            justify_content: JustifyContent::Center,
+            column_gap: px(28),
+            ..default()
+        },
+        // Opaque, so a menu's footer hides the one of the screen underneath it.
+        BackgroundColor(BACKGROUND),
+        Pickable::IGNORE,
+        Children::spawn(SpawnWith(move |parent: &mut ChildSpawner| {
+            for (key, action) in hints {
+                parent.spawn((
+                    Name::new("Control Hint"),
+                    Node {
+                        column_gap: px(8),
+                        ..default()
+                    },
+                    Pickable::IGNORE,
+                    children![
+                        (
+                            Text(key.to_string()),
+                            TextFont::from_font_size(18.0),
+                            TextColor(HEADER_TEXT),
+                            Pickable::IGNORE,
+                        ),
+                        (
+                            Text(action.to_string()),
+                            TextFont::from_font_size(18.0),
+                            TextColor(SUB_HEADER_TEXT),
+                            Pickable::IGNORE,
+                        ),
+                    ],
+                ));
+            }
+        })),
+    )
+}
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -230,6 +304,7 @@ where
                 .spawn((
                     Name::new("Button Inner"),
                     Button,
+                    Focusable,
                     BackgroundColor(BUTTON_BACKGROUND),
                     InteractionPalette {
                         none: BUTTON_BACKGROUND,